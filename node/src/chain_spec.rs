@@ -2,7 +2,8 @@ use sp_core::{Pair, Public, sr25519, crypto::UncheckedInto};
 use subsocial_runtime::{
 	AccountId, AuraConfig, BalancesConfig,
 	GenesisConfig, GrandpaConfig, UtilsConfig,
-	SudoConfig, SpacesConfig, SystemConfig,
+	SudoConfig, SpacesConfig, PostsConfig, ProfilesConfig, SystemConfig,
+	ProfileFollowsConfig, SpaceFollowsConfig, ReactionsConfig, ScoresConfig, RolesConfig,
 	WASM_BINARY, Signature, constants::currency::SMNS,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -228,6 +229,13 @@ fn testnet_genesis(
 		pallet_spaces: Some(SpacesConfig {
 			endowed_account: root_key,
 		}),
+		pallet_posts: Some(PostsConfig::default()),
+		pallet_profiles: Some(ProfilesConfig::default()),
+		pallet_profile_follows: Some(ProfileFollowsConfig::default()),
+		pallet_space_follows: Some(SpaceFollowsConfig::default()),
+		pallet_reactions: Some(ReactionsConfig::default()),
+		pallet_scores: Some(ScoresConfig::default()),
+		pallet_roles: Some(RolesConfig::default()),
         pallet_contracts: Some(ContractsConfig {
             current_schedule: pallet_contracts::Schedule {
                 enable_println,
@@ -14,6 +14,7 @@ use sp_block_builder::BlockBuilder;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_transaction_pool::TransactionPool;
 use pallet_contracts_rpc::{Contracts, ContractsApi};
+use pallet_scores_rpc::{Scores, ScoresApi};
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -36,6 +37,7 @@ pub fn create_full<C, P>(
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
     C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: pallet_scores_rpc::ScoresRuntimeApi<Block, AccountId>,
     P: TransactionPool + 'static,
 {
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -58,6 +60,9 @@ pub fn create_full<C, P>(
     io.extend_with(
         ContractsApi::to_delegate(Contracts::new(client.clone()))
     );
+    io.extend_with(
+        ScoresApi::to_delegate(Scores::new(client.clone()))
+    );
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
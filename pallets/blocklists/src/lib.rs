@@ -0,0 +1,146 @@
+//! # Blocklists Module
+//!
+//! The Blocklists module allows any account to maintain its personal lists of blocked
+//! and muted accounts.
+//!
+//! A blocked account is not able to interact with the blocker: to follow them, to reply to
+//! or to react on their posts. Other pallets check this via `df_traits::IsBlockedByAccount`.
+//!
+//! Muting has no effect on-chain: it is a signal for UIs to hide the content of muted accounts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, ensure,
+    dispatch::DispatchResult,
+    storage::IterableStorageDoubleMap,
+    traits::Get,
+};
+use frame_system::{self as system, ensure_signed};
+
+use df_traits::{CleanUpReapedAccount, IsBlockedByAccount};
+
+/// The pallet's configuration trait.
+pub trait Trait: system::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Trait> as BlocklistsModule {
+        /// Accounts (2nd key) blocked by an account (1st key).
+        pub AccountBlockedByAccount get(fn account_blocked_by_account):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Accounts (2nd key) muted by an account (1st key).
+        pub AccountMutedByAccount get(fn account_muted_by_account):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+    }
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Trait>::AccountId,
+    {
+        AccountBlocked(/* blocker */ AccountId, /* blocked */ AccountId),
+        AccountUnblocked(/* blocker */ AccountId, /* unblocked */ AccountId),
+        AccountMuted(/* muter */ AccountId, /* muted */ AccountId),
+        AccountUnmuted(/* muter */ AccountId, /* unmuted */ AccountId),
+    }
+);
+
+decl_error! {
+    pub enum Error for Module<T: Trait> {
+        /// Account can not block itself.
+        AccountCannotBlockItself,
+        /// Account is already blocked by this account.
+        AccountAlreadyBlocked,
+        /// Account is not blocked by this account.
+        AccountNotBlocked,
+
+        /// Account can not mute itself.
+        AccountCannotMuteItself,
+        /// Account is already muted by this account.
+        AccountAlreadyMuted,
+        /// Account is not muted by this account.
+        AccountNotMuted,
+    }
+}
+
+decl_module! {
+  pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+
+    // Initializing errors
+    type Error = Error<T>;
+
+    // Initializing events
+    fn deposit_event() = default;
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn block_account(origin, account: T::AccountId) -> DispatchResult {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(blocker != account, Error::<T>::AccountCannotBlockItself);
+      ensure!(!Self::account_blocked_by_account(&blocker, &account), Error::<T>::AccountAlreadyBlocked);
+
+      <AccountBlockedByAccount<T>>::insert(&blocker, &account, true);
+
+      Self::deposit_event(RawEvent::AccountBlocked(blocker, account));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn unblock_account(origin, account: T::AccountId) -> DispatchResult {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(Self::account_blocked_by_account(&blocker, &account), Error::<T>::AccountNotBlocked);
+
+      <AccountBlockedByAccount<T>>::remove(&blocker, &account);
+
+      Self::deposit_event(RawEvent::AccountUnblocked(blocker, account));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn mute_account(origin, account: T::AccountId) -> DispatchResult {
+      let muter = ensure_signed(origin)?;
+
+      ensure!(muter != account, Error::<T>::AccountCannotMuteItself);
+      ensure!(!Self::account_muted_by_account(&muter, &account), Error::<T>::AccountAlreadyMuted);
+
+      <AccountMutedByAccount<T>>::insert(&muter, &account, true);
+
+      Self::deposit_event(RawEvent::AccountMuted(muter, account));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn unmute_account(origin, account: T::AccountId) -> DispatchResult {
+      let muter = ensure_signed(origin)?;
+
+      ensure!(Self::account_muted_by_account(&muter, &account), Error::<T>::AccountNotMuted);
+
+      <AccountMutedByAccount<T>>::remove(&muter, &account);
+
+      Self::deposit_event(RawEvent::AccountUnmuted(muter, account));
+      Ok(())
+    }
+  }
+}
+
+impl<T: Trait> IsBlockedByAccount<T::AccountId> for Module<T> {
+    fn is_blocked_by_account(account: &T::AccountId, blocker: &T::AccountId) -> bool {
+        Self::account_blocked_by_account(blocker, account)
+    }
+}
+
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+    /// Remove blocklists and mutelists of a reaped account.
+    /// Blocks and mutes of this account by others are kept, as they are decisions of others.
+    fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+        let mut removed = <AccountBlockedByAccount<T>>::drain_prefix(who).take(limit as usize).count() as u32;
+        removed += <AccountMutedByAccount<T>>::drain_prefix(who).take((limit - removed) as usize).count() as u32;
+        removed
+    }
+}
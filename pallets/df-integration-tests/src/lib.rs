@@ -4,9 +4,12 @@ mod tests {
         assert_ok, assert_noop,
         impl_outer_origin, parameter_types,
         weights::Weight,
-        dispatch::DispatchResult,
-        storage::StorageMap,
+        dispatch::{DispatchError, DispatchResult},
+        storage::{StorageMap, StorageValue, migration::put_storage_value},
+        Blake2_128Concat, StorageHasher, Twox64Concat,
+        traits::OnInitialize,
     };
+    use codec::Encode;
     use sp_core::H256;
     use sp_io::TestExternalities;
     use sp_runtime::{
@@ -17,17 +20,21 @@ mod tests {
     };
     use frame_system::{self as system};
 
+    use pallet_blocklists::Error as BlocklistsError;
     use pallet_permissions::{
         SpacePermission,
         SpacePermission as SP,
         SpacePermissions,
     };
-    use pallet_posts::{PostId, Post, PostUpdate, PostExtension, Comment, Error as PostsError};
+    use pallet_posts::{PostId, Post, PostUpdate, PostExtension, Comment, Error as PostsError, mock_scheduler::MockScheduler};
     use pallet_profiles::{ProfileUpdate, Error as ProfilesError};
     use pallet_profile_follows::Error as ProfileFollowsError;
-    use pallet_reactions::{ReactionId, ReactionKind, PostReactionScores, Error as ReactionsError};
-    use pallet_scores::ScoringAction;
-    use pallet_spaces::{SpaceById, SpaceUpdate, Error as SpacesError};
+    use pallet_reactions::{
+        EmojiCode, ReactionId, ReactionKind, ReactionTarget, PostReactionScores, Error as ReactionsError,
+    };
+    use pallet_roles::Error as RolesError;
+    use pallet_scores::{ScoreContribution, ScoringAction};
+    use pallet_spaces::{Space, SpaceById, SpaceUpdate, Error as SpacesError};
     use pallet_space_follows::Error as SpaceFollowsError;
     use pallet_space_ownership::Error as SpaceOwnershipError;
     use pallet_moderation::{EntityId, EntityStatus, ReportId};
@@ -75,7 +82,7 @@ mod tests {
         type PalletInfo = ();
         type AccountData = pallet_balances::AccountData<u64>;
         type OnNewAccount = ();
-        type OnKilledAccount = ();
+        type OnKilledAccount = Profiles;
         type SystemWeightInfo = ();
     }
 
@@ -107,6 +114,8 @@ mod tests {
     parameter_types! {
       pub const MinHandleLen: u32 = 5;
       pub const MaxHandleLen: u32 = 50;
+      pub const MaxRawContentLen: u32 = 1024;
+      pub const RawContentDepositPerByte: u64 = RAW_CONTENT_DEPOSIT_PER_BYTE;
     }
 
     impl pallet_utils::Trait for TestRuntime {
@@ -114,6 +123,8 @@ mod tests {
         type Currency = Balances;
         type MinHandleLen = MinHandleLen;
         type MaxHandleLen = MaxHandleLen;
+        type MaxRawContentLen = MaxRawContentLen;
+        type RawContentDepositPerByte = RawContentDepositPerByte;
     }
 
     use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -122,16 +133,27 @@ mod tests {
         type DefaultSpacePermissions = DefaultSpacePermissions;
     }
 
+    impl pallet_blocklists::Trait for TestRuntime {
+        type Event = ();
+    }
+
     parameter_types! {
         pub const MaxCommentDepth: u32 = 10;
+        pub const MaxRepliesToProcessPerDeletePost: u16 = 100;
     }
 
     impl pallet_posts::Trait for TestRuntime {
         type Event = ();
         type MaxCommentDepth = MaxCommentDepth;
         type PostScores = Scores;
+        type MaxRepliesToProcessPerDeletePost = MaxRepliesToProcessPerDeletePost;
         type AfterPostUpdated = PostHistory;
+        type AfterPostDeleted = (Scores, Reactions, PostHistory);
         type IsPostBlocked = Moderation;
+        type IsBlockedByAccount = Blocklists;
+        type ScheduledCall = pallet_posts::Call<TestRuntime>;
+        type PalletsOrigin = system::RawOrigin<AccountId>;
+        type Scheduler = MockScheduler<TestRuntime>;
     }
 
     parameter_types! {}
@@ -144,37 +166,57 @@ mod tests {
         type Event = ();
         type BeforeAccountFollowed = Scores;
         type BeforeAccountUnfollowed = Scores;
+        type IsBlockedByAccount = Blocklists;
     }
 
-    parameter_types! {}
+    const PROFILE_HANDLE_DEPOSIT: u64 = 7;
+    parameter_types! {
+        pub const ProfileHandleDeposit: u64 = PROFILE_HANDLE_DEPOSIT;
+        pub const MaxReapedAccountEntriesPerBlock: u32 = 2;
+    }
 
     impl pallet_profiles::Trait for TestRuntime {
         type Event = ();
         type AfterProfileUpdated = ProfileHistory;
+        type HandleDeposit = ProfileHandleDeposit;
+        type IsHandleReserved = Spaces;
+        type ReapedAccountCleanup = (ProfileFollows, SpaceFollows, Roles, Blocklists, Scores, Reactions);
+        type MaxReapedAccountEntriesPerBlock = MaxReapedAccountEntriesPerBlock;
     }
 
     parameter_types! {}
 
     impl pallet_profile_history::Trait for TestRuntime {}
 
-    parameter_types! {}
+    parameter_types! {
+        pub SupportedEmojis: Vec<EmojiCode> = vec![EMOJI_THUMBS_UP, EMOJI_HEART];
+        pub const MaxReactionsToCleanUpPerBlock: u32 = 2;
+    }
 
     impl pallet_reactions::Trait for TestRuntime {
         type Event = ();
         type PostReactionScores = Scores;
+        type SupportedEmojis = SupportedEmojis;
+        type MaxReactionsToCleanUpPerBlock = MaxReactionsToCleanUpPerBlock;
+        type WeightInfo = ();
     }
 
     parameter_types! {
         pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+        pub const MaxExpiredRoleEntriesPerBlock: u32 = 40;
+        pub const MaxRoleEntriesToCleanUpPerBlock: u32 = 2;
     }
 
     impl pallet_roles::Trait for TestRuntime {
         type Event = ();
         type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+        type MaxExpiredRoleEntriesPerBlock = MaxExpiredRoleEntriesPerBlock;
+        type MaxRoleEntriesToCleanUpPerBlock = MaxRoleEntriesToCleanUpPerBlock;
         type Spaces = Spaces;
         type SpaceFollows = SpaceFollows;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
+        type Reputation = Scores;
     }
 
     parameter_types! {
@@ -189,6 +231,12 @@ mod tests {
         pub const ShareCommentActionWeight: i16 = 5;
         pub const UpvoteCommentActionWeight: i16 = 4;
         pub const DownvoteCommentActionWeight: i16 = -2;
+
+        pub const ReputationDecayPeriod: u64 = 100;
+        pub const ReputationDecayPercent: u8 = 10;
+        pub const ReputationDecayFloor: u32 = 1;
+        pub const MaxDecayedAccountsPerBlock: u32 = 2;
+        pub const MaxPostScoresToCleanUpPerBlock: u32 = 2;
     }
 
     impl pallet_scores::Trait for TestRuntime {
@@ -205,14 +253,25 @@ mod tests {
         type ShareCommentActionWeight = ShareCommentActionWeight;
         type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
         type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+        type ReputationDecayPeriod = ReputationDecayPeriod;
+        type ReputationDecayPercent = ReputationDecayPercent;
+        type ReputationDecayFloor = ReputationDecayFloor;
+        type MaxDecayedAccountsPerBlock = MaxDecayedAccountsPerBlock;
+        type MaxPostScoresToCleanUpPerBlock = MaxPostScoresToCleanUpPerBlock;
+
+        type UpdateWeightsOrigin = system::EnsureRoot<AccountId>;
     }
 
-    parameter_types! {}
+    parameter_types! {
+        pub const MaxSpaceFollowersToCleanUpPerBlock: u32 = 2;
+    }
 
     impl pallet_space_follows::Trait for TestRuntime {
         type Event = ();
         type BeforeSpaceFollowed = Scores;
         type BeforeSpaceUnfollowed = Scores;
+        type MaxSpaceFollowersToCleanUpPerBlock = MaxSpaceFollowersToCleanUpPerBlock;
     }
 
     parameter_types! {}
@@ -222,8 +281,10 @@ mod tests {
     }
 
     const HANDLE_DEPOSIT: u64 = 5;
+    const RAW_CONTENT_DEPOSIT_PER_BYTE: u64 = 1;
     parameter_types! {
         pub const HandleDeposit: u64 = HANDLE_DEPOSIT;
+        pub const MaxSpaceDepth: u32 = 5;
     }
 
     impl pallet_spaces::Trait for TestRuntime {
@@ -233,9 +294,13 @@ mod tests {
         type SpaceFollows = SpaceFollows;
         type BeforeSpaceCreated = SpaceFollows;
         type AfterSpaceUpdated = SpaceHistory;
+        type BeforeSpaceDeleted = SpaceFollows;
+        type AfterSpaceDeleted = (Roles, SpaceHistory, SpaceOwnership, Reactions, Moderation);
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
         type HandleDeposit = HandleDeposit;
+        type IsHandleReserved = Profiles;
+        type MaxSpaceDepth = MaxSpaceDepth;
     }
 
     parameter_types! {}
@@ -254,6 +319,7 @@ mod tests {
     type System = system::Module<TestRuntime>;
     type Balances = pallet_balances::Module<TestRuntime>;
 
+    type Blocklists = pallet_blocklists::Module<TestRuntime>;
     type Posts = pallet_posts::Module<TestRuntime>;
     type PostHistory = pallet_post_history::Module<TestRuntime>;
     type ProfileFollows = pallet_profile_follows::Module<TestRuntime>;
@@ -415,6 +481,11 @@ mod tests {
 
     const REACTION1: ReactionId = 1;
     const REACTION2: ReactionId = 2;
+    const REACTION3: ReactionId = 3;
+
+    const EMOJI_THUMBS_UP: EmojiCode = 0x1F44D;
+    const EMOJI_HEART: EmojiCode = 0x2764;
+    const EMOJI_UNSUPPORTED: EmojiCode = 0x1F4A9;
 
     /// Lowercase a handle and then try to find a space id by it.
     fn find_space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId> {
@@ -498,6 +569,10 @@ mod tests {
         ReactionKind::Downvote
     }
 
+    fn post1_target() -> ReactionTarget<AccountId> {
+        ReactionTarget::Post(POST1)
+    }
+
     fn scoring_action_upvote_post() -> ScoringAction {
         ScoringAction::UpvotePost
     }
@@ -609,6 +684,43 @@ mod tests {
         )
     }
 
+    fn _hide_space(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResult {
+        Spaces::hide_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
+    fn _unhide_space(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResult {
+        Spaces::unhide_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
+    fn _set_inherit_parent_permissions(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        inherit: bool,
+    ) -> DispatchResult {
+        Spaces::set_inherit_parent_permissions(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE2),
+            inherit,
+        )
+    }
+
+    fn _delete_default_space() -> DispatchResult {
+        _delete_space(None, None)
+    }
+
+    fn _delete_space(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResult {
+        Spaces::delete_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
     fn _default_follow_space() -> DispatchResult {
         _follow_space(None, None)
     }
@@ -620,6 +732,67 @@ mod tests {
         )
     }
 
+    /// All followers of a space, sorted to not depend on the order of storage iteration.
+    fn all_space_followers(space_id: SpaceId) -> Vec<AccountId> {
+        let mut followers = SpaceFollows::space_followers(space_id, 0, u32::MAX);
+        followers.sort();
+        followers
+    }
+
+    fn all_spaces_followed_by_account(account: AccountId) -> Vec<SpaceId> {
+        let mut space_ids = SpaceFollows::spaces_followed_by_account(&account, 0, u32::MAX);
+        space_ids.sort();
+        space_ids
+    }
+
+    fn all_follow_requests_to_space(space_id: SpaceId) -> Vec<AccountId> {
+        let mut requesters = SpaceFollows::follow_requests_to_space(space_id, 0, u32::MAX);
+        requesters.sort();
+        requesters
+    }
+
+    fn all_space_follow_requests_by_account(requester: AccountId) -> Vec<SpaceId> {
+        let mut space_ids = SpaceFollows::space_follow_requests_by_account(&requester, 0, u32::MAX);
+        space_ids.sort();
+        space_ids
+    }
+
+    fn _require_space_follow_approval() -> DispatchResult {
+        _set_space_follow_approval_required(None, None, true)
+    }
+
+    fn _set_space_follow_approval_required(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        required: bool,
+    ) -> DispatchResult {
+        SpaceFollows::set_follow_approval_required(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            required,
+        )
+    }
+
+    fn _accept_default_space_follow_request() -> DispatchResult {
+        _accept_space_follow_request(None, None)
+    }
+
+    fn _accept_space_follow_request(origin: Option<Origin>, requester: Option<AccountId>) -> DispatchResult {
+        SpaceFollows::accept_follow_request(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            requester.unwrap_or(ACCOUNT2),
+        )
+    }
+
+    fn _reject_default_space_follow_request() -> DispatchResult {
+        SpaceFollows::reject_follow_request(Origin::signed(ACCOUNT1), SPACE1, ACCOUNT2)
+    }
+
+    fn _cancel_default_space_follow_request() -> DispatchResult {
+        SpaceFollows::cancel_follow_request(Origin::signed(ACCOUNT2), SPACE1)
+    }
+
     fn _default_unfollow_space() -> DispatchResult {
         _unfollow_space(None, None)
     }
@@ -682,6 +855,20 @@ mod tests {
         )
     }
 
+    fn _delete_default_post() -> DispatchResult {
+        _delete_post(None, None)
+    }
+
+    fn _delete_post(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+    ) -> DispatchResult {
+        Posts::delete_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
     fn _create_default_comment() -> DispatchResult {
         _create_comment(None, None, None, None)
     }
@@ -717,6 +904,55 @@ mod tests {
         )
     }
 
+    const PUBLISH_AT: BlockNumber = 10;
+
+    fn _create_default_scheduled_post() -> DispatchResult {
+        _create_scheduled_post(None, None)
+    }
+
+    fn _create_scheduled_post(origin: Option<Origin>, publish_at: Option<BlockNumber>) -> DispatchResult {
+        Posts::create_scheduled_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            post_content_ipfs(),
+            publish_at.unwrap_or(PUBLISH_AT),
+        )
+    }
+
+    fn _hide_post(origin: Option<Origin>, post_id: Option<PostId>) -> DispatchResult {
+        Posts::hide_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _unhide_post(origin: Option<Origin>, post_id: Option<PostId>) -> DispatchResult {
+        Posts::unhide_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
+    fn _update_post_permissions(
+        origin: Option<Origin>,
+        post_id: Option<PostId>,
+        permissions: Option<SpacePermissions>,
+    ) -> DispatchResult {
+        Posts::update_post_permissions(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+            permissions,
+        )
+    }
+
+    /// Permission overrides that forbid a given permission for everyone.
+    fn permissions_where_nobody_can(permission: SpacePermission) -> SpacePermissions {
+        SpacePermissions {
+            none: Some(vec![permission].into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
     fn _create_default_post_reaction() -> DispatchResult {
         _create_post_reaction(None, None, None)
     }
@@ -788,6 +1024,39 @@ mod tests {
         _delete_post_reaction(origin, Some(post_id.unwrap_or(2)), reaction_id)
     }
 
+    fn _create_reaction(
+        origin: Option<Origin>,
+        target: ReactionTarget<AccountId>,
+        kind: Option<ReactionKind>,
+    ) -> DispatchResult {
+        Reactions::create_reaction(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            target,
+            kind.unwrap_or_else(reaction_upvote),
+        )
+    }
+
+    fn _allow_default_emoji_reactions() -> DispatchResult {
+        _set_allowed_space_reactions(None, None, Some(Some(vec![
+            ReactionKind::Upvote,
+            ReactionKind::Downvote,
+            ReactionKind::Emoji(EMOJI_THUMBS_UP),
+            ReactionKind::Emoji(EMOJI_HEART),
+        ])))
+    }
+
+    fn _set_allowed_space_reactions(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        reactions_opt: Option<Option<Vec<ReactionKind>>>,
+    ) -> DispatchResult {
+        Reactions::set_allowed_space_reactions(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            reactions_opt.unwrap_or_default(),
+        )
+    }
+
     fn _create_default_profile() -> DispatchResult {
         _create_profile(None, None)
     }
@@ -814,6 +1083,60 @@ mod tests {
         )
     }
 
+    fn profile_handle() -> Vec<u8> {
+        b"Profile_Handle".to_vec()
+    }
+
+    fn _update_default_profile_handle() -> DispatchResult {
+        _update_profile_handle(None, None)
+    }
+
+    fn _update_profile_handle(
+        origin: Option<Origin>,
+        handle_opt: Option<Option<Vec<u8>>>
+    ) -> DispatchResult {
+        Profiles::update_profile_handle(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            handle_opt.unwrap_or_else(|| Some(profile_handle())),
+        )
+    }
+
+    /// Lowercase a handle and then try to find a profile by it.
+    fn find_profile_id_by_handle(handle: Vec<u8>) -> Option<AccountId> {
+        let lc_handle = Utils::<TestRuntime>::lowercase_handle(handle);
+        Profiles::profile_id_by_handle(lc_handle)
+    }
+
+    fn _default_block_account() -> DispatchResult {
+        _block_account(None, None)
+    }
+
+    fn _block_account(origin: Option<Origin>, account: Option<AccountId>) -> DispatchResult {
+        Blocklists::block_account(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            account.unwrap_or(ACCOUNT2),
+        )
+    }
+
+    fn _default_unblock_account() -> DispatchResult {
+        Blocklists::unblock_account(Origin::signed(ACCOUNT1), ACCOUNT2)
+    }
+
+    fn _default_mute_account() -> DispatchResult {
+        _mute_account(None, None)
+    }
+
+    fn _mute_account(origin: Option<Origin>, account: Option<AccountId>) -> DispatchResult {
+        Blocklists::mute_account(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            account.unwrap_or(ACCOUNT2),
+        )
+    }
+
+    fn _default_unmute_account() -> DispatchResult {
+        Blocklists::unmute_account(Origin::signed(ACCOUNT1), ACCOUNT2)
+    }
+
     fn _default_follow_account() -> DispatchResult {
         _follow_account(None, None)
     }
@@ -825,6 +1148,47 @@ mod tests {
         )
     }
 
+    /// All followers of an account, sorted to not depend on the order of storage iteration.
+    fn all_account_followers(account: AccountId) -> Vec<AccountId> {
+        let mut followers = ProfileFollows::account_followers(&account, 0, u32::MAX);
+        followers.sort();
+        followers
+    }
+
+    fn all_accounts_followed_by_account(account: AccountId) -> Vec<AccountId> {
+        let mut account_ids = ProfileFollows::accounts_followed_by_account(&account, 0, u32::MAX);
+        account_ids.sort();
+        account_ids
+    }
+
+    fn all_follow_requests_to_account(account: AccountId) -> Vec<AccountId> {
+        let mut requesters = ProfileFollows::follow_requests_to_account(&account, 0, u32::MAX);
+        requesters.sort();
+        requesters
+    }
+
+    fn all_follow_requests_by_account(requester: AccountId) -> Vec<AccountId> {
+        let mut account_ids = ProfileFollows::follow_requests_by_account(&requester, 0, u32::MAX);
+        account_ids.sort();
+        account_ids
+    }
+
+    fn _require_account_follow_approval() -> DispatchResult {
+        ProfileFollows::set_follow_approval_required(Origin::signed(ACCOUNT1), true)
+    }
+
+    fn _accept_default_account_follow_request() -> DispatchResult {
+        ProfileFollows::accept_follow_request(Origin::signed(ACCOUNT1), ACCOUNT2)
+    }
+
+    fn _reject_default_account_follow_request() -> DispatchResult {
+        ProfileFollows::reject_follow_request(Origin::signed(ACCOUNT1), ACCOUNT2)
+    }
+
+    fn _cancel_default_account_follow_request() -> DispatchResult {
+        ProfileFollows::cancel_follow_request(Origin::signed(ACCOUNT2), ACCOUNT1)
+    }
+
     fn _default_unfollow_account() -> DispatchResult {
         _unfollow_account(None, None)
     }
@@ -1313,6 +1677,22 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_space_should_not_reserve_content_deposit_when_handle_is_not_unique() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let reserved_balance = Balances::reserved_balance(ACCOUNT1);
+
+            assert_noop!(_create_space(
+                None,
+                None,
+                Some(Content::Raw(b"Hello".to_vec())),
+                None
+            ), SpacesError::<TestRuntime>::SpaceHandleIsNotUnique);
+
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), reserved_balance);
+        });
+    }
+
     #[test]
     fn create_space_should_fail_when_handle_contains_at_char() {
         ExtBuilder::build().execute_with(|| {
@@ -1382,6 +1762,16 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_space_should_work_with_hyper_content() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_space(None, None, Some(valid_content_hyper()), None));
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.content, valid_content_hyper());
+        });
+    }
+
     #[test]
     fn update_space_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
@@ -1659,542 +2049,3113 @@ mod tests {
         });
     }
 
-    // Post tests
     #[test]
-    fn create_post_should_work() {
+    fn delete_space_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1 which is permitted by default
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::next_post_id(), POST2);
+            assert_ok!(_delete_default_space());
+            clean_up_followers_and_roles_of_deleted_spaces();
 
-            // Check whether data stored correctly
-            let post = Posts::post_by_id(POST1).unwrap();
+            // Check that the space was removed from all storages
+            assert!(Spaces::space_by_id(SPACE1).is_none());
+            assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+            assert!(find_space_id_by_handle(space_handle()).is_none());
 
-            assert_eq!(post.created.account, ACCOUNT1);
-            assert!(post.updated.is_none());
-            assert_eq!(post.hidden, false);
+            // Check that all followers have unfollowed the space
+            assert!(all_space_followers(SPACE1).is_empty());
+            assert!(all_spaces_followed_by_account(ACCOUNT1).is_empty());
+            assert!(all_spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count, 0);
 
-            assert_eq!(post.space_id, Some(SPACE1));
-            assert_eq!(post.extension, extension_regular_post());
+            // Check that the handle deposit has been unreserved:
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+        });
+    }
 
-            assert_eq!(post.content, post_content_ipfs());
+    fn clean_up_followers_and_roles_of_deleted_spaces() {
+        while SpaceFollows::first_deleted_space_index() != SpaceFollows::next_deleted_space_index()
+            || Roles::first_deleted_space_index() != Roles::next_deleted_space_index()
+        {
+            SpaceFollows::on_initialize(System::block_number());
+            Roles::on_initialize(System::block_number());
+        }
+    }
 
-            assert_eq!(post.replies_count, 0);
-            assert_eq!(post.hidden_replies_count, 0);
-            assert_eq!(post.shares_count, 0);
-            assert_eq!(post.upvotes_count, 0);
-            assert_eq!(post.downvotes_count, 0);
+    #[test]
+    fn delete_space_should_remove_its_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(|| {
+            assert_ok!(_delete_default_space());
+            clean_up_followers_and_roles_of_deleted_spaces();
 
-            assert_eq!(post.score, 0);
+            assert!(Roles::role_by_id(ROLE1).is_none());
+            assert!(Roles::role_by_id(ROLE2).is_none());
+            assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+            assert!(Roles::users_by_role_id(ROLE1).is_empty());
+            assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
+        });
+    }
 
-            assert!(PostHistory::edit_history(POST1).is_empty());
+    #[test]
+    fn delete_space_should_remove_its_roles_in_batches() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(|| {
+            // Roles are kept inside of a space deletion.
+            assert_ok!(_delete_default_space());
+            assert_eq!(Roles::role_ids_by_space_id(SPACE1), vec![ROLE1, ROLE2]);
+
+            // Only `MaxRoleEntriesToCleanUpPerBlock` (2) roles and users are removed per block.
+            Roles::on_initialize(System::block_number());
+            assert!(Roles::role_by_id(ROLE2).is_none());
+            assert!(Roles::role_by_id(ROLE1).is_some());
+            assert_eq!(Roles::role_ids_by_space_id(SPACE1), vec![ROLE1]);
+            assert_ne!(Roles::first_deleted_space_index(), Roles::next_deleted_space_index());
+
+            Roles::on_initialize(System::block_number());
+            assert!(Roles::role_by_id(ROLE1).is_none());
+            assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+            assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
+            assert_eq!(Roles::first_deleted_space_index(), Roles::next_deleted_space_index());
         });
     }
 
     #[test]
-    fn create_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1,
-                None, // RegularPost extension
-                None, // Default post content
-            ));
+    fn delete_space_should_remove_its_followers_in_batches() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
+
+            // Followers are kept inside of a space deletion.
+            assert_ok!(_delete_default_space());
+            assert_eq!(all_space_followers(SPACE1).len(), 3);
+
+            // Only `MaxSpaceFollowersToCleanUpPerBlock` (2) followers are removed per block.
+            SpaceFollows::on_initialize(System::block_number());
+            assert_eq!(all_space_followers(SPACE1).len(), 1);
+            assert_ne!(SpaceFollows::first_deleted_space_index(), SpaceFollows::next_deleted_space_index());
+
+            SpaceFollows::on_initialize(System::block_number());
+            assert!(all_space_followers(SPACE1).is_empty());
+            assert!(all_spaces_followed_by_account(ACCOUNT3).is_empty());
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().following_spaces_count, 0);
+            assert_eq!(SpaceFollows::first_deleted_space_index(), SpaceFollows::next_deleted_space_index());
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_post_has_no_space_id() {
+    fn delete_subspace_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_create_post(
-                None,
-                Some(None),
-                None,
-                None
-            ), PostsError::<TestRuntime>::PostHasNoSpaceId);
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_eq!(Spaces::subspace_ids_by_parent_id(SPACE1), vec![SPACE2]);
+
+            assert_ok!(_delete_space(None, Some(SPACE2)));
+
+            assert!(Spaces::space_by_id(SPACE2).is_none());
+            assert!(Spaces::subspace_ids_by_parent_id(SPACE1).is_empty());
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_space_not_found() {
+    fn delete_space_should_fail_when_space_not_found() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_default_post(), SpacesError::<TestRuntime>::SpaceNotFound);
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceNotFound);
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_ipfs_cid_is_invalid() {
+    fn delete_space_should_fail_when_account_is_not_space_owner() {
         ExtBuilder::build_with_space().execute_with(|| {
-            // Try to catch an error creating a regular post with invalid content
-            assert_noop!(_create_post(
-                None,
-                None,
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            assert_noop!(
+                _delete_space(Some(Origin::signed(ACCOUNT2)), None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_account_has_no_permission() {
+    fn delete_space_should_fail_when_space_has_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceHasPosts);
+        });
+    }
+
+    #[test]
+    fn delete_space_should_fail_when_space_has_subspaces() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            assert_noop!(_delete_default_space(), SpacesError::<TestRuntime>::SpaceHasSubspaces);
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_delete_default_role());
+    fn delete_subspace_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1,
-                None, // RegularPost extension
-                None, // Default post content
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert_noop!(
+                _delete_space(Some(Origin::signed(ACCOUNT2)), Some(SPACE2)),
+                SpacesError::<TestRuntime>::NoPermissionToDeleteAnySubspace
+            );
         });
     }
 
     #[test]
-    fn update_post_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let expected_content_ipfs = updated_post_content();
+    fn get_space_ancestors_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(Some(SPACE2)), Some(None), None, None)); // SpaceId 3
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(
-                None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
-                None,
-                Some(
-                    post_update(
-                        None,
-                        Some(expected_content_ipfs.clone()),
-                        Some(true)
-                    )
-                )
-            ));
+            let ancestor_ids: Vec<SpaceId> = Spaces::get_space_ancestors(SPACE2 + 1)
+                .iter().map(|space| space.id).collect();
 
-            // Check whether post updates correctly
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.space_id, Some(SPACE1));
-            assert_eq!(post.content, expected_content_ipfs);
-            assert_eq!(post.hidden, true);
+            assert_eq!(ancestor_ids, vec![SPACE2 + 1, SPACE2, SPACE1]);
+        });
+    }
 
-            // Check whether history recorded correctly
-            let post_history = PostHistory::edit_history(POST1)[0].clone();
-            assert!(post_history.old_data.space_id.is_none());
-            assert_eq!(post_history.old_data.content, Some(post_content_ipfs()));
-            assert_eq!(post_history.old_data.hidden, Some(false));
+    #[test]
+    fn create_subspace_should_fail_when_max_space_depth_reached() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let max_depth = MaxSpaceDepth::get();
+
+            // SPACE1 is the first level, so create subspaces for the rest of levels.
+            for parent_id in SPACE1..SPACE1 + max_depth as SpaceId - 1 {
+                assert_ok!(_create_subspace(None, Some(Some(parent_id)), Some(None), None, None));
+            }
+
+            let last_space_id = SPACE1 + max_depth as SpaceId - 1;
+            assert_noop!(
+                _create_subspace(None, Some(Some(last_space_id)), Some(None), None, None),
+                SpacesError::<TestRuntime>::MaxSpaceDepthReached
+            );
         });
     }
 
-    fn check_if_post_moved_correctly(
-        moved_post_id: PostId,
-        old_space_id: SpaceId,
-        expected_new_space_id: SpaceId
-    ) {
-        let post: Post<TestRuntime> = Posts::post_by_id(moved_post_id).unwrap(); // `POST2` is a comment
-        let new_space_id = post.space_id.unwrap();
+    #[test]
+    fn update_space_should_move_subspace_to_another_parent() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(None), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 3
 
-        // Check that space id of the post has been updated from 1 to 2
-        assert_eq!(new_space_id, expected_new_space_id);
+            let space_id = SPACE2 + 1;
+            assert_ok!(_update_space(
+                None,
+                Some(space_id),
+                Some(SpaceUpdate { parent_id: Some(Some(SPACE2)), ..space_update(None, None, None) })
+            ));
 
-        // Check that stats on the old space have been decreased
-        let old_space = Spaces::space_by_id(old_space_id).unwrap();
-        assert_eq!(old_space.posts_count, 0);
-        assert_eq!(old_space.hidden_posts_count, 0);
-        assert_eq!(old_space.score, 0);
+            assert_eq!(Spaces::space_by_id(space_id).unwrap().parent_id, Some(SPACE2));
+            assert!(Spaces::subspace_ids_by_parent_id(SPACE1).is_empty());
+            assert_eq!(Spaces::subspace_ids_by_parent_id(SPACE2), vec![space_id]);
+        });
+    }
 
-        // Check that stats on the new space have been increased
-        let new_space = Spaces::space_by_id(new_space_id).unwrap();
-        assert_eq!(new_space.posts_count, 1);
-        assert_eq!(new_space.hidden_posts_count, if post.hidden { 1 } else { 0 });
-        assert_eq!(new_space.score, post.score);
+    #[test]
+    fn update_space_should_fail_when_space_becomes_its_own_parent() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _update_space(
+                    None,
+                    None,
+                    Some(SpaceUpdate { parent_id: Some(Some(SPACE1)), ..space_update(None, None, None) })
+                ),
+                SpacesError::<TestRuntime>::SpaceCannotBeItsOwnAncestor
+            );
+        });
     }
 
     #[test]
-    fn move_post_should_work() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            assert_ok!(_move_post_1_to_space_2());
+    fn update_space_should_fail_when_space_moved_under_its_descendant() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(Some(SPACE2)), Some(None), None, None)); // SpaceId 3
 
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1;
-            let expected_new_space_id = SPACE2;
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+            assert_noop!(
+                _update_space(
+                    None,
+                    None,
+                    Some(SpaceUpdate { parent_id: Some(Some(SPACE2 + 1)), ..space_update(None, None, None) })
+                ),
+                SpacesError::<TestRuntime>::SpaceCannotBeItsOwnAncestor
+            );
+        });
+    }
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+    #[test]
+    fn update_space_should_fail_when_max_space_depth_reached() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let max_depth = MaxSpaceDepth::get();
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+            // Build a chain of the max depth starting from SPACE1.
+            for parent_id in SPACE1..SPACE1 + max_depth as SpaceId - 1 {
+                assert_ok!(_create_subspace(None, Some(Some(parent_id)), Some(None), None, None));
+            }
+
+            // Create a new root space with a subspace and try to put it under the deepest space.
+            let new_root_id = SPACE1 + max_depth as SpaceId;
+            assert_ok!(_create_subspace(None, Some(None), Some(None), None, None));
+            assert_ok!(_create_subspace(None, Some(Some(new_root_id)), Some(None), None, None));
+
+            assert_noop!(
+                _update_space(
+                    None,
+                    Some(new_root_id),
+                    Some(SpaceUpdate { parent_id: Some(Some(SPACE1 + max_depth as SpaceId - 1)), ..space_update(None, None, None) })
+                ),
+                SpacesError::<TestRuntime>::MaxSpaceDepthReached
+            );
         });
     }
 
     #[test]
-    fn move_post_should_work_when_space_id_none() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1; // Where post were before moving to `SpaceId:None`
-            let expected_new_space_id = SPACE2;
+    fn inherit_parent_permissions_should_apply_roles_of_parent_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            assert_ok!(_move_post_to_nowhere(moved_post_id));
-            assert_ok!(_move_post_1_to_space_2());
+            assert_noop!(
+                _create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
 
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+            assert_ok!(_set_inherit_parent_permissions(None, None, true));
+            assert!(Spaces::inherits_parent_permissions(SPACE2));
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None));
+        });
+    }
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+    #[test]
+    fn inherit_parent_permissions_should_be_reset_when_space_moved_to_another_parent() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(None), Some(None), None, None)); // SpaceId 3
+            assert_ok!(_set_inherit_parent_permissions(None, None, true));
+
+            assert_ok!(_update_space(
+                None,
+                Some(SPACE2),
+                Some(SpaceUpdate { parent_id: Some(Some(SPACE2 + 1)), ..space_update(None, None, None) })
+            ));
+
+            assert!(!Spaces::inherits_parent_permissions(SPACE2));
+            assert_noop!(
+                _create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
         });
     }
 
     #[test]
-    fn move_hidden_post_should_work() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1;
-            let expected_new_space_id = SPACE2;
+    fn inherit_parent_permissions_should_apply_overrides_of_parent_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let everyone_can_create_posts = SpacePermissions {
+                everyone: Some(vec![SP::CreatePosts].into_iter().collect()),
+                ..Default::default()
+            };
 
-            // Hide the post before moving it
-            assert_ok!(_update_post(
+            assert_ok!(_update_space(
                 None,
-                Some(moved_post_id),
-                Some(post_update(
-                    None,
-                    None,
-                    Some(true)
-                ))
+                None,
+                Some(SpaceUpdate {
+                    permissions: Some(Some(everyone_can_create_posts)),
+                    ..space_update(None, None, None)
+                })
             ));
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_set_inherit_parent_permissions(None, None, true));
 
-            assert_ok!(_move_post_1_to_space_2());
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None));
 
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+            assert_ok!(_set_inherit_parent_permissions(None, None, false));
+            assert!(!Spaces::inherits_parent_permissions(SPACE2));
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+            assert_noop!(
+                _create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
+        });
+    }
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+    #[test]
+    fn hide_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_hide_space(None, None));
+            assert!(Spaces::space_by_id(SPACE1).unwrap().hidden);
+
+            assert_ok!(_unhide_space(None, None));
+            assert!(!Spaces::space_by_id(SPACE1).unwrap().hidden);
         });
     }
 
     #[test]
-    fn move_hidden_post_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Note that we have not created a post that we are trying to move
-            assert_noop!(
-                _move_post_1_to_space_2(),
-                PostsError::<TestRuntime>::PostNotFound
-            );
+    fn hide_subspace_should_work_when_parent_space_owner_hides_any_subspace() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+
+            assert_ok!(_hide_space(None, Some(SPACE2)));
+            assert!(Spaces::space_by_id(SPACE2).unwrap().hidden);
         });
     }
 
     #[test]
-    fn move_hidden_post_should_fail_when_provided_space_not_found() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Note that we have not created a new space #2 before moving the post
+    fn hide_space_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
             assert_noop!(
-                _move_post_1_to_space_2(),
-                SpacesError::<TestRuntime>::SpaceNotFound
+                _hide_space(Some(Origin::signed(ACCOUNT2)), None),
+                SpacesError::<TestRuntime>::NoPermissionToUpdateSpace
             );
         });
     }
 
     #[test]
-    fn move_hidden_post_should_fail_origin_has_no_permission_to_create_posts() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Create a space #2 from account #2
-            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
+    fn hide_subspace_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            // Should not be possible to move the post b/c it's owner is account #1
-            // when the space #2 is owned by account #2
             assert_noop!(
-                _move_post_1_to_space_2(),
-                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+                _hide_space(Some(Origin::signed(ACCOUNT2)), Some(SPACE2)),
+                SpacesError::<TestRuntime>::NoPermissionToHideAnySubspace
             );
         });
     }
 
     #[test]
-    fn move_post_should_fail_when_account_has_no_permission() {
-        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
-            assert_noop!(
-                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
-                PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
-            );
+    fn unhide_space_should_fail_when_space_is_not_hidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_unhide_space(None, None), SpacesError::<TestRuntime>::SpaceIsNotHidden);
         });
     }
 
     #[test]
-    fn move_post_should_fail_when_space_none_and_account_is_not_post_owner() {
-        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
-            assert_ok!(_move_post_to_nowhere(POST1));
+    fn set_inherit_parent_permissions_should_fail_when_space_is_at_root() {
+        ExtBuilder::build_with_space().execute_with(|| {
             assert_noop!(
-                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
-                PostsError::<TestRuntime>::NotAPostOwner
+                _set_inherit_parent_permissions(None, Some(SPACE1), true),
+                SpacesError::<TestRuntime>::SpaceIsAtRoot
             );
         });
     }
 
     #[test]
-    fn should_fail_when_trying_to_move_comment() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            assert_ok!(_create_space(None, Some(None), None, None));
+    fn set_inherit_parent_permissions_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
 
-            // Comments cannot be moved, they stick to their parent post
             assert_noop!(
-                _move_post(None, Some(POST2), None),
-                PostsError::<TestRuntime>::CannotUpdateSpaceIdOnComment
+                _set_inherit_parent_permissions(Some(Origin::signed(ACCOUNT2)), None, true),
+                SpacesError::<TestRuntime>::NoPermissionToUpdateSpace
             );
         });
     }
 
     #[test]
-    fn update_post_should_work_after_transfer_space_ownership() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let post_update = post_update(
+    fn subspace_tree_depth_should_decrease_when_deepest_subspace_moved_out() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(Some(SPACE2)), Some(None), None, None)); // SpaceId 3
+            assert_eq!(Spaces::get_subspace_tree_depth(SPACE1), 3);
+
+            assert_ok!(_update_space(
                 None,
-                Some(updated_post_content()),
-                Some(true),
-            );
+                Some(SPACE2 + 1),
+                Some(SpaceUpdate { parent_id: Some(None), ..space_update(None, None, None) })
+            ));
 
-            assert_ok!(_transfer_default_space_ownership());
+            assert_eq!(Spaces::get_subspace_tree_depth(SPACE1), 2);
+            assert_eq!(Spaces::get_subspace_tree_depth(SPACE2), 1);
+            assert_eq!(Spaces::subspace_counts_by_tree_depth(SPACE1), vec![1]);
+        });
+    }
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(None, None, Some(post_update)));
+    #[test]
+    fn subspace_tree_depth_should_decrease_when_deepest_subspace_deleted() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 3
+            assert_ok!(_create_subspace(None, Some(Some(SPACE2)), Some(None), None, None)); // SpaceId 4
+            assert_eq!(Spaces::subspace_counts_by_tree_depth(SPACE1), vec![1, 1]);
+
+            assert_ok!(_delete_space(None, Some(SPACE2 + 2)));
+
+            assert_eq!(Spaces::get_subspace_tree_depth(SPACE1), 2);
+            assert_eq!(Spaces::subspace_counts_by_tree_depth(SPACE1), vec![2]);
+            assert!(Spaces::subspace_counts_by_tree_depth(SPACE2).is_empty());
         });
     }
 
+    // Post tests
     #[test]
-    fn update_any_post_should_work_when_account_has_default_permission() {
+    fn create_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1 which is permitted by default
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::next_post_id(), POST2);
+
+            // Check whether data stored correctly
+            let post = Posts::post_by_id(POST1).unwrap();
+
+            assert_eq!(post.created.account, ACCOUNT1);
+            assert!(post.updated.is_none());
+            assert_eq!(post.hidden, false);
+
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.extension, extension_regular_post());
+
+            assert_eq!(post.content, post_content_ipfs());
+
+            assert_eq!(post.replies_count, 0);
+            assert_eq!(post.hidden_replies_count, 0);
+            assert_eq!(post.shares_count, 0);
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 0);
+
+            assert_eq!(post.score, 0);
+
+            assert!(PostHistory::edit_history(POST1).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_when_one_of_roles_is_permitted() {
         ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
-            );
             assert_ok!(_create_post(
                 Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1
+                None, // SpaceId 1,
                 None, // RegularPost extension
-                None // Default post content
-            )); // PostId 1
+                None, // Default post content
+            ));
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_post_has_no_space_id() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_post(
+                None,
+                Some(None),
+                None,
+                None
+            ), PostsError::<TestRuntime>::PostHasNoSpaceId);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_default_post(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            // Try to catch an error creating a regular post with invalid content
+            assert_noop!(_create_post(
+                None,
+                None,
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_with_hyper_content() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(None, None, None, Some(valid_content_hyper())));
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.content, valid_content_hyper());
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_hyper_id_is_invalid() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_post(
+                None,
+                None,
+                None,
+                Some(invalid_content_hyper())
+            ), UtilsError::<TestRuntime>::InvalidHypercoreId);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_delete_default_role());
+
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1,
+                None, // RegularPost extension
+                None, // Default post content
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn update_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let expected_content_ipfs = updated_post_content();
 
             // Post update with ID 1 should be fine
             assert_ok!(_update_post(
-                None, // From ACCOUNT1 (has default permission to UpdateAnyPosts as SpaceOwner)
-                Some(POST1),
-                Some(post_update)
+                None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
+                None,
+                Some(
+                    post_update(
+                        None,
+                        Some(expected_content_ipfs.clone()),
+                        Some(true)
+                    )
+                )
+            ));
+
+            // Check whether post updates correctly
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.content, expected_content_ipfs);
+            assert_eq!(post.hidden, true);
+
+            // Check whether history recorded correctly
+            let post_history = PostHistory::edit_history(POST1)[0].clone();
+            assert!(post_history.old_data.space_id.is_none());
+            assert_eq!(post_history.old_data.content, Some(post_content_ipfs()));
+            assert_eq!(post_history.old_data.hidden, Some(false));
+        });
+    }
+
+    fn check_if_post_moved_correctly(
+        moved_post_id: PostId,
+        old_space_id: SpaceId,
+        expected_new_space_id: SpaceId
+    ) {
+        let post: Post<TestRuntime> = Posts::post_by_id(moved_post_id).unwrap(); // `POST2` is a comment
+        let new_space_id = post.space_id.unwrap();
+
+        // Check that space id of the post has been updated from 1 to 2
+        assert_eq!(new_space_id, expected_new_space_id);
+
+        // Check that stats on the old space have been decreased
+        let old_space = Spaces::space_by_id(old_space_id).unwrap();
+        assert_eq!(old_space.posts_count, 0);
+        assert_eq!(old_space.hidden_posts_count, 0);
+        assert_eq!(old_space.score, 0);
+
+        // Check that stats on the new space have been increased
+        let new_space = Spaces::space_by_id(new_space_id).unwrap();
+        assert_eq!(new_space.posts_count, 1);
+        assert_eq!(new_space.hidden_posts_count, if post.hidden { 1 } else { 0 });
+        assert_eq!(new_space.score, post.score);
+    }
+
+    #[test]
+    fn move_post_should_work() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_move_post_1_to_space_2());
+
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1;
+            let expected_new_space_id = SPACE2;
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+        });
+    }
+
+    #[test]
+    fn move_post_should_work_when_space_id_none() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1; // Where post were before moving to `SpaceId:None`
+            let expected_new_space_id = SPACE2;
+
+            assert_ok!(_move_post_to_nowhere(moved_post_id));
+            assert_ok!(_move_post_1_to_space_2());
+
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+        });
+    }
+
+    #[test]
+    fn move_hidden_post_should_work() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1;
+            let expected_new_space_id = SPACE2;
+
+            // Hide the post before moving it
+            assert_ok!(_update_post(
+                None,
+                Some(moved_post_id),
+                Some(post_update(
+                    None,
+                    None,
+                    Some(true)
+                ))
             ));
+
+            assert_ok!(_move_post_1_to_space_2());
+
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+        });
+    }
+
+    #[test]
+    fn move_hidden_post_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Note that we have not created a post that we are trying to move
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                PostsError::<TestRuntime>::PostNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn move_hidden_post_should_fail_when_provided_space_not_found() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Note that we have not created a new space #2 before moving the post
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                SpacesError::<TestRuntime>::SpaceNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn move_hidden_post_should_fail_origin_has_no_permission_to_create_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Create a space #2 from account #2
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
+
+            // Should not be possible to move the post b/c it's owner is account #1
+            // when the space #2 is owned by account #2
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
+        });
+    }
+
+    #[test]
+    fn move_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_noop!(
+                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+            );
+        });
+    }
+
+    #[test]
+    fn move_post_should_fail_when_space_none_and_account_is_not_post_owner() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_noop!(
+                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NotAPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn should_fail_when_trying_to_move_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None));
+
+            // Comments cannot be moved, they stick to their parent post
+            assert_noop!(
+                _move_post(None, Some(POST2), None),
+                PostsError::<TestRuntime>::CannotUpdateSpaceIdOnComment
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_should_work_after_transfer_space_ownership() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let post_update = post_update(
+                None,
+                Some(updated_post_content()),
+                Some(true),
+            );
+
+            assert_ok!(_transfer_default_space_ownership());
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(None, None, Some(post_update)));
+        });
+    }
+
+    #[test]
+    fn update_any_post_should_work_when_account_has_default_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            let post_update = post_update(
+                None,
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1
+                None, // RegularPost extension
+                None // Default post content
+            )); // PostId 1
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(
+                None, // From ACCOUNT1 (has default permission to UpdateAnyPosts as SpaceOwner)
+                Some(POST1),
+                Some(post_update)
+            ));
+        });
+    }
+
+    #[test]
+    fn update_any_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
+            let post_update = post_update(
+                None,
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_default_post()); // PostId 1
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update)
+            ));
+        });
+    }
+
+    #[test]
+    fn update_any_post_should_charge_editor_for_raw_content() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
+            let content = Content::Raw(b"Hello".to_vec());
+            let updated_content = Content::Raw(b"Hello, Subsocial!".to_vec());
+            let deposit = Utils::<TestRuntime>::content_deposit(&content);
+            let updated_deposit = Utils::<TestRuntime>::content_deposit(&updated_content);
+
+            assert_ok!(_create_post(None, None, None, Some(content))); // PostId 1
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), deposit);
+
+            assert_ok!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update(None, Some(updated_content), None))
+            ));
+
+            // The editor pays for the new content and the author gets the old deposit back.
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+            assert_eq!(Balances::reserved_balance(ACCOUNT2), updated_deposit);
+
+            // The deposit is returned to the editor when the post is deleted.
+            assert_ok!(_delete_post(None, None));
+            assert!(Balances::reserved_balance(ACCOUNT2).is_zero());
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_no_updates_for_post_provided() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error updating a post with no changes
+            assert_noop!(_update_post(None, None, None), PostsError::<TestRuntime>::NoUpdatesForPost);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_post_not_found() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
+
+            // Try to catch an error updating a post with wrong post ID
+            assert_noop!(_update_post(
+                None,
+                Some(POST2),
+                Some(
+                    post_update(
+                        // FIXME: when Post's `space_id` update is fully implemented
+                        None/*Some(SPACE2)*/,
+                        None,
+                        Some(true)/*None*/
+                    )
+                )
+            ), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_account_has_no_permission_to_update_any_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
+
+            // Try to catch an error updating a post with different account
+            assert_noop!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(
+                    post_update(
+                        // FIXME: when Post's `space_id` update is fully implemented
+                        None/*Some(SPACE2)*/,
+                        None,
+                        Some(true)/*None*/
+                    )
+                )
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error updating a post with invalid content
+            assert_noop!(_update_post(
+                None,
+                None,
+                Some(
+                    post_update(
+                        None,
+                        Some(invalid_content_ipfs()),
+                        None
+                    )
+                )
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
+            let post_update = post_update(
+                None,
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_default_post());
+            // PostId 1
+            assert_ok!(_delete_default_role());
+
+            // Post update with ID 1 should be fine
+            assert_noop!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update)
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_work() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2
+
+            assert_ok!(_delete_default_post());
+            clean_up_reactions_on_deleted_targets();
+
+            // Check that the post and its replies were removed from all storages
+            assert!(Posts::post_by_id(POST1).is_none());
+            assert!(Posts::post_by_id(POST2).is_none());
+            assert!(Posts::post_ids_by_space_id(SPACE1).is_empty());
+            assert!(Posts::reply_ids_by_post_id(POST1).is_empty());
+
+            // Check that the space counters were updated
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 0);
+            assert_eq!(space.hidden_posts_count, 0);
+            assert_eq!(space.score, 0);
+
+            // Check that reactions and edit history of the post were cleaned up
+            assert!(Reactions::reaction_ids_by_post_id(POST1).is_empty());
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(!Reactions::has_reacted(&ACCOUNT1, &post1_target(), reaction_upvote()));
+            assert!(PostHistory::edit_history(POST1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_hidden_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+            assert_ok!(_delete_default_post());
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 0);
+            assert_eq!(space.hidden_posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn delete_sharing_post_should_work() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(
+                None,
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 2
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+
+            assert!(Posts::post_by_id(POST2).is_none());
+            assert!(Posts::shared_post_ids_by_original_post_id(POST1).is_empty());
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 0);
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn shares_should_leave_index_when_deleted_after_original_post() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(
+                None,
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 2
+
+            // The shares index is not cleared with the original post, as it can be too large.
+            assert_ok!(_delete_default_post());
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+            assert_eq!(Posts::next_shared_post_index_by_original_post_id(POST1), 0);
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+            assert!(Posts::shared_post_ids_by_original_post_id(POST1).is_empty());
+            assert!(Posts::shared_post_index_by_shared_post_id(POST2).is_none());
+        });
+    }
+
+    #[test]
+    fn delete_post_should_work_when_space_id_none() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_ok!(_delete_default_post());
+
+            assert!(Posts::post_by_id(POST1).is_none());
+        });
+    }
+
+    #[test]
+    fn delete_post_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_delete_default_post(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToDeleteAnyPost
+            );
+        });
+    }
+
+    #[test]
+    fn delete_post_should_fail_when_space_none_and_account_is_not_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NotAPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let permissions = permissions_where_nobody_can(SP::CreateComments);
+            assert_ok!(_update_post_permissions(None, None, Some(permissions.clone())));
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.permissions, Some(permissions));
+            assert!(post.updated.is_some());
+
+            // Reset overrides to permissions of the space:
+            assert_ok!(_update_post_permissions(None, None, None));
+            assert!(Posts::post_by_id(POST1).unwrap().permissions.is_none());
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_nothing_to_update() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(None, None, None),
+                PostsError::<TestRuntime>::NoUpdatesForPost
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(
+                    Some(Origin::signed(ACCOUNT2)),
+                    None,
+                    Some(permissions_where_nobody_can(SP::CreateComments))
+                ),
+                PostsError::<TestRuntime>::NoPermissionToOverridePostPermissions
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_permissions_should_fail_when_post_is_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _update_post_permissions(None, Some(POST2), Some(permissions_where_nobody_can(SP::Upvote))),
+                PostsError::<TestRuntime>::CannotOverridePermissionsOnComment
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_comments_are_forbidden_by_post_permissions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_permissions(None, None, Some(permissions_where_nobody_can(SP::CreateComments))));
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreateComments
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_downvotes_are_forbidden_by_post_permissions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post_permissions(None, None, Some(permissions_where_nobody_can(SP::Downvote))));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::NoPermissionToDownvote
+            );
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_upvote())));
+        });
+    }
+
+    #[test]
+    fn hide_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_hide_post(None, None));
+
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 1);
+
+            assert_ok!(_unhide_post(None, None));
+
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn hide_comment_should_work_when_space_owner_hides_any_comment() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
+
+            assert_ok!(_hide_post(None, Some(POST2)));
+
+            assert!(Posts::post_by_id(POST2).unwrap().hidden);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().hidden_replies_count, 1);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn hide_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _hide_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToHideAnyPost
+            );
+        });
+    }
+
+    #[test]
+    fn hide_comment_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _hide_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)),
+                PostsError::<TestRuntime>::NoPermissionToHideAnyComment
+            );
+        });
+    }
+
+    #[test]
+    fn hide_post_should_fail_when_post_is_already_hidden() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_hide_post(None, None));
+            assert_noop!(_hide_post(None, None), PostsError::<TestRuntime>::PostIsAlreadyHidden);
+        });
+    }
+
+    #[test]
+    fn unhide_post_should_fail_when_post_is_not_hidden() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(_unhide_post(None, None), PostsError::<TestRuntime>::PostIsNotHidden);
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert!(post.hidden);
+            assert_eq!(Posts::scheduled_post_publish_at(POST1), Some(PUBLISH_AT));
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 1);
+            assert_eq!(space.hidden_posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_fail_when_publish_at_is_not_in_future() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_scheduled_post(None, Some(System::block_number())),
+                PostsError::<TestRuntime>::PublishAtShouldBeInFuture
+            );
+        });
+    }
+
+    #[test]
+    fn publish_scheduled_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_ok!(Posts::publish_scheduled_post(Origin::root(), POST1));
+
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert!(Posts::scheduled_post_publish_at(POST1).is_none());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 0);
+        });
+    }
+
+    #[test]
+    fn scheduler_should_publish_post_at_publish_at_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            MockScheduler::<TestRuntime>::dispatch_due_tasks(PUBLISH_AT - 1);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+
+            MockScheduler::<TestRuntime>::dispatch_due_tasks(PUBLISH_AT);
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
+            assert!(Posts::scheduled_post_publish_at(POST1).is_none());
+        });
+    }
+
+    #[test]
+    fn scheduler_should_not_publish_canceled_post() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+            assert_ok!(Posts::cancel_scheduled_post(Origin::signed(ACCOUNT1), POST1));
+
+            MockScheduler::<TestRuntime>::dispatch_due_tasks(PUBLISH_AT);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+        });
+    }
+
+    #[test]
+    fn scheduler_should_not_publish_post_hidden_by_moderator() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideAnyPost]).execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            // The author publishes the post earlier and then a moderator hides it.
+            assert_ok!(_unhide_post(None, None));
+            assert!(Posts::scheduled_post_publish_at(POST1).is_none());
+            assert_ok!(_hide_post(Some(Origin::signed(ACCOUNT2)), None));
+
+            MockScheduler::<TestRuntime>::dispatch_due_tasks(PUBLISH_AT);
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn create_scheduled_post_should_not_schedule_when_post_cannot_be_created() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _create_scheduled_post(Some(Origin::signed(ACCOUNT2)), None),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
+        });
+    }
+
+    #[test]
+    fn publish_scheduled_post_should_fail_when_origin_is_not_root() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_noop!(
+                Posts::publish_scheduled_post(Origin::signed(ACCOUNT1), POST1),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn reschedule_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_ok!(Posts::reschedule_post(Origin::signed(ACCOUNT1), POST1, PUBLISH_AT + 5));
+            assert_eq!(Posts::scheduled_post_publish_at(POST1), Some(PUBLISH_AT + 5));
+        });
+    }
+
+    #[test]
+    fn reschedule_post_should_fail_when_account_is_not_post_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_noop!(
+                Posts::reschedule_post(Origin::signed(ACCOUNT2), POST1, PUBLISH_AT + 5),
+                PostsError::<TestRuntime>::NotAPostOwner
+            );
+        });
+    }
+
+    #[test]
+    fn cancel_scheduled_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_ok!(Posts::cancel_scheduled_post(Origin::signed(ACCOUNT1), POST1));
+
+            assert!(Posts::scheduled_post_publish_at(POST1).is_none());
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+
+            assert_noop!(
+                Posts::publish_scheduled_post(Origin::root(), POST1),
+                PostsError::<TestRuntime>::PostIsNotScheduled
+            );
+        });
+    }
+
+    #[test]
+    fn delete_post_should_cancel_scheduled_publishing() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_scheduled_post()); // PostId 1
+
+            assert_ok!(_delete_default_post());
+            assert!(Posts::scheduled_post_publish_at(POST1).is_none());
+        });
+    }
+
+    #[test]
+    fn latest_post_ids_by_space_id_should_be_paginated() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            for _ in 0..5 {
+                assert_ok!(_create_default_post());
+            }
+            assert_ok!(_delete_post(None, Some(3)));
+
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![1, 2, 4, 5]);
+            assert!(Posts::space_post_index_by_post_id(3).is_none());
+
+            let (first_page, cursor) = Posts::latest_post_ids_by_space_id(SPACE1, None, 2);
+            assert_eq!(first_page, vec![5, 4]);
+            assert_eq!(cursor, Some(3));
+
+            // The deleted post leaves a gap that is skipped
+            let (second_page, cursor) = Posts::latest_post_ids_by_space_id(SPACE1, cursor, 2);
+            assert_eq!(second_page, vec![2, 1]);
+            assert_eq!(cursor, None);
+        });
+    }
+
+    #[test]
+    fn latest_post_ids_by_space_id_should_stop_scan_at_many_gaps() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            for _ in 0..6 {
+                assert_ok!(_create_default_post());
+            }
+            for post_id in 2..=5 {
+                assert_ok!(_delete_post(None, Some(post_id)));
+            }
+
+            let (first_page, cursor) = Posts::latest_post_ids_by_space_id(SPACE1, None, 1);
+            assert_eq!(first_page, vec![6]);
+            assert_eq!(cursor, Some(5));
+
+            // Only 4 indexes are scanned per requested post, so the scan stops in the gap
+            let (second_page, cursor) = Posts::latest_post_ids_by_space_id(SPACE1, cursor, 1);
+            assert!(second_page.is_empty());
+            assert_eq!(cursor, Some(1));
+
+            let (third_page, cursor) = Posts::latest_post_ids_by_space_id(SPACE1, cursor, 1);
+            assert_eq!(third_page, vec![1]);
+            assert_eq!(cursor, None);
+        });
+    }
+
+    #[test]
+    fn latest_reply_ids_by_post_id_should_return_newest_replies_first() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            for _ in 0..3 {
+                assert_ok!(_create_default_comment());
+            }
+
+            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![2, 3, 4]);
+            assert_eq!(Posts::latest_reply_ids_by_post_id(POST1, None, 10), (vec![4, 3, 2], None));
+        });
+    }
+
+    // Comment tests
+    #[test]
+    fn create_comment_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2 by ACCOUNT1 which is permitted by default
+
+            // Check storages
+            let root_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![POST2]);
+            assert_eq!(root_post.replies_count, 1);
+            assert_eq!(root_post.hidden_replies_count, 0);
+
+            // Check whether data stored correctly
+            let comment = Posts::post_by_id(POST2).unwrap();
+            let comment_ext = comment.get_comment_ext().unwrap();
+
+            assert!(comment_ext.parent_id.is_none());
+            assert_eq!(comment_ext.root_post_id, POST1);
+            assert_eq!(comment.created.account, ACCOUNT1);
+            assert!(comment.updated.is_none());
+            assert_eq!(comment.content, comment_content_ipfs());
+            assert_eq!(comment.replies_count, 0);
+            assert_eq!(comment.hidden_replies_count, 0);
+            assert_eq!(comment.shares_count, 0);
+            assert_eq!(comment.upvotes_count, 0);
+            assert_eq!(comment.downvotes_count, 0);
+            assert_eq!(comment.score, 0);
+
+            assert!(PostHistory::edit_history(POST2).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_comment_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let penultimate_comment_id: PostId = 8;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            for comment_id in first_comment_id..penultimate_comment_id as PostId {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                let replies_should_be = last_comment_id - comment_id;
+                assert_eq!(comment.replies_count, replies_should_be as u16);
+                assert_eq!(Posts::reply_ids_by_post_id(comment_id), vec![comment_id + 1]);
+
+                assert_eq!(comment.hidden_replies_count, 0);
+            }
+
+            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
+            assert_eq!(last_comment.replies_count, 0);
+            assert!(Posts::reply_ids_by_post_id(last_comment_id).is_empty());
+
+            assert_eq!(last_comment.hidden_replies_count, 0);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error creating a comment with wrong post
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_parent_comment_is_unknown() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error creating a comment with wrong parent
+            assert_noop!(_create_comment(
+                None,
+                None,
+                Some(Some(POST2)),
+                None
+            ), PostsError::<TestRuntime>::UnknownParentComment);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error creating a comment with wrong parent
+            assert_noop!(_create_comment(
+                None,
+                None,
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_trying_to_create_in_hidden_space_scope() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_trying_create_in_hidden_post_scope() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_max_comment_depth_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+
+            for parent_id in 2..11 as PostId {
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None)); // PostId N (last = 10)
+            }
+
+            // Some(Some(11)) - here is parent_id 11 of type PostId
+            assert_noop!(_create_comment(
+                None,
+                None,
+                Some(Some(11)),
+                None
+            ), PostsError::<TestRuntime>::MaxCommentDepthReached);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_work() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_comment(None, None, None));
+
+            // Check whether post updates correctly
+            let comment = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(comment.content, reply_content_ipfs());
+
+            // Check whether history recorded correctly
+            assert_eq!(PostHistory::edit_history(POST2)[0].old_data.content, Some(comment_content_ipfs()));
+        });
+    }
+
+    #[test]
+    fn update_comment_hidden_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let penultimate_comment_id: PostId = 8;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            assert_ok!(_update_comment(
+                None,
+                Some(last_comment_id),
+                Some(post_update(
+                    None,
+                    None,
+                    Some(true) // make comment hidden
+                ))
+            ));
+
+            for comment_id in first_comment_id..penultimate_comment_id as PostId {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                assert_eq!(comment.hidden_replies_count, 1);
+            }
+            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
+            assert_eq!(last_comment.hidden_replies_count, 0);
+        });
+    }
+
+    #[test]
+    // `PostNotFound` here: Post with Comment extension. Means that comment wasn't found.
+    fn update_comment_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error updating a comment with wrong PostId
+            assert_noop!(_update_comment(None, None, None), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_account_is_not_a_comment_author() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Try to catch an error updating a comment with wrong Account
+            assert_noop!(_update_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ), PostsError::<TestRuntime>::NotACommentAuthor);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Try to catch an error updating a comment with invalid content
+            assert_noop!(_update_comment(
+                None,
+                None,
+                Some(
+                    post_update(
+                        None,
+                        Some(invalid_content_ipfs()),
+                        None
+                    )
+                )
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn delete_comment_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let deleted_comment_id: PostId = 5;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            // Hide the last comment, so we can check the hidden replies counters as well
+            assert_ok!(_update_comment(None, Some(last_comment_id), Some(post_update(None, None, Some(true)))));
+
+            assert_ok!(_delete_post(None, Some(deleted_comment_id)));
+
+            // The deleted comment and all its replies should be removed
+            for comment_id in deleted_comment_id..=last_comment_id {
+                assert!(Posts::post_by_id(comment_id).is_none());
+            }
+
+            let remaining_replies_count = deleted_comment_id - first_comment_id;
+            let root_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(root_post.replies_count, remaining_replies_count as u16);
+            assert_eq!(root_post.hidden_replies_count, 0);
+
+            for comment_id in first_comment_id..deleted_comment_id {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                assert_eq!(comment.replies_count, (deleted_comment_id - comment_id - 1) as u16);
+                assert_eq!(comment.hidden_replies_count, 0);
+            }
+
+            assert!(Posts::reply_ids_by_post_id(deleted_comment_id - 1).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_comment_should_fail_when_account_is_not_a_comment_author() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)),
+                PostsError::<TestRuntime>::NotACommentAuthor
+            );
+        });
+    }
+
+    // Reaction tests
+    #[test]
+    fn create_post_reaction_should_work_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 1);
+            assert_eq!(post.downvotes_count, 0);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_upvote());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_work_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 1);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_downvote());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            
+            // Hide the space
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            
+            // Hide the post
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+        });
+    }
+
+    #[test]
+    fn create_emoji_reactions_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_allow_default_emoji_reactions());
+
+            let thumbs_up = ReactionKind::Emoji(EMOJI_THUMBS_UP);
+            let heart = ReactionKind::Emoji(EMOJI_HEART);
+
+            // ACCOUNT2 can upvote a post and add several different emojis to it
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            let score_after_upvote = Posts::post_by_id(POST1).unwrap().score;
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(thumbs_up)));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(heart)));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, Some(heart)));
+
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1, REACTION2, REACTION3, 4]);
+            assert_eq!(
+                Reactions::emoji_reaction_id_by_account(post1_target(), (ACCOUNT2, EMOJI_HEART)),
+                Some(REACTION3)
+            );
+
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), ReactionKind::Upvote), 1);
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), thumbs_up), 1);
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), heart), 2);
+
+            // Emoji reactions should not change votes and scores
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 1);
+            assert_eq!(post.downvotes_count, 0);
+            assert_eq!(post.score, score_after_upvote);
+        });
+    }
+
+    #[test]
+    fn create_emoji_reaction_should_fail_when_account_has_already_added_this_emoji() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_allow_default_emoji_reactions());
+
+            let heart = Some(ReactionKind::Emoji(EMOJI_HEART));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, heart));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, heart),
+                ReactionsError::<TestRuntime>::AccountAlreadyReacted
+            );
+        });
+    }
+
+    #[test]
+    fn create_emoji_reaction_should_fail_when_space_allows_only_votes() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_post_reaction(None, None, Some(ReactionKind::Emoji(EMOJI_HEART))),
+                ReactionsError::<TestRuntime>::ReactionNotAllowedInSpace
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_space_disallows_downvotes() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_allowed_space_reactions(None, None, Some(Some(vec![ReactionKind::Upvote]))));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::ReactionNotAllowedInSpace
+            );
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_for_emoji_reaction() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_allow_default_emoji_reactions());
+            assert_ok!(_create_post_reaction(None, None, Some(reaction_upvote())));
+            assert_ok!(_create_post_reaction(None, None, Some(ReactionKind::Emoji(EMOJI_HEART))));
+
+            assert_noop!(
+                _update_post_reaction(None, None, REACTION1, Some(ReactionKind::Emoji(EMOJI_THUMBS_UP))),
+                ReactionsError::<TestRuntime>::CannotUpdateEmojiReaction
+            );
+            assert_noop!(
+                _update_post_reaction(None, None, REACTION2, Some(reaction_downvote())),
+                ReactionsError::<TestRuntime>::CannotUpdateEmojiReaction
+            );
+        });
+    }
+
+    #[test]
+    fn delete_emoji_reaction_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_allow_default_emoji_reactions());
+
+            let heart = ReactionKind::Emoji(EMOJI_HEART);
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(heart)));
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(Reactions::reaction_ids_by_post_id(POST1).is_empty());
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), heart), 0);
+            assert!(!Reactions::has_reacted(&ACCOUNT2, &post1_target(), heart));
+        });
+    }
+
+    #[test]
+    fn set_allowed_space_reactions_should_fail_with_unsupported_emoji() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_allowed_space_reactions(
+                    None, None, Some(Some(vec![ReactionKind::Emoji(EMOJI_UNSUPPORTED)]))
+                ),
+                ReactionsError::<TestRuntime>::UnsupportedEmoji
+            );
+        });
+    }
+
+    #[test]
+    fn set_allowed_space_reactions_should_fail_with_too_many_reactions() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            // Two supported emojis, upvote and downvote are allowed at most.
+            let reactions = vec![ReactionKind::Upvote; SupportedEmojis::get().len() + 3];
+            assert_noop!(
+                _set_allowed_space_reactions(None, None, Some(Some(reactions))),
+                ReactionsError::<TestRuntime>::TooManyAllowedReactions
+            );
+        });
+    }
+
+    #[test]
+    fn set_allowed_space_reactions_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_allowed_space_reactions(Some(Origin::signed(ACCOUNT2)), None, Some(None)),
+                ReactionsError::<TestRuntime>::NoPermissionToUpdateAllowedReactions
+            );
+        });
+    }
+
+    #[test]
+    fn create_space_reaction_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let target = ReactionTarget::Space(SPACE1);
+            assert_ok!(_create_reaction(None, target.clone(), None));
+
+            assert_eq!(Reactions::reaction_ids_by_target(&target), vec![REACTION1]);
+            assert_eq!(Reactions::find_reaction_id(&ACCOUNT2, &target, reaction_upvote()), Some(REACTION1));
+            assert_eq!(Reactions::reactions_count_by_kind(target.clone(), reaction_upvote()), 1);
+
+            // A vote for a space can be changed like a vote for a post
+            assert_ok!(Reactions::update_reaction(
+                Origin::signed(ACCOUNT2), target.clone(), REACTION1, reaction_downvote()
+            ));
+            assert_eq!(Reactions::reactions_count_by_kind(target.clone(), reaction_upvote()), 0);
+            assert_eq!(Reactions::reactions_count_by_kind(target, reaction_downvote()), 1);
+        });
+    }
+
+    #[test]
+    fn create_space_reaction_should_fail_when_space_is_hidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_hide_space(None, None));
+
+            assert_noop!(
+                _create_reaction(None, ReactionTarget::Space(SPACE1), None),
+                ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden
+            );
+        });
+    }
+
+    #[test]
+    fn create_space_reaction_should_fail_when_account_is_blocked_by_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_block_account(None, None));
+
+            assert_noop!(
+                _create_reaction(None, ReactionTarget::Space(SPACE1), None),
+                UtilsError::<TestRuntime>::AccountIsBlockedByAccount
+            );
+        });
+    }
+
+    /// Run `on_initialize` of reactions until reactions on all deleted targets are removed.
+    fn clean_up_reactions_on_deleted_targets() {
+        while Reactions::first_deleted_target_index() != Reactions::next_deleted_target_index() {
+            Reactions::on_initialize(System::block_number());
+        }
+    }
+
+    #[test]
+    fn delete_space_should_remove_its_reactions() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let target = ReactionTarget::Space(SPACE1);
+            assert_ok!(_create_reaction(None, target.clone(), None));
+
+            assert_ok!(_delete_default_space());
+            clean_up_reactions_on_deleted_targets();
+
+            assert!(Reactions::reaction_ids_by_target(&target).is_empty());
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(!Reactions::has_reacted(&ACCOUNT2, &target, reaction_upvote()));
+        });
+    }
+
+    #[test]
+    fn delete_post_should_remove_its_reactions_in_batches() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, None));
+
+            // Reactions are kept inside of a post deletion.
+            assert_ok!(_delete_default_post());
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1, REACTION2, REACTION3]);
+
+            // Only `MaxReactionsToCleanUpPerBlock` (2) reactions are removed per block.
+            Reactions::on_initialize(System::block_number());
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1).len(), 1);
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), reaction_upvote()), 3);
+            assert_ne!(Reactions::first_deleted_target_index(), Reactions::next_deleted_target_index());
+
+            Reactions::on_initialize(System::block_number());
+            assert!(Reactions::reaction_ids_by_post_id(POST1).is_empty());
+            assert_eq!(Reactions::reactions_count_by_kind(post1_target(), reaction_upvote()), 0);
+            assert_eq!(Reactions::first_deleted_target_index(), Reactions::next_deleted_target_index());
+
+            for account in &[ACCOUNT1, ACCOUNT2, ACCOUNT3] {
+                assert!(!Reactions::has_reacted(account, &post1_target(), reaction_upvote()));
+            }
+        });
+    }
+
+    #[test]
+    fn create_profile_reaction_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            let target = ReactionTarget::Profile(ACCOUNT1);
+            assert_ok!(_create_reaction(None, target.clone(), Some(ReactionKind::Emoji(EMOJI_HEART))));
+            assert_ok!(_create_reaction(None, target.clone(), None));
+
+            assert_eq!(Reactions::reaction_ids_by_target(&target), vec![REACTION1, REACTION2]);
+            assert_eq!(Reactions::reactions_count_by_kind(target, ReactionKind::Emoji(EMOJI_HEART)), 1);
+
+            // Votes for a profile should not change the reputation of its owner
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn create_profile_reaction_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _create_reaction(None, ReactionTarget::Profile(ACCOUNT1), None),
+                ProfilesError::<TestRuntime>::AccountHasNoProfile
+            );
+        });
+    }
+
+    #[test]
+    fn create_profile_reaction_should_fail_with_unsupported_emoji() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            assert_noop!(
+                _create_reaction(None, ReactionTarget::Profile(ACCOUNT1), Some(ReactionKind::Emoji(EMOJI_UNSUPPORTED))),
+                ReactionsError::<TestRuntime>::UnsupportedEmoji
+            );
+        });
+    }
+
+// Rating system tests
+
+    #[test]
+    fn check_results_of_score_diff_for_action_with_common_values() {
+        ExtBuilder::build().execute_with(|| {
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_post()), UpvotePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_post()), DownvotePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_post()), SharePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_create_comment()), CreateCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_comment()), UpvoteCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_comment()), DownvoteCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_comment()), ShareCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_space()), FollowSpaceActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_account()), FollowAccountActionWeight::get() as i16);
+        });
+    }
+
+    #[test]
+    fn check_results_of_score_diff_for_action_with_random_values() {
+        ExtBuilder::build().execute_with(|| {
+            assert_eq!(Scores::score_diff_for_action(32768, scoring_action_upvote_post()), 80); // 2^15
+            assert_eq!(Scores::score_diff_for_action(32769, scoring_action_upvote_post()), 80); // 2^15 + 1
+            assert_eq!(Scores::score_diff_for_action(65535, scoring_action_upvote_post()), 80); // 2^16 - 1
+            assert_eq!(Scores::score_diff_for_action(65536, scoring_action_upvote_post()), 85); // 2^16
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_space_score_should_work_for_follow_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_follow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, FollowSpaceActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + FollowSpaceActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_unfollow_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_follow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+            assert_ok!(_unfollow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_upvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_downvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            )); // ReactionId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_post_score_should_work_for_create_comment() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, CreateCommentActionWeight::get() as i32);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, CreateCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_create_comment())), Some(CreateCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_upvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_downvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_for_revert_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+            // ReactionId 1
+            assert_ok!(_delete_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_for_revert_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+            // ReactionId 1
+            assert_ok!(_delete_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_change_upvote_with_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+            // ReactionId 1
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                Some(reaction_downvote())
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_change_downvote_with_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+            // ReactionId 1
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                None
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_social_account_reputation_should_work_when_max_score_diff_provided() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                std::i16::MAX,
+                scoring_action_follow_account())
+            );
+        });
+    }
+
+    #[test]
+    fn change_social_account_reputation_should_work_when_min_score_diff_provided() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                std::i16::MIN,
+                scoring_action_follow_account())
+            );
+        });
+    }
+
+    #[test]
+    fn change_social_account_reputation_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                DownvotePostActionWeight::get(),
+                scoring_action_downvote_post())
+            );
+            assert_eq!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_downvote_post())), Some(0));
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+
+            // To ensure function works correctly, multiply default UpvotePostActionWeight by two
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                UpvotePostActionWeight::get() * 2,
+                scoring_action_upvote_post())
+            );
+
+            assert_eq!(
+                Scores::account_reputation_diff_by_account(
+                    (
+                        ACCOUNT2,
+                        ACCOUNT1,
+                        scoring_action_upvote_post()
+                    )
+                ), Some(UpvotePostActionWeight::get() * 2)
+            );
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + (UpvotePostActionWeight::get() * 2) as u32);
+        });
+    }
+
+    #[test]
+    fn set_scoring_action_weight_should_change_weight_of_new_actions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(Scores::set_scoring_action_weight(Origin::root(), scoring_action_upvote_post(), Some(10)));
+            assert_eq!(Scores::weight_of_scoring_action(scoring_action_upvote_post()), 10);
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 10);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + 10);
+        });
+    }
+
+    #[test]
+    fn set_scoring_action_weight_should_reset_weight_to_default() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(Scores::set_scoring_action_weight(Origin::root(), scoring_action_follow_account(), Some(-1)));
+            assert_ok!(Scores::set_scoring_action_weight(Origin::root(), scoring_action_follow_account(), None));
+
+            assert!(Scores::weight_by_scoring_action(scoring_action_follow_account()).is_none());
+            assert_eq!(
+                Scores::weight_of_scoring_action(scoring_action_follow_account()),
+                FollowAccountActionWeight::get()
+            );
+        });
+    }
+
+    #[test]
+    fn set_scoring_action_weight_should_fail_when_origin_is_not_root() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                Scores::set_scoring_action_weight(Origin::signed(ACCOUNT1), scoring_action_upvote_post(), Some(10)),
+                DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn score_diff_for_action_should_saturate_when_weight_is_too_big() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(Scores::set_scoring_action_weight(Origin::root(), scoring_action_upvote_post(), Some(i16::MAX)));
+            assert_eq!(Scores::score_diff_for_action(u32::MAX, scoring_action_upvote_post()), i16::MAX);
+        });
+    }
+
+    #[test]
+    fn score_contributions_should_list_diffs_of_actors() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, None)); // ReactionId 1
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 2
+
+            let upvote_weight = UpvotePostActionWeight::get();
+            let expected = vec![
+                ScoreContribution { actor: ACCOUNT2, action: scoring_action_upvote_post(), diff: upvote_weight },
+                ScoreContribution { actor: ACCOUNT3, action: scoring_action_upvote_post(), diff: upvote_weight },
+            ];
+
+            assert_eq!(Scores::post_score_contributions(POST1), expected);
+            assert_eq!(Scores::account_reputation_contributions(ACCOUNT1), expected);
+            assert!(Scores::account_reputation_contributions(ACCOUNT2).is_empty());
+        });
+    }
+
+    #[test]
+    fn score_contributions_should_not_list_reverted_diffs() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, None)); // ReactionId 2
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            let expected = vec![
+                ScoreContribution { actor: ACCOUNT3, action: scoring_action_upvote_post(), diff: UpvotePostActionWeight::get() },
+            ];
+
+            assert_eq!(Scores::post_score_contributions(POST1), expected);
+            assert_eq!(Scores::account_reputation_contributions(ACCOUNT1), expected);
+        });
+    }
+
+    #[test]
+    fn post_scores_should_be_removed_when_post_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 2 shares PostId 1
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 3
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, None));
+
+            assert_ok!(_delete_default_post());
+
+            // Only `MaxPostScoresToCleanUpPerBlock` (2) scores are removed per block.
+            Scores::on_initialize(System::block_number());
+            assert_eq!(Scores::post_score_contributions(POST1).len(), 1);
+            assert_ne!(Scores::first_deleted_post_index(), Scores::next_deleted_post_index());
+
+            Scores::on_initialize(System::block_number());
+            assert_eq!(Scores::first_deleted_post_index(), Scores::next_deleted_post_index());
+
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())).is_none());
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_create_comment())).is_none());
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST1, scoring_action_upvote_post())).is_none());
+            assert!(Scores::post_score_contributions(POST1).is_empty());
+        });
+    }
+
+    fn _give_reputation(account: AccountId, scorer: AccountId, score_diff: i16) {
+        assert_ok!(Scores::change_social_account_reputation(
+            account,
+            scorer,
+            score_diff,
+            scoring_action_upvote_post())
+        );
+    }
+
+    #[test]
+    fn reputation_should_decay_toward_floor_on_next_change() {
+        ExtBuilder::build().execute_with(|| {
+            _give_reputation(ACCOUNT1, ACCOUNT2, 100);
+            assert_eq!(Scores::reputation_decayed_at(ACCOUNT1), Some(1));
+
+            // Two decay periods and a half have passed: 101 -> 91 -> 82.
+            System::set_block_number(1 + ReputationDecayPeriod::get() * 5 / 2);
+            assert_eq!(Scores::decayed_reputation(&ACCOUNT1, 101), 82);
+
+            _give_reputation(ACCOUNT1, ACCOUNT3, UpvotePostActionWeight::get());
+
+            assert_eq!(
+                Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation,
+                82 + UpvotePostActionWeight::get() as u32
+            );
+            // The remainder of the current period is kept.
+            assert_eq!(Scores::reputation_decayed_at(ACCOUNT1), Some(1 + ReputationDecayPeriod::get() * 2));
+        });
+    }
+
+    #[test]
+    fn reputation_should_not_decay_below_floor() {
+        ExtBuilder::build().execute_with(|| {
+            _give_reputation(ACCOUNT1, ACCOUNT2, 10);
+
+            System::set_block_number(1 + ReputationDecayPeriod::get() * 1_000);
+            assert_eq!(Scores::decayed_reputation(&ACCOUNT1, 11), ReputationDecayFloor::get());
+            assert_eq!(Scores::decay_reputation(ReputationDecayFloor::get(), 1_000), ReputationDecayFloor::get());
+        });
+    }
+
+    #[test]
+    fn decayed_reputation_of_voter_should_be_used_to_score_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Smoothed reputation of 257 is 9.
+            _give_reputation(ACCOUNT2, ACCOUNT3, 256);
+
+            System::set_block_number(1 + ReputationDecayPeriod::get() * 100);
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            // Reputation of the inactive voter has decayed to the floor.
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
+        });
+    }
+
+    #[test]
+    fn on_initialize_should_decay_reputation_of_inactive_accounts_in_batches() {
+        ExtBuilder::build().execute_with(|| {
+            _give_reputation(ACCOUNT1, ACCOUNT3, 100);
+            _give_reputation(ACCOUNT2, ACCOUNT3, 100);
+            _give_reputation(ACCOUNT3, ACCOUNT1, 100);
+            assert_eq!(Scores::decaying_accounts_count(), 3);
+
+            let block_number = 1 + ReputationDecayPeriod::get();
+            System::set_block_number(block_number);
+
+            // Only two accounts are decayed per block.
+            Scores::on_initialize(block_number);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 91);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 91);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 101);
+            assert_eq!(Scores::next_decay_index(), 2);
+
+            // The next batch continues from the third account and wraps around the queue.
+            Scores::on_initialize(block_number + 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 91);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 91);
+            assert_eq!(Scores::next_decay_index(), 1);
+        });
+    }
+
+    fn _set_min_reputation_for_permission(
+        origin: Option<Origin>,
+        permission: SpacePermission,
+        min_reputation: Option<u32>,
+    ) -> DispatchResult {
+        Roles::set_min_reputation_for_permission(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            permission,
+            min_reputation,
+        )
+    }
+
+    #[test]
+    fn upvote_should_fail_when_account_has_not_enough_reputation() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_min_reputation_for_permission(None, SP::Upvote, Some(10)));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None),
+                RolesError::<TestRuntime>::NotEnoughReputation
+            );
+        });
+    }
+
+    #[test]
+    fn upvote_should_work_when_account_has_enough_reputation() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_min_reputation_for_permission(None, SP::Upvote, Some(10)));
+            _give_reputation(ACCOUNT2, ACCOUNT3, 10);
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+        });
+    }
+
+    #[test]
+    fn upvote_should_fail_when_reputation_decayed_below_min_reputation() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Reputation of 11 decays to 10 in one period.
+            assert_ok!(_set_min_reputation_for_permission(None, SP::Upvote, Some(11)));
+            _give_reputation(ACCOUNT2, ACCOUNT3, 10);
+
+            System::set_block_number(1 + ReputationDecayPeriod::get());
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None),
+                RolesError::<TestRuntime>::NotEnoughReputation
+            );
+        });
+    }
+
+    #[test]
+    fn min_reputation_should_not_apply_to_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_set_min_reputation_for_permission(None, SP::CreatePosts, Some(10)));
+
+            assert_ok!(_create_default_post());
+        });
+    }
+
+    #[test]
+    fn min_reputation_should_be_removed_when_set_to_none() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_min_reputation_for_permission(None, SP::Upvote, Some(10)));
+            assert_ok!(_set_min_reputation_for_permission(None, SP::Upvote, None));
+
+            assert!(Roles::min_reputation_by_space_permission(SPACE1, SP::Upvote).is_none());
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+        });
+    }
+
+    #[test]
+    fn set_min_reputation_for_permission_should_fail_when_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_min_reputation_for_permission(Some(Origin::signed(ACCOUNT2)), SP::Upvote, Some(10)),
+                RolesError::<TestRuntime>::NoPermissionToUpdateMinReputation
+            );
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_comment_score_should_work_for_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(
+                ACCOUNT3,
+                POST2,
+                reaction_upvote()
+            ));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_work_for_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_for_revert_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_upvote_comment())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_for_revert_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_downvote_comment())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_comment_score_check_for_cancel_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_check_for_cancel_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+        });
+    }
+
+// Shares tests
+
+    #[test]
+    fn share_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+
+            assert_eq!(shared_post.space_id, Some(SPACE2));
+            assert_eq!(shared_post.created.account, ACCOUNT2);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content,
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(shared_post.space_id, Some(SPACE1));
+            assert_eq!(shared_post.created.account, ACCOUNT1);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_change_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + SharePostActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())), Some(SharePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn share_post_should_not_change_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST1, scoring_action_share_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_original_post_not_found() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            // Skipped creating PostId 1
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_trying_to_share_shared_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None)
+            );
+
+            // Try to share post with extension SharedPost
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST2)),
+                None
+            ), PostsError::<TestRuntime>::CannotShareSharingPost);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(None), // No space_handle provided (ok)
+                None, // Default space content,
+                None
+            )); // SpaceId 2 by ACCOUNT1
+
+            // Try to share post with extension SharedPost
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_delete_default_role());
+
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
         });
     }
 
+// Profiles tests
+
     #[test]
-    fn update_any_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
-            );
-            assert_ok!(_create_default_post()); // PostId 1
+    fn create_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile()); // AccountId 1
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(POST1),
-                Some(post_update)
-            ));
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.created.account, ACCOUNT1);
+            assert!(profile.updated.is_none());
+            assert_eq!(profile.content, profile_content_ipfs());
+
+            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_no_updates_for_post_provided() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error updating a post with no changes
-            assert_noop!(_update_post(None, None, None), PostsError::<TestRuntime>::NoUpdatesForPost);
+    fn create_profile_should_fail_when_profile_is_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_post_not_found() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
-
-            // Try to catch an error updating a post with wrong post ID
-            assert_noop!(_update_post(
+    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
                 None,
-                Some(POST2),
-                Some(
-                    post_update(
-                        // FIXME: when Post's `space_id` update is fully implemented
-                        None/*Some(SPACE2)*/,
-                        None,
-                        Some(true)/*None*/
-                    )
-                )
-            ), PostsError::<TestRuntime>::PostNotFound);
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_account_has_no_permission_to_update_any_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
+    fn create_profile_should_work_with_hyper_content() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(valid_content_hyper())));
 
-            // Try to catch an error updating a post with different account
-            assert_noop!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(
-                    post_update(
-                        // FIXME: when Post's `space_id` update is fully implemented
-                        None/*Some(SPACE2)*/,
-                        None,
-                        Some(true)/*None*/
-                    )
-                )
-            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.content, valid_content_hyper());
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error updating a post with invalid content
-            assert_noop!(_update_post(
-                None,
+    fn create_profile_should_fail_when_hyper_id_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
                 None,
-                Some(
-                    post_update(
-                        None,
-                        Some(invalid_content_ipfs()),
-                        None
-                    )
-                )
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+                Some(invalid_content_hyper())
+            ), UtilsError::<TestRuntime>::InvalidHypercoreId);
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
-            let post_update = post_update(
+    fn update_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_ok!(_update_profile(
                 None,
-                Some(updated_post_content()),
-                Some(true),
-            );
-            assert_ok!(_create_default_post());
-            // PostId 1
-            assert_ok!(_delete_default_role());
-
-            // Post update with ID 1 should be fine
-            assert_noop!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(POST1),
-                Some(post_update)
-            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
-        });
-    }
-
-    // Comment tests
-    #[test]
-    fn create_comment_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_default_comment()); // PostId 2 by ACCOUNT1 which is permitted by default
-
-            // Check storages
-            let root_post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![POST2]);
-            assert_eq!(root_post.replies_count, 1);
-            assert_eq!(root_post.hidden_replies_count, 0);
-
-            // Check whether data stored correctly
-            let comment = Posts::post_by_id(POST2).unwrap();
-            let comment_ext = comment.get_comment_ext().unwrap();
+                Some(space_content_ipfs())
+            ));
 
-            assert!(comment_ext.parent_id.is_none());
-            assert_eq!(comment_ext.root_post_id, POST1);
-            assert_eq!(comment.created.account, ACCOUNT1);
-            assert!(comment.updated.is_none());
-            assert_eq!(comment.content, comment_content_ipfs());
-            assert_eq!(comment.replies_count, 0);
-            assert_eq!(comment.hidden_replies_count, 0);
-            assert_eq!(comment.shares_count, 0);
-            assert_eq!(comment.upvotes_count, 0);
-            assert_eq!(comment.downvotes_count, 0);
-            assert_eq!(comment.score, 0);
+            // Check whether profile updated correctly
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert!(profile.updated.is_some());
+            assert_eq!(profile.content, space_content_ipfs());
 
-            assert!(PostHistory::edit_history(POST2).is_empty());
+            // Check whether profile history is written correctly
+            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
+            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
         });
     }
 
     #[test]
-    fn create_comment_should_work_when_comment_has_parents() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            let first_comment_id: PostId = 2;
-            let penultimate_comment_id: PostId = 8;
-            let last_comment_id: PostId = 9;
-
-            for parent_id in first_comment_id..last_comment_id as PostId {
-                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
-            }
-
-            for comment_id in first_comment_id..penultimate_comment_id as PostId {
-                let comment = Posts::post_by_id(comment_id).unwrap();
-                let replies_should_be = last_comment_id - comment_id;
-                assert_eq!(comment.replies_count, replies_should_be as u16);
-                assert_eq!(Posts::reply_ids_by_post_id(comment_id), vec![comment_id + 1]);
-
-                assert_eq!(comment.hidden_replies_count, 0);
-            }
-
-            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
-            assert_eq!(last_comment.replies_count, 0);
-            assert!(Posts::reply_ids_by_post_id(last_comment_id).is_empty());
-
-            assert_eq!(last_comment.hidden_replies_count, 0);
+    fn update_profile_should_fail_when_social_account_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_post_not_found() {
+    fn update_profile_should_fail_when_account_has_no_profile() {
         ExtBuilder::build().execute_with(|| {
-            // Try to catch an error creating a comment with wrong post
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::PostNotFound);
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_parent_comment_is_unknown() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error creating a comment with wrong parent
-            assert_noop!(_create_comment(
-                None,
+    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_update_profile(
                 None,
-                Some(Some(POST2)),
                 None
-            ), PostsError::<TestRuntime>::UnknownParentComment);
+            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error creating a comment with wrong parent
-            assert_noop!(_create_comment(
-                None,
-                None,
+    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(_update_profile(
                 None,
                 Some(invalid_content_ipfs())
             ), UtilsError::<TestRuntime>::InvalidIpfsCid);
@@ -2202,1146 +5163,1077 @@ mod tests {
     }
 
     #[test]
-    fn create_comment_should_fail_when_trying_to_create_in_hidden_space_scope() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
-
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
-        });
-    }
+    fn update_profile_handle_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
 
-    #[test]
-    fn create_comment_should_fail_when_trying_create_in_hidden_post_scope() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_update_post(
-                None,
-                None,
-                Some(post_update(None, None, Some(true)))
-            ));
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.handle, Some(profile_handle()));
+            assert!(profile.updated.is_some());
 
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+            assert_eq!(find_profile_id_by_handle(profile_handle()), Some(ACCOUNT1));
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), PROFILE_HANDLE_DEPOSIT);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_max_comment_depth_reached() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+    fn update_profile_handle_should_change_handle() {
+        ExtBuilder::build().execute_with(|| {
+            let new_handle: Vec<u8> = b"new_profile_handle".to_vec();
 
-            for parent_id in 2..11 as PostId {
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None)); // PostId N (last = 10)
-            }
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
+            assert_ok!(_update_profile_handle(None, Some(Some(new_handle.clone()))));
 
-            // Some(Some(11)) - here is parent_id 11 of type PostId
-            assert_noop!(_create_comment(
-                None,
-                None,
-                Some(Some(11)),
-                None
-            ), PostsError::<TestRuntime>::MaxCommentDepthReached);
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.handle, Some(new_handle.clone()));
+
+            assert!(find_profile_id_by_handle(profile_handle()).is_none());
+            assert_eq!(find_profile_id_by_handle(new_handle), Some(ACCOUNT1));
+
+            // A deposit should be reserved only once per profile.
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), PROFILE_HANDLE_DEPOSIT);
         });
     }
 
     #[test]
-    fn update_comment_should_work() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_comment(None, None, None));
+    fn update_profile_handle_should_allow_to_change_letter_case_of_handle() {
+        ExtBuilder::build().execute_with(|| {
+            let uppercase_handle: Vec<u8> = b"PROFILE_HANDLE".to_vec();
 
-            // Check whether post updates correctly
-            let comment = Posts::post_by_id(POST2).unwrap();
-            assert_eq!(comment.content, reply_content_ipfs());
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
+            assert_ok!(_update_profile_handle(None, Some(Some(uppercase_handle.clone()))));
 
-            // Check whether history recorded correctly
-            assert_eq!(PostHistory::edit_history(POST2)[0].old_data.content, Some(comment_content_ipfs()));
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.handle, Some(uppercase_handle));
+            assert_eq!(find_profile_id_by_handle(profile_handle()), Some(ACCOUNT1));
         });
     }
 
     #[test]
-    fn update_comment_hidden_should_work_when_comment_has_parents() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            let first_comment_id: PostId = 2;
-            let penultimate_comment_id: PostId = 8;
-            let last_comment_id: PostId = 9;
+    fn update_profile_handle_should_release_handle() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
+            assert_ok!(_update_profile_handle(None, Some(None)));
 
-            for parent_id in first_comment_id..last_comment_id as PostId {
-                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
-            }
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert!(profile.handle.is_none());
 
-            assert_ok!(_update_comment(
-                None,
-                Some(last_comment_id),
-                Some(post_update(
-                    None,
-                    None,
-                    Some(true) // make comment hidden
-                ))
-            ));
+            assert!(find_profile_id_by_handle(profile_handle()).is_none());
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
 
-            for comment_id in first_comment_id..penultimate_comment_id as PostId {
-                let comment = Posts::post_by_id(comment_id).unwrap();
-                assert_eq!(comment.hidden_replies_count, 1);
-            }
-            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
-            assert_eq!(last_comment.hidden_replies_count, 0);
+            // The released handle can be taken by another profile.
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_ok!(_update_profile_handle(Some(Origin::signed(ACCOUNT2)), None));
+            assert_eq!(find_profile_id_by_handle(profile_handle()), Some(ACCOUNT2));
         });
     }
 
     #[test]
-    // `PostNotFound` here: Post with Comment extension. Means that comment wasn't found.
-    fn update_comment_should_fail_when_post_not_found() {
+    fn update_profile_handle_should_fail_when_account_has_no_profile() {
         ExtBuilder::build().execute_with(|| {
-            // Try to catch an error updating a comment with wrong PostId
-            assert_noop!(_update_comment(None, None, None), PostsError::<TestRuntime>::PostNotFound);
+            assert_noop!(
+                _update_default_profile_handle(),
+                ProfilesError::<TestRuntime>::SocialAccountNotFound
+            );
         });
     }
 
     #[test]
-    fn update_comment_should_fail_when_account_is_not_a_comment_author() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Try to catch an error updating a comment with wrong Account
-            assert_noop!(_update_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            ), PostsError::<TestRuntime>::NotACommentAuthor);
+    fn update_profile_handle_should_fail_when_handle_is_the_same() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
+
+            assert_noop!(
+                _update_default_profile_handle(),
+                ProfilesError::<TestRuntime>::NoUpdatesForProfile
+            );
         });
     }
 
     #[test]
-    fn update_comment_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Try to catch an error updating a comment with invalid content
-            assert_noop!(_update_comment(
-                None,
-                None,
-                Some(
-                    post_update(
-                        None,
-                        Some(invalid_content_ipfs()),
-                        None
-                    )
-                )
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn update_profile_handle_should_fail_when_handle_is_taken_by_another_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
+
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_noop!(
+                _update_profile_handle(
+                    Some(Origin::signed(ACCOUNT2)),
+                    Some(Some(b"PROFILE_handle".to_vec()))
+                ), ProfilesError::<TestRuntime>::ProfileHandleIsNotUnique
+            );
         });
     }
 
-    // Reaction tests
     #[test]
-    fn create_post_reaction_should_work_upvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+    fn update_profile_handle_should_fail_when_handle_is_taken_by_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_profile());
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+            assert_noop!(
+                _update_profile_handle(None, Some(Some(space_handle()))),
+                ProfilesError::<TestRuntime>::ProfileHandleIsNotUnique
+            );
+        });
+    }
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 1);
-            assert_eq!(post.downvotes_count, 0);
+    #[test]
+    fn update_profile_handle_should_fail_when_handle_contains_invalid_chars() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_upvote());
+            assert_noop!(
+                _update_profile_handle(None, Some(Some(b"@profile".to_vec()))),
+                UtilsError::<TestRuntime>::HandleContainsInvalidChars
+            );
         });
     }
 
     #[test]
-    fn create_post_reaction_should_work_downvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+    fn create_space_should_fail_when_handle_is_taken_by_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_ok!(_update_default_profile_handle());
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+            assert_noop!(
+                _create_space(None, Some(Some(profile_handle())), None, None),
+                SpacesError::<TestRuntime>::SpaceHandleIsNotUnique
+            );
+        });
+    }
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 0);
-            assert_eq!(post.downvotes_count, 1);
+// Space following tests
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_downvote());
+    #[test]
+    fn follow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
+            assert_eq!(all_spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+    fn follow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+    fn follow_space_should_fail_when_account_is_already_space_follower() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            
-            // Hide the space
+    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
             assert_ok!(_update_space(
                 None,
                 None,
                 Some(space_update(None, None, Some(true)))
             ));
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            
-            // Hide the post
-            assert_ok!(_update_post(
-                None,
-                None,
-                Some(post_update(None, None, Some(true)))
-            ));
+    fn unfollow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_default_unfollow_space());
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert!(all_spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1]);
         });
     }
 
-// Rating system tests
+    #[test]
+    fn unfollow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
 
     #[test]
-    fn check_results_of_score_diff_for_action_with_common_values() {
+    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
+        });
+    }
+
+// Storage migration tests
+
+    #[test]
+    fn scores_migration_should_index_score_diffs_by_subject() {
         ExtBuilder::build().execute_with(|| {
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_post()), UpvotePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_post()), DownvotePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_post()), SharePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_create_comment()), CreateCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_comment()), UpvoteCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_comment()), DownvoteCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_comment()), ShareCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_space()), FollowSpaceActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_account()), FollowAccountActionWeight::get() as i16);
+            let action = scoring_action_upvote_post();
+            put_storage_value(
+                b"ScoresModule", b"AccountReputationDiffByAccount",
+                &Blake2_128Concat::hash(&(ACCOUNT2, ACCOUNT1, action).encode()), 5i16,
+            );
+            put_storage_value(
+                b"ScoresModule", b"PostScoreByAccount",
+                &Blake2_128Concat::hash(&(ACCOUNT2, POST1, action).encode()), 5i16,
+            );
+
+            pallet_scores::migration::migrate::<TestRuntime>();
+
+            let expected = vec![ScoreContribution { actor: ACCOUNT2, action, diff: 5 }];
+            assert_eq!(Scores::account_reputation_contributions(ACCOUNT1), expected);
+            assert_eq!(Scores::post_score_contributions(POST1), expected);
         });
     }
 
     #[test]
-    fn check_results_of_score_diff_for_action_with_random_values() {
+    fn scores_migration_should_start_reputation_decay_of_existing_accounts() {
         ExtBuilder::build().execute_with(|| {
-            assert_eq!(Scores::score_diff_for_action(32768, scoring_action_upvote_post()), 80); // 2^15
-            assert_eq!(Scores::score_diff_for_action(32769, scoring_action_upvote_post()), 80); // 2^15 + 1
-            assert_eq!(Scores::score_diff_for_action(65535, scoring_action_upvote_post()), 80); // 2^16 - 1
-            assert_eq!(Scores::score_diff_for_action(65536, scoring_action_upvote_post()), 85); // 2^16
+            for (account, reputation) in &[(ACCOUNT1, 50), (ACCOUNT2, 1)] {
+                let mut social_account = Profiles::get_or_new_social_account(*account);
+                social_account.reputation = *reputation;
+                pallet_profiles::SocialAccountById::<TestRuntime>::insert(account, social_account);
+            }
+
+            System::set_block_number(10);
+            pallet_scores::migration::migrate::<TestRuntime>();
+
+            assert_eq!(Scores::reputation_decayed_at(ACCOUNT1), Some(10));
+            assert_eq!(Scores::decaying_account_by_index(0), Some(ACCOUNT1));
+
+            // Reputation at the floor has nothing to decay.
+            assert!(Scores::reputation_decayed_at(ACCOUNT2).is_none());
+            assert_eq!(Scores::decaying_accounts_count(), 1);
         });
     }
 
-//--------------------------------------------------------------------------------------------------
+    #[test]
+    fn scores_migration_should_index_decay_queue_and_drop_reaped_accounts() {
+        ExtBuilder::build().execute_with(|| {
+            for account in &[ACCOUNT1, ACCOUNT3] {
+                let social_account = Profiles::get_or_new_social_account(*account);
+                pallet_profiles::SocialAccountById::<TestRuntime>::insert(account, social_account);
+            }
+
+            // ACCOUNT2 was reaped before the queue was indexed by account.
+            for (index, account) in [ACCOUNT1, ACCOUNT2, ACCOUNT3].iter().enumerate() {
+                pallet_scores::DecayingAccountByIndex::<TestRuntime>::insert(index as u64, account);
+                pallet_scores::ReputationDecayedAt::<TestRuntime>::insert(account, 1);
+            }
+            pallet_scores::DecayingAccountsCount::put(3);
+
+            pallet_scores::migration::migrate::<TestRuntime>();
+
+            assert_eq!(Scores::decaying_accounts_count(), 2);
+            assert_eq!(Scores::decaying_account_by_index(0), Some(ACCOUNT1));
+            assert_eq!(Scores::decaying_account_by_index(1), Some(ACCOUNT3));
+            assert_eq!(Scores::decay_index_by_account(ACCOUNT1), Some(0));
+            assert_eq!(Scores::decay_index_by_account(ACCOUNT3), Some(1));
+            assert!(Scores::decay_index_by_account(ACCOUNT2).is_none());
+            assert!(Scores::reputation_decayed_at(ACCOUNT2).is_none());
+        });
+    }
 
     #[test]
-    fn change_space_score_should_work_for_follow_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_follow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
+    fn space_follows_migration_should_move_followers_to_double_maps() {
+        ExtBuilder::build().execute_with(|| {
+            put_storage_value(
+                b"SpaceFollowsModule", b"SpaceFollowers",
+                &Twox64Concat::hash(&SPACE1.encode()), vec![ACCOUNT1, ACCOUNT2],
+            );
+            put_storage_value(
+                b"SpaceFollowsModule", b"SpacesFollowedByAccount",
+                &Blake2_128Concat::hash(&ACCOUNT2.encode()), vec![SPACE1],
+            );
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, FollowSpaceActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + FollowSpaceActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            pallet_space_follows::migration::migrate::<TestRuntime>();
+
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(all_spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_unfollow_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_follow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
-            assert_ok!(_unfollow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
+    fn profile_follows_migration_should_move_followers_to_double_maps() {
+        ExtBuilder::build().execute_with(|| {
+            put_storage_value(
+                b"ProfileFollowsModule", b"AccountFollowers",
+                &Blake2_128Concat::hash(&ACCOUNT1.encode()), vec![ACCOUNT2, ACCOUNT3],
+            );
+            put_storage_value(
+                b"ProfileFollowsModule", b"AccountsFollowedByAccount",
+                &Blake2_128Concat::hash(&ACCOUNT2.encode()), vec![ACCOUNT1],
+            );
+
+            pallet_profile_follows::migration::migrate::<TestRuntime>();
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(all_account_followers(ACCOUNT1), vec![ACCOUNT2, ACCOUNT3]);
+            assert_eq!(all_accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_upvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    fn spaces_migration_should_count_subspaces_by_tree_depth() {
+        ExtBuilder::build().execute_with(|| {
+            for (space_id, parent_id) in &[(SPACE1, None), (SPACE2, Some(SPACE1)), (SPACE2 + 1, Some(SPACE2)), (SPACE2 + 2, Some(SPACE1))] {
+                SpaceById::<TestRuntime>::insert(
+                    space_id,
+                    Space::<TestRuntime>::new(*space_id, *parent_id, ACCOUNT1, Content::None, None, None)
+                );
+            }
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+            pallet_spaces::migration::migrate::<TestRuntime>();
+
+            assert_eq!(Spaces::subspace_counts_by_tree_depth(SPACE1), vec![1, 1]);
+            assert_eq!(Spaces::subspace_counts_by_tree_depth(SPACE2), vec![1]);
+            assert_eq!(Spaces::get_subspace_tree_depth(SPACE1), 3);
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_downvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            )); // ReactionId 1
+    fn posts_migration_should_move_post_ids_to_paged_indexes() {
+        ExtBuilder::build().execute_with(|| {
+            put_storage_value(
+                b"PostsModule", b"PostIdsBySpaceId",
+                &Twox64Concat::hash(&SPACE1.encode()), vec![POST1, POST2, POST3],
+            );
+            put_storage_value(
+                b"PostsModule", b"ReplyIdsByPostId",
+                &Twox64Concat::hash(&POST1.encode()), vec![4 as PostId, 5],
+            );
+            put_storage_value(
+                b"PostsModule", b"SharedPostIdsByOriginalPostId",
+                &Twox64Concat::hash(&POST2.encode()), vec![6 as PostId],
+            );
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            pallet_posts::migration::migrate::<TestRuntime>();
+
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2, POST3]);
+            assert_eq!(Posts::next_post_index_by_space_id(SPACE1), 3);
+            assert_eq!(Posts::space_post_index_by_post_id(POST3), Some(2));
+            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![4, 5]);
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST2), vec![6]);
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_post_score_should_work_for_create_comment() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+    fn reactions_migration_should_move_post_reactions_to_targets() {
+        ExtBuilder::build().execute_with(|| {
+            let upvote_id = Reactions::insert_new_reaction(ACCOUNT2, reaction_upvote());
+            let emoji_id = Reactions::insert_new_reaction(ACCOUNT2, ReactionKind::Emoji(EMOJI_HEART));
+            put_storage_value(
+                b"ReactionsModule", b"ReactionIdsByPostId",
+                &Twox64Concat::hash(&POST1.encode()), vec![upvote_id, emoji_id],
+            );
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, CreateCommentActionWeight::get() as i32);
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, CreateCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_create_comment())), Some(CreateCommentActionWeight::get()));
+            pallet_reactions::migration::migrate::<TestRuntime>();
+
+            let target = post1_target();
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![upvote_id, emoji_id]);
+            assert_eq!(Reactions::find_reaction_id(&ACCOUNT2, &target, reaction_upvote()), Some(upvote_id));
+            assert_eq!(Reactions::emoji_reaction_id_by_account(target.clone(), (ACCOUNT2, EMOJI_HEART)), Some(emoji_id));
+            assert_eq!(Reactions::reactions_count_by_kind(target, reaction_upvote()), 1);
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_upvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            ));
+    fn reactions_migration_should_move_reaction_indexes_to_double_maps() {
+        ExtBuilder::build().execute_with(|| {
+            put_storage_value(b"ReactionsModule", b"StorageVersion", &[], pallet_reactions::Releases::V2_0_0);
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+            let target = post1_target();
+            put_storage_value(
+                b"ReactionsModule", b"ReactionIdsByTarget",
+                &Blake2_128Concat::hash(&target.encode()), vec![REACTION1, REACTION2],
+            );
+            put_storage_value(
+                b"ReactionsModule", b"ReactionIdByAccount",
+                &Blake2_128Concat::hash(&(ACCOUNT2, target.clone()).encode()), REACTION1,
+            );
+            put_storage_value(
+                b"ReactionsModule", b"EmojiReactionIdByAccount",
+                &Blake2_128Concat::hash(&(ACCOUNT2, target.clone(), EMOJI_HEART).encode()), REACTION2,
+            );
+
+            pallet_reactions::migration::migrate::<TestRuntime>();
+
+            assert_eq!(Reactions::reaction_ids_by_target(&target), vec![REACTION1, REACTION2]);
+            assert_eq!(Reactions::reaction_id_by_account(target.clone(), ACCOUNT2), Some(REACTION1));
+            assert_eq!(Reactions::emoji_reaction_id_by_account(target, (ACCOUNT2, EMOJI_HEART)), Some(REACTION2));
         });
     }
 
+// Account following tests
+
     #[test]
-    fn change_post_score_should_work_for_downvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            ));
+    fn follow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+            assert_eq!(all_accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
+            assert_eq!(all_account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
         });
     }
 
     #[test]
-    fn change_post_score_should_for_revert_upvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            ));
-            // ReactionId 1
-            assert_ok!(_delete_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                REACTION1
-            ));
+    fn account_followers_should_be_paginated() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), None));
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+            let first_page = ProfileFollows::account_followers(&ACCOUNT1, 0, 1);
+            let second_page = ProfileFollows::account_followers(&ACCOUNT1, 1, 1);
+            assert_eq!(first_page.len(), 1);
+            assert_eq!(second_page.len(), 1);
+            assert_ne!(first_page, second_page);
+
+            assert!(ProfileFollows::account_followers(&ACCOUNT1, 2, 1).is_empty());
+            assert_eq!(all_account_followers(ACCOUNT1), vec![ACCOUNT2, ACCOUNT3]);
         });
     }
 
     #[test]
-    fn change_post_score_should_for_revert_downvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            ));
-            // ReactionId 1
-            assert_ok!(_delete_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
+    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_follow_account(
                 None,
-                REACTION1
-            ));
-
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_change_upvote_with_downvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            ));
-            // ReactionId 1
-            assert_ok!(_update_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                REACTION1,
-                Some(reaction_downvote())
-            ));
+    fn follow_account_should_fail_when_account_is_already_following_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_change_downvote_with_upvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            ));
-            // ReactionId 1
-            assert_ok!(_update_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                REACTION1,
-                None
-            ));
+    fn unfollow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_unfollow_account());
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+            assert!(all_accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(all_account_followers(ACCOUNT1).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_social_account_reputation_should_work_when_max_score_diff_provided() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                std::i16::MAX,
-                scoring_action_follow_account())
-            );
+    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_unfollow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
         });
     }
 
     #[test]
-    fn change_social_account_reputation_should_work_when_min_score_diff_provided() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                std::i16::MIN,
-                scoring_action_follow_account())
-            );
+    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_default_unfollow_account());
+
+            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
         });
     }
 
-    #[test]
-    fn change_social_account_reputation_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                DownvotePostActionWeight::get(),
-                scoring_action_downvote_post())
-            );
-            assert_eq!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_downvote_post())), Some(0));
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+// Follow requests tests
 
-            // To ensure function works correctly, multiply default UpvotePostActionWeight by two
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                UpvotePostActionWeight::get() * 2,
-                scoring_action_upvote_post())
-            );
+    #[test]
+    fn follow_account_should_create_request_when_approval_required() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account()); // Request to follow ACCOUNT1 by ACCOUNT2
 
-            assert_eq!(
-                Scores::account_reputation_diff_by_account(
-                    (
-                        ACCOUNT2,
-                        ACCOUNT1,
-                        scoring_action_upvote_post()
-                    )
-                ), Some(UpvotePostActionWeight::get() * 2)
-            );
+            assert_eq!(all_follow_requests_to_account(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(all_follow_requests_by_account(ACCOUNT2), vec![ACCOUNT1]);
+            assert!(ProfileFollows::follow_requested_by_account((ACCOUNT2, ACCOUNT1)));
 
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + (UpvotePostActionWeight::get() * 2) as u32);
+            assert!(all_account_followers(ACCOUNT1).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            assert!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_follow_account())).is_none());
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_comment_score_should_work_for_upvote() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+    fn follow_account_should_fail_when_request_already_sent() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account());
 
-            assert_ok!(_score_post_on_reaction_with_id(
-                ACCOUNT3,
-                POST2,
-                reaction_upvote()
-            ));
+            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::FollowRequestAlreadySent);
+        });
+    }
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+    #[test]
+    fn accept_account_follow_request_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account());
+            assert_ok!(_accept_default_account_follow_request());
+
+            assert!(all_follow_requests_to_account(ACCOUNT1).is_empty());
+            assert!(all_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert_eq!(ProfileFollows::follow_requested_by_account((ACCOUNT2, ACCOUNT1)), false);
+
+            assert_eq!(all_account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().followers_count, 1);
+            assert!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_follow_account())).is_some());
         });
     }
 
     #[test]
-    fn change_comment_score_should_work_for_downvote() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+    fn accept_account_follow_request_should_fail_when_request_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _accept_default_account_follow_request(),
+                ProfileFollowsError::<TestRuntime>::FollowRequestNotFound
+            );
+        });
+    }
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+    #[test]
+    fn reject_account_follow_request_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account());
+            assert_ok!(_reject_default_account_follow_request());
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+            assert!(all_follow_requests_to_account(ACCOUNT1).is_empty());
+            assert!(all_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert!(all_account_followers(ACCOUNT1).is_empty());
         });
     }
 
     #[test]
-    fn change_comment_score_should_for_revert_upvote() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
-
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+    fn cancel_account_follow_request_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account());
+            assert_ok!(_cancel_default_account_follow_request());
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_upvote_comment())).is_none());
+            assert!(all_follow_requests_to_account(ACCOUNT1).is_empty());
+            assert!(all_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert_noop!(
+                _accept_default_account_follow_request(),
+                ProfileFollowsError::<TestRuntime>::FollowRequestNotFound
+            );
         });
     }
 
     #[test]
-    fn change_comment_score_should_for_revert_downvote() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+    fn follow_account_should_work_when_approval_is_no_longer_required() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_default_follow_account());
+            assert_ok!(ProfileFollows::set_follow_approval_required(Origin::signed(ACCOUNT1), false));
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_ok!(_default_follow_account());
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_downvote_comment())).is_none());
+            assert_eq!(all_account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert!(all_follow_requests_to_account(ACCOUNT1).is_empty());
         });
     }
 
     #[test]
-    fn change_comment_score_check_for_cancel_upvote() {
+    fn follow_space_should_create_request_when_approval_required() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_space()); // Request to follow SpaceId 1 by ACCOUNT2
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_eq!(all_follow_requests_to_space(SPACE1), vec![ACCOUNT2]);
+            assert_eq!(all_space_follow_requests_by_account(ACCOUNT2), vec![SPACE1]);
+            assert!(SpaceFollows::space_follow_requested_by_account((ACCOUNT2, SPACE1)));
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
         });
     }
 
     #[test]
-    fn change_comment_score_check_for_cancel_downvote() {
+    fn follow_space_should_fail_when_request_already_sent() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
-
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_space());
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::FollowRequestAlreadySent);
         });
     }
 
-// Shares tests
-
     #[test]
-    fn share_post_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+    fn set_space_follow_approval_required_should_fail_with_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _set_space_follow_approval_required(Some(Origin::signed(ACCOUNT2)), None, true),
+                SpaceFollowsError::<TestRuntime>::NoPermissionToUpdateFollowApproval
+            );
+        });
+    }
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+    #[test]
+    fn accept_space_follow_request_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_accept_default_space_follow_request());
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
+            assert!(all_follow_requests_to_space(SPACE1).is_empty());
+            assert!(all_space_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert_eq!(SpaceFollows::space_follow_requested_by_account((ACCOUNT2, SPACE1)), false);
 
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, FollowSpaceActionWeight::get() as i32);
+        });
+    }
 
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+    #[test]
+    fn accept_space_follow_request_should_work_with_role_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::ManageFollowRequests]).execute_with(|| {
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
 
-            let shared_post = Posts::post_by_id(POST2).unwrap();
+            assert_ok!(_accept_space_follow_request(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT3)));
 
-            assert_eq!(shared_post.space_id, Some(SPACE2));
-            assert_eq!(shared_post.created.account, ACCOUNT2);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT3, SPACE1)), true);
         });
     }
 
     #[test]
-    fn share_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content,
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+    fn accept_space_follow_request_should_fail_with_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+            assert_noop!(
+                _accept_space_follow_request(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT3)),
+                SpaceFollowsError::<TestRuntime>::NoPermissionToManageFollowRequests
+            );
         });
     }
 
     #[test]
-    fn share_post_should_work_for_share_own_post_in_same_own_space() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1
-
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
-
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
-
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
-
-            let shared_post = Posts::post_by_id(POST2).unwrap();
-            assert_eq!(shared_post.space_id, Some(SPACE1));
-            assert_eq!(shared_post.created.account, ACCOUNT1);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+    fn accept_space_follow_request_should_fail_when_request_not_found() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _accept_default_space_follow_request(),
+                SpaceFollowsError::<TestRuntime>::FollowRequestNotFound
+            );
         });
     }
 
     #[test]
-    fn share_post_should_change_score() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
-
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+    fn reject_space_follow_request_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_reject_default_space_follow_request());
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + SharePostActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())), Some(SharePostActionWeight::get()));
+            assert!(all_follow_requests_to_space(SPACE1).is_empty());
+            assert!(all_space_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), false);
         });
     }
 
     #[test]
-    fn share_post_should_not_change_score() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId
+    fn cancel_space_follow_request_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_space());
+            assert_ok!(_cancel_default_space_follow_request());
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST1, scoring_action_share_post())).is_none());
+            assert!(all_follow_requests_to_space(SPACE1).is_empty());
+            assert!(all_space_follow_requests_by_account(ACCOUNT2).is_empty());
         });
     }
 
+// Blocklists tests
+
     #[test]
-    fn share_post_should_fail_when_original_post_not_found() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+    fn block_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account()); // Block ACCOUNT2 by ACCOUNT1
 
-            // Skipped creating PostId 1
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+            assert!(Blocklists::account_blocked_by_account(ACCOUNT1, ACCOUNT2));
+            assert!(!Blocklists::account_blocked_by_account(ACCOUNT2, ACCOUNT1));
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_trying_to_share_shared_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
-
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None)
+    fn block_account_should_fail_when_account_tries_to_block_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _block_account(None, Some(ACCOUNT1)),
+                BlocklistsError::<TestRuntime>::AccountCannotBlockItself
             );
-
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST2)),
-                None
-            ), PostsError::<TestRuntime>::CannotShareSharingPost);
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(None), // No space_handle provided (ok)
-                None, // Default space content,
-                None
-            )); // SpaceId 2 by ACCOUNT1
+    fn block_account_should_fail_when_account_is_already_blocked() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account());
 
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert_noop!(_default_block_account(), BlocklistsError::<TestRuntime>::AccountAlreadyBlocked);
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
-
-            assert_ok!(_delete_default_role());
+    fn unblock_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_block_account());
+            assert_ok!(_default_unblock_account());
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert!(!Blocklists::account_blocked_by_account(ACCOUNT1, ACCOUNT2));
         });
     }
 
-// Profiles tests
-
     #[test]
-    fn create_profile_should_work() {
+    fn unblock_account_should_fail_when_account_is_not_blocked() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile()); // AccountId 1
-
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert_eq!(profile.created.account, ACCOUNT1);
-            assert!(profile.updated.is_none());
-            assert_eq!(profile.content, profile_content_ipfs());
-
-            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+            assert_noop!(_default_unblock_account(), BlocklistsError::<TestRuntime>::AccountNotBlocked);
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_profile_is_already_created() {
+    fn mute_account_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+            assert_ok!(_default_mute_account()); // Mute ACCOUNT2 by ACCOUNT1
+
+            assert!(Blocklists::account_muted_by_account(ACCOUNT1, ACCOUNT2));
+            // Muted account is still able to interact with the muter.
+            assert_ok!(_default_follow_account());
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+    fn mute_account_should_fail_when_account_tries_to_mute_themself() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            assert_noop!(
+                _mute_account(None, Some(ACCOUNT1)),
+                BlocklistsError::<TestRuntime>::AccountCannotMuteItself
+            );
         });
     }
 
     #[test]
-    fn update_profile_should_work() {
+    fn mute_account_should_fail_when_account_is_already_muted() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_ok!(_update_profile(
-                None,
-                Some(space_content_ipfs())
-            ));
+            assert_ok!(_default_mute_account());
 
-            // Check whether profile updated correctly
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert!(profile.updated.is_some());
-            assert_eq!(profile.content, space_content_ipfs());
-
-            // Check whether profile history is written correctly
-            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
-            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+            assert_noop!(_default_mute_account(), BlocklistsError::<TestRuntime>::AccountAlreadyMuted);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_social_account_not_found() {
+    fn unmute_account_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+            assert_ok!(_default_mute_account());
+            assert_ok!(_default_unmute_account());
+
+            assert!(!Blocklists::account_muted_by_account(ACCOUNT1, ACCOUNT2));
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_account_has_no_profile() {
+    fn unmute_account_should_fail_when_account_is_not_muted() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+            assert_noop!(_default_unmute_account(), BlocklistsError::<TestRuntime>::AccountNotMuted);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+    fn follow_account_should_fail_when_follower_is_blocked() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_update_profile(
-                None,
-                None
-            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+            assert_ok!(_default_block_account()); // Block ACCOUNT2 by ACCOUNT1
+
+            assert_noop!(_default_follow_account(), UtilsError::<TestRuntime>::AccountIsBlockedByAccount);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+    fn follow_account_should_work_after_follower_is_unblocked() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            assert_noop!(_update_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            assert_ok!(_default_block_account());
+            assert_ok!(_default_unblock_account());
+
+            assert_ok!(_default_follow_account());
         });
     }
 
-// Space following tests
+    #[test]
+    fn create_comment_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_block_account()); // Block ACCOUNT2 by ACCOUNT1
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                UtilsError::<TestRuntime>::AccountIsBlockedByAccount
+            );
+        });
+    }
 
     #[test]
-    fn follow_space_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+    fn create_comment_should_fail_when_blocked_by_parent_comment_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT3)), None, None, None)); // PostId 2
+            assert_ok!(_block_account(Some(Origin::signed(ACCOUNT3)), None));
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
-            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
-            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, Some(Some(2)), None),
+                UtilsError::<TestRuntime>::AccountIsBlockedByAccount
+            );
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_space_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn create_post_reaction_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_default_block_account()); // Block ACCOUNT2 by ACCOUNT1
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None),
+                UtilsError::<TestRuntime>::AccountIsBlockedByAccount
+            );
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_account_is_already_space_follower() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+    fn update_post_reaction_should_fail_when_blocked_by_post_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_default_block_account());
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_downvote())),
+                UtilsError::<TestRuntime>::AccountIsBlockedByAccount
+            );
         });
     }
 
+// Reaped account cleanup tests
+
+    fn _reap_account2() -> DispatchResult {
+        // Transfer the whole balance of ACCOUNT2, so it goes below the existential deposit.
+        Balances::transfer(Origin::signed(ACCOUNT2), ACCOUNT1, 100)
+    }
+
+    fn is_reaped_account_cleanup_queue_empty() -> bool {
+        Profiles::first_reaped_account_index() == Profiles::next_reaped_account_index()
+    }
+
+    /// Run `on_initialize` of profiles until all reaped accounts are cleaned up.
+    fn clean_up_reaped_accounts() {
+        while !is_reaped_account_cleanup_queue_empty() {
+            Profiles::on_initialize(System::block_number());
+        }
+    }
+
     #[test]
-    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
+    fn reaped_account_should_be_removed_from_account_follows() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), Some(ACCOUNT2)));
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            assert!(all_accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(all_account_followers(ACCOUNT2).is_empty());
+            assert!(all_account_followers(ACCOUNT1).is_empty());
+            assert!(all_accounts_followed_by_account(ACCOUNT3).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT3, ACCOUNT2)), false);
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().followers_count, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().following_accounts_count, 0);
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_none());
         });
     }
 
     #[test]
-    fn unfollow_space_should_work() {
+    fn reaped_account_should_be_removed_from_space_followers() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space());
-            // Follow SpaceId 1 by ACCOUNT2
-            assert_ok!(_default_unfollow_space());
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
 
+            assert!(all_spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(all_space_followers(SPACE1), vec![ACCOUNT1]);
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), false);
             assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
-            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
         });
     }
 
     #[test]
-    fn unfollow_space_should_fail_when_space_not_found() {
-        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn reaped_account_should_lose_granted_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(|| {
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            assert!(Roles::users_by_role_id(ROLE1).is_empty());
+            assert!(Roles::users_by_role_id(ROLE2).is_empty());
+            assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
         });
     }
 
     #[test]
-    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+    fn reaped_account_should_lose_follow_requests() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
+            assert_ok!(_require_account_follow_approval());
+            assert_ok!(_require_space_follow_approval());
+            assert_ok!(_default_follow_account()); // Request to follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_follow_space()); // Request to follow SpaceId 1 by ACCOUNT2
+
+            assert_ok!(ProfileFollows::set_follow_approval_required(Origin::signed(ACCOUNT2), true));
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), Some(ACCOUNT2)));
+
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            assert!(all_follow_requests_to_account(ACCOUNT1).is_empty());
+            assert!(all_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert!(all_follow_requests_to_account(ACCOUNT2).is_empty());
+            assert!(all_follow_requests_by_account(ACCOUNT3).is_empty());
+            assert_eq!(ProfileFollows::follow_requested_by_account((ACCOUNT3, ACCOUNT2)), false);
+            assert!(all_follow_requests_to_space(SPACE1).is_empty());
+            assert!(all_space_follow_requests_by_account(ACCOUNT2).is_empty());
+            assert_eq!(SpaceFollows::space_follow_requested_by_account((ACCOUNT2, SPACE1)), false);
         });
     }
 
-// Account following tests
-
     #[test]
-    fn follow_account_should_work() {
+    fn reaped_account_should_lose_blocklists_and_reputation_diffs() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_block_account(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT1)));
+            assert_ok!(_mute_account(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT3)));
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), Some(ACCOUNT2)));
+            assert!(Scores::account_reputation_diff_by_account((ACCOUNT3, ACCOUNT2, scoring_action_follow_account())).is_some());
 
-            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
-            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            assert_eq!(Blocklists::account_blocked_by_account(ACCOUNT2, ACCOUNT1), false);
+            assert_eq!(Blocklists::account_muted_by_account(ACCOUNT2, ACCOUNT3), false);
+            assert!(Scores::account_reputation_diff_by_account((ACCOUNT3, ACCOUNT2, scoring_action_follow_account())).is_none());
+            assert!(Scores::reputation_decayed_at(ACCOUNT2).is_none());
         });
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
+    fn reaped_account_should_lose_profile_reactions() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_follow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+
+            let target = ReactionTarget::Profile(ACCOUNT2);
+            assert_ok!(_create_reaction(Some(Origin::signed(ACCOUNT1)), target.clone(), None));
+
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            assert!(Reactions::reaction_ids_by_target(&target).is_empty());
+            assert!(Reactions::reaction_by_id(REACTION1).is_none());
+            assert!(!Reactions::has_reacted(&ACCOUNT1, &target, reaction_upvote()));
+            assert_eq!(Reactions::reactions_count_by_kind(target, reaction_upvote()), 0);
         });
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_is_already_following_account() {
+    fn reaped_account_should_leave_reputation_decay_queue() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
+            _give_reputation(ACCOUNT1, ACCOUNT3, 100);
+            _give_reputation(ACCOUNT2, ACCOUNT3, 100);
+            _give_reputation(ACCOUNT3, ACCOUNT1, 100);
+            assert_eq!(Scores::decay_index_by_account(ACCOUNT2), Some(1));
 
-            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+            assert_ok!(_reap_account2());
+            clean_up_reaped_accounts();
+
+            // The last account of the queue takes the place of the reaped one.
+            assert_eq!(Scores::decaying_accounts_count(), 2);
+            assert_eq!(Scores::decaying_account_by_index(1), Some(ACCOUNT3));
+            assert_eq!(Scores::decay_index_by_account(ACCOUNT3), Some(1));
+            assert!(Scores::decaying_account_by_index(2).is_none());
+            assert!(Scores::decay_index_by_account(ACCOUNT2).is_none());
+            assert!(Scores::reputation_decayed_at(ACCOUNT2).is_none());
         });
     }
 
     #[test]
-    fn unfollow_account_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            // Follow ACCOUNT1 by ACCOUNT2
-            assert_ok!(_default_unfollow_account());
+    fn reaped_account_should_be_cleaned_up_in_batches() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT3)), Some(ACCOUNT2)));
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
 
-            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
-            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            // Nothing but a social account is removed inside of a balance transfer.
+            assert_ok!(_reap_account2());
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_none());
+            assert_eq!(all_accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
+            assert!(!is_reaped_account_cleanup_queue_empty());
+
+            // Only `MaxReapedAccountEntriesPerBlock` (2) entries are removed per block.
+            Profiles::on_initialize(System::block_number());
+            assert!(all_accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(all_account_followers(ACCOUNT2).is_empty());
+            assert_eq!(all_spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
+            assert!(!is_reaped_account_cleanup_queue_empty());
+
+            Profiles::on_initialize(System::block_number() + 1);
+            assert!(all_spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert!(is_reaped_account_cleanup_queue_empty());
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+    fn reaped_account_should_lose_profile() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_unfollow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+
+            assert_ok!(_reap_account2());
+
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_none());
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+    fn account_with_reserved_handle_deposit_should_not_be_reaped() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            assert_ok!(_default_unfollow_account());
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None));
+            assert_ok!(_update_profile_handle(Some(Origin::signed(ACCOUNT2)), None));
 
-            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+            let free_balance = Balances::free_balance(ACCOUNT2);
+            assert_ok!(Balances::transfer(Origin::signed(ACCOUNT2), ACCOUNT1, free_balance));
+
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_some());
+            assert_eq!(find_profile_id_by_handle(profile_handle()), Some(ACCOUNT2));
         });
     }
 
@@ -2,7 +2,7 @@ use crate::*;
 
 use frame_support::dispatch::DispatchError;
 use pallet_posts::Module as Posts;
-use pallet_spaces::Space;
+use pallet_spaces::{AfterSpaceDeleted, Space};
 use pallet_space_follows::Module as SpaceFollows;
 use df_traits::moderation::*;
 
@@ -11,6 +11,15 @@ impl<T: Trait> Module<T> {
         Ok(Self::report_by_id(report_id).ok_or(Error::<T>::ReportNotFound)?)
     }
 
+    /// Convert an IPFS CID of `EntityId::Content` to its canonical form, so the same content
+    /// cannot be reported, allowed or blocked under a differently encoded CID.
+    pub fn normalize_entity(entity: EntityId<T::AccountId>) -> Result<EntityId<T::AccountId>, DispatchError> {
+        match entity {
+            EntityId::Content(content) => Utils::<T>::normalize_content(content).map(EntityId::Content),
+            _ => Ok(entity),
+        }
+    }
+
     /// Get entity space_id if it exists.
     /// Content and Account has no scope, consider check with `if let Some`
     fn get_entity_scope(entity: &EntityId<T::AccountId>) -> Result<Option<SpaceId>, DispatchError> {
@@ -34,6 +43,11 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    /// Invalid content cannot be blocked, so it is looked up as is.
+    fn normalize_content_or_keep(content: Content) -> Content {
+        Utils::<T>::normalize_content(content.clone()).unwrap_or(content)
+    }
+
     #[allow(dead_code)]
     // fixme: do we need this?
     fn ensure_entity_exists(entity: &EntityId<T::AccountId>) -> DispatchResult {
@@ -155,14 +169,20 @@ impl<T: Trait> IsPostBlocked<PostId> for Module<T> {
 
 impl<T: Trait> IsContentBlocked for Module<T> {
     fn is_blocked_content(content: Content, scope: SpaceId) -> bool {
-        let entity = EntityId::Content(content);
+        let entity = EntityId::Content(Self::normalize_content_or_keep(content));
 
         Self::status_by_entity_in_space(entity, scope) == Some(EntityStatus::Blocked)
     }
 
     fn is_allowed_content(content: Content, scope: SpaceId) -> bool {
-        let entity = EntityId::Content(content);
+        let entity = EntityId::Content(Self::normalize_content_or_keep(content));
 
         Self::status_by_entity_in_space(entity, scope) != Some(EntityStatus::Blocked)
     }
 }
+
+impl<T: Trait> AfterSpaceDeleted<T> for Module<T> {
+    fn after_space_deleted(_account: T::AccountId, space: &Space<T>) {
+        ModerationSettings::remove(space.id);
+    }
+}
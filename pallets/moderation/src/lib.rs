@@ -228,6 +228,7 @@ decl_module! {
             reason: Content
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let entity = Self::normalize_entity(entity)?;
 
             // TODO check this func, if looks strange
             Utils::<T>::ensure_content_is_some(&reason).map_err(|_| Error::<T>::ReasonIsEmpty)?;
@@ -264,6 +265,7 @@ decl_module! {
             report_id_opt: Option<ReportId>
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let entity = Self::normalize_entity(entity)?;
 
             if let Some(report_id) = report_id_opt {
                 let report = Self::require_report(report_id)?;
@@ -315,6 +317,7 @@ decl_module! {
             status_opt: Option<EntityStatus>
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let entity = Self::normalize_entity(entity)?;
 
             // TODO: add `forbid_content` parameter and track entity Content blocking via OCW
             //  - `forbid_content` - whether to block `Content` provided with entity.
@@ -346,6 +349,7 @@ decl_module! {
             scope: SpaceId
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let entity = Self::normalize_entity(entity)?;
 
             let status = Self::status_by_entity_in_space(&entity, scope);
             ensure!(status.is_some(), Error::<T>::EntityHasNoStatusInScope);
@@ -14,7 +14,7 @@ use sp_io::TestExternalities;
 
 use pallet_utils::{Content, SpaceId};
 use pallet_spaces::{RESERVED_SPACE_COUNT, SpaceById};
-use pallet_posts::{PostId, PostExtension};
+use pallet_posts::{PostId, PostExtension, mock_scheduler::MockScheduler};
 
 pub use pallet_utils::mock_functions::valid_content_ipfs;
 
@@ -74,6 +74,8 @@ impl pallet_timestamp::Trait for Test {
 parameter_types! {
     pub const MinHandleLen: u32 = 5;
     pub const MaxHandleLen: u32 = 50;
+    pub const MaxRawContentLen: u32 = 1024;
+    pub const RawContentDepositPerByte: u64 = 0;
 }
 
 impl pallet_utils::Trait for Test {
@@ -81,6 +83,8 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type MaxRawContentLen = MaxRawContentLen;
+    type RawContentDepositPerByte = RawContentDepositPerByte;
 }
 
 parameter_types! {
@@ -103,6 +107,10 @@ impl pallet_permissions::Trait for Test {
     type DefaultSpacePermissions = DefaultSpacePermissions;
 }
 
+parameter_types! {
+    pub const MaxSpaceDepth: u32 = 10;
+}
+
 impl pallet_spaces::Trait for Test {
     type Event = ();
     type Currency = Balances;
@@ -110,45 +118,70 @@ impl pallet_spaces::Trait for Test {
     type SpaceFollows = SpaceFollows;
     type BeforeSpaceCreated = SpaceFollows;
     type AfterSpaceUpdated = ();
+    type BeforeSpaceDeleted = ();
+    type AfterSpaceDeleted = Moderation;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
     type HandleDeposit = ();
+    type IsHandleReserved = ();
+    type MaxSpaceDepth = MaxSpaceDepth;
+}
+
+parameter_types! {
+    pub const MaxSpaceFollowersToCleanUpPerBlock: u32 = 100;
 }
 
 impl pallet_space_follows::Trait for Test {
     type Event = ();
     type BeforeSpaceFollowed = ();
     type BeforeSpaceUnfollowed = ();
+    type MaxSpaceFollowersToCleanUpPerBlock = MaxSpaceFollowersToCleanUpPerBlock;
 }
 
 parameter_types! {
     pub const MaxCommentDepth: u32 = 10;
+    pub const MaxRepliesToProcessPerDeletePost: u16 = 100;
 }
 
 impl pallet_posts::Trait for Test {
     type Event = ();
     type MaxCommentDepth = MaxCommentDepth;
     type PostScores = ();
+    type MaxRepliesToProcessPerDeletePost = MaxRepliesToProcessPerDeletePost;
     type AfterPostUpdated = ();
+    type AfterPostDeleted = ();
     type IsPostBlocked = Moderation;
+    type IsBlockedByAccount = ();
+    type ScheduledCall = pallet_posts::Call<Test>;
+    type PalletsOrigin = system::RawOrigin<AccountId>;
+    type Scheduler = MockScheduler<Test>;
 }
 
 parameter_types! {
     pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+    pub const MaxExpiredRoleEntriesPerBlock: u32 = 40;
+    pub const MaxRoleEntriesToCleanUpPerBlock: u32 = 40;
 }
 
 impl pallet_roles::Trait for Test {
     type Event = ();
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxExpiredRoleEntriesPerBlock = MaxExpiredRoleEntriesPerBlock;
+    type MaxRoleEntriesToCleanUpPerBlock = MaxRoleEntriesToCleanUpPerBlock;
     type Spaces = Spaces;
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
+    type Reputation = ();
 }
 
 impl pallet_profiles::Trait for Test {
     type Event = ();
     type AfterProfileUpdated = ();
+    type HandleDeposit = ();
+    type IsHandleReserved = ();
+    type ReapedAccountCleanup = ();
+    type MaxReapedAccountEntriesPerBlock = ();
 }
 
 parameter_types! {
@@ -169,6 +202,7 @@ type Posts = pallet_posts::Module<Test>;
 type Roles = pallet_roles::Module<Test>;
 
 pub type AccountId = u64;
+pub type BlockNumber = u64;
 
 pub struct ExtBuilder;
 
@@ -275,6 +309,11 @@ pub(crate) fn create_space_and_post() {
     ));
 }
 
+pub(crate) fn delete_post_and_space() {
+    assert_ok!(Posts::delete_post(Origin::signed(ACCOUNT_SCOPE_OWNER), POST1));
+    assert_ok!(Spaces::delete_space(Origin::signed(ACCOUNT_SCOPE_OWNER), SPACE1));
+}
+
 pub(crate) fn _report_default_post() -> DispatchResult {
     _report_entity(None, None, None, None)
 }
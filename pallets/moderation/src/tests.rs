@@ -1,6 +1,7 @@
 use crate::{Error, mock::*};
 use crate::*;
 
+use df_traits::moderation::IsContentBlocked;
 use frame_support::{assert_ok, assert_noop};
 use pallet_posts::PostById;
 use pallet_utils::{
@@ -51,6 +52,41 @@ fn report_entity_should_fail_when_reason_is_invalid_ipfs_cid() {
     });
 }
 
+/// The same content as `valid_content_ipfs()`, but encoded as CIDv1 in base36.
+fn valid_content_ipfs_as_cid_v1() -> Content {
+    Content::IPFS(b"K2JMTXSEQZ46SOLM5WN95OZ85WNBQOJ14L9QCJH3KKUBQ21GE0RXET27".to_vec())
+}
+
+#[test]
+fn report_entity_should_fail_when_content_is_already_reported_with_another_cid_encoding() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_ok!(_report_entity(None, Some(EntityId::Content(valid_content_ipfs())), None, None));
+
+        assert_noop!(
+            _report_entity(
+                None,
+                Some(EntityId::Content(valid_content_ipfs_as_cid_v1())),
+                None,
+                None
+            ), Error::<Test>::AlreadyReportedEntity
+        );
+    });
+}
+
+#[test]
+fn report_entity_should_fail_when_reported_content_has_invalid_ipfs_cid() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_noop!(
+            _report_entity(
+                None,
+                Some(EntityId::Content(invalid_content_ipfs())),
+                None,
+                None
+            ), UtilsError::<Test>::InvalidIpfsCid
+        );
+    });
+}
+
 #[test]
 fn report_entity_should_fail_when_invalid_scope_provided() {
     ExtBuilder::build().execute_with(|| {
@@ -268,6 +304,17 @@ fn update_moderation_settings_should_work() {
     });
 }
 
+#[test]
+fn moderation_settings_should_be_removed_when_space_deleted() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_ok!(_update_autoblock_threshold_in_moderation_settings());
+
+        delete_post_and_space();
+
+        assert!(Moderation::moderation_settings(SPACE1).is_none());
+    });
+}
+
 // TODO test that autoblock works
 
 #[test]
@@ -305,3 +352,18 @@ fn update_moderation_settings_should_fail_when_origin_has_no_permission() {
         );
     });
 }
+
+#[test]
+fn update_entity_status_should_block_content_regardless_of_cid_encoding() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_ok!(_update_entity_status(
+            None,
+            Some(EntityId::Content(valid_content_ipfs())),
+            None,
+            Some(Some(EntityStatus::Blocked))
+        ));
+
+        assert!(Moderation::is_blocked_content(valid_content_ipfs(), SPACE1));
+        assert!(Moderation::is_blocked_content(valid_content_ipfs_as_cid_v1(), SPACE1));
+    });
+}
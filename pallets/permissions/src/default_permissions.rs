@@ -27,6 +27,7 @@ parameter_types! {
       SP::Upvote,
       SP::Downvote,
       SP::Share,
+      SP::React,
     ].into_iter())),
 
     // Followers can do everything that everyone else can.
@@ -57,6 +58,8 @@ parameter_types! {
       SP::UpdateEntityStatus,
 
       SP::UpdateSpaceSettings,
+
+      SP::ManageFollowRequests,
     ].into_iter())),
   };
 }
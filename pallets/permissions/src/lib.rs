@@ -82,10 +82,20 @@ pub enum SpacePermission {
 
   /// Allows to update space settings across different pallets.
   UpdateSpaceSettings,
+
+  /// Accept or reject requests to follow this space.
+  ManageFollowRequests,
+
+  /// Add emoji reactions to any post or comment in this space.
+  React,
 }
 
 pub type SpacePermissionSet = BTreeSet<SpacePermission>;
 
+/// Permission overrides of parent spaces that a space inherits,
+/// ordered from the nearest parent to the farthest ancestor.
+pub type InheritedSpacePermissions = Vec<(SpaceId, Option<SpacePermissions>)>;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SpacePermissions {
   pub none: Option<SpacePermissionSet>,
@@ -110,7 +120,11 @@ pub struct SpacePermissionsContext {
   pub space_id: SpaceId,
   pub is_space_owner: bool,
   pub is_space_follower: bool,
-  pub space_perms: Option<SpacePermissions>
+  pub space_perms: Option<SpacePermissions>,
+  /// Overrides of parent spaces, if this space inherits permissions from its parent.
+  pub inherited_perms: InheritedSpacePermissions,
+  /// Overrides of a post, if a permission is checked for a post or its comments.
+  pub post_perms: Option<SpacePermissions>,
 }
 
 /// The pallet's configuration trait.
@@ -150,11 +164,27 @@ impl<T: Trait> Module<T> {
   }
 
   fn resolve_space_perms(
+    post_perms: Option<SpacePermissions>,
     space_perms: Option<SpacePermissions>,
+    inherited_perms: InheritedSpacePermissions,
   ) -> SpacePermissions {
 
     let defaults = T::DefaultSpacePermissions::get();
-    let overrides = space_perms.unwrap_or_default();
+    let mut overrides = post_perms.unwrap_or_default();
+
+    // Every role that is not overridden in a post is taken from its space,
+    // and then from the nearest parent space that overrides it.
+    let space_and_inherited_perms = sp_std::iter::once(space_perms)
+      .chain(inherited_perms.into_iter().map(|(_, parent_perms_opt)| parent_perms_opt));
+
+    for next_perms in space_and_inherited_perms.flatten() {
+      overrides = SpacePermissions {
+        none: Self::get_overrides_or_defaults(overrides.none, next_perms.none),
+        everyone: Self::get_overrides_or_defaults(overrides.everyone, next_perms.everyone),
+        follower: Self::get_overrides_or_defaults(overrides.follower, next_perms.follower),
+        space_owner: Self::get_overrides_or_defaults(overrides.space_owner, next_perms.space_owner)
+      };
+    }
 
     SpacePermissions {
       none: Self::get_overrides_or_defaults(overrides.none, defaults.none),
@@ -169,7 +199,7 @@ impl<T: Trait> Module<T> {
     permission: SpacePermission,
   ) -> Option<bool> {
 
-    let perms_by_role = Self::resolve_space_perms(ctx.space_perms);
+    let perms_by_role = Self::resolve_space_perms(ctx.post_perms, ctx.space_perms, ctx.inherited_perms);
 
     // Check if this permission is forbidden:
     if permission.is_present_in_role(perms_by_role.none) {
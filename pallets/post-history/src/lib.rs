@@ -6,7 +6,7 @@ use sp_runtime::RuntimeDebug;
 use sp_std::prelude::Vec;
 use frame_system::{self as system};
 
-use pallet_posts::{PostId, Post, PostUpdate, AfterPostUpdated};
+use pallet_posts::{PostId, Post, PostUpdate, AfterPostUpdated, AfterPostDeleted};
 use pallet_utils::WhoAndWhen;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
@@ -48,3 +48,9 @@ impl<T: Trait> AfterPostUpdated<T> for Module<T> {
             ids.push(PostHistoryRecord::<T>::new(sender, old_data)));
     }
 }
+
+impl<T: Trait> AfterPostDeleted<T> for Module<T> {
+    fn after_post_deleted(_account: T::AccountId, post: &Post<T>) {
+        <EditHistory<T>>::remove(post.id);
+    }
+}
@@ -1,6 +1,10 @@
-use frame_support::dispatch::DispatchResult;
+use codec::FullEncode;
+use frame_support::{
+    dispatch::DispatchResult,
+    storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap},
+};
 
-use pallet_utils::{SpaceId, remove_from_vec};
+use pallet_utils::SpaceId;
 
 use super::*;
 
@@ -27,7 +31,8 @@ impl<T: Trait> Post<T> {
             shares_count: 0,
             upvotes_count: 0,
             downvotes_count: 0,
-            score: 0
+            score: 0,
+            permissions: None,
         }
     }
 
@@ -196,6 +201,165 @@ impl<T: Trait> Module<T> {
         )
     }
 
+    /// Check a permission of account in a space of this post,
+    /// taking into account permission overrides of a root post.
+    pub fn ensure_account_has_post_permission(
+        account: T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>,
+        permission: SpacePermission,
+        error: DispatchError,
+    ) -> DispatchResult {
+        let root_post = post.get_root_post()?;
+
+        let mut ctx = Spaces::get_permissions_context(&account, space);
+        ctx.post_perms = root_post.permissions;
+
+        T::Roles::ensure_account_has_space_permission(
+            account,
+            ctx,
+            permission,
+            error,
+        )
+    }
+
+    pub fn ensure_account_can_delete_post(
+        account: &T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        let is_owner = post.is_owner(account);
+
+        let permission_to_check: SpacePermission;
+        let permission_error: DispatchError;
+
+        if post.is_comment() {
+          if is_owner {
+            permission_to_check = SpacePermission::DeleteOwnComments;
+            permission_error = Error::<T>::NoPermissionToDeleteOwnComments.into();
+          } else {
+            return Err(Error::<T>::NotACommentAuthor.into());
+          }
+        } else if is_owner {
+          permission_to_check = SpacePermission::DeleteOwnPosts;
+          permission_error = Error::<T>::NoPermissionToDeleteOwnPosts.into();
+        } else {
+          permission_to_check = SpacePermission::DeleteAnyPost;
+          permission_error = Error::<T>::NoPermissionToDeleteAnyPost.into();
+        }
+
+        Spaces::ensure_account_has_space_permission(
+          account.clone(),
+          space,
+          permission_to_check,
+          permission_error
+        )
+    }
+
+    pub fn ensure_account_can_hide_post(
+        account: &T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        let is_owner = post.is_owner(account);
+
+        let (permission_to_check, permission_error) = match (post.is_comment(), is_owner) {
+            (true, true) => (SpacePermission::HideOwnComments, Error::<T>::NoPermissionToHideOwnComments),
+            (true, false) => (SpacePermission::HideAnyComment, Error::<T>::NoPermissionToHideAnyComment),
+            (false, true) => (SpacePermission::HideOwnPosts, Error::<T>::NoPermissionToHideOwnPosts),
+            (false, false) => (SpacePermission::HideAnyPost, Error::<T>::NoPermissionToHideAnyPost),
+        };
+
+        Spaces::ensure_account_has_space_permission(
+          account.clone(),
+          space,
+          permission_to_check,
+          permission_error.into()
+        )
+    }
+
+    /// Hide or unhide a post (or a comment) and update the hidden counters
+    /// of its space (for root posts) or of its ancestors (for comments).
+    pub fn try_update_post_hidden(who: T::AccountId, post_id: PostId, hidden: bool) -> DispatchResult {
+        let mut post = Self::require_post(post_id)?;
+
+        if hidden {
+            ensure!(!post.hidden, Error::<T>::PostIsAlreadyHidden);
+        } else {
+            ensure!(post.hidden, Error::<T>::PostIsNotHidden);
+        }
+
+        let space_opt = post.try_get_space();
+
+        if let Some(space) = &space_opt {
+            ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+            Self::ensure_account_can_hide_post(&who, &post, space)?;
+        } else {
+            post.ensure_owner(&who)?;
+        }
+
+        if let PostExtension::Comment(comment_ext) = post.extension {
+            Self::update_counters_on_comment_hidden_change(&comment_ext, hidden)?;
+        } else if let Some(mut space) = space_opt {
+            if hidden {
+                space.inc_hidden_posts();
+            } else {
+                space.dec_hidden_posts();
+            }
+            <SpaceById<T>>::insert(space.id, space);
+        }
+
+        let old_data = PostUpdate {
+            hidden: Some(post.hidden),
+            ..PostUpdate::default()
+        };
+
+        post.hidden = hidden;
+        post.updated = Some(WhoAndWhen::<T>::new(who.clone()));
+        <PostById<T>>::insert(post_id, post.clone());
+
+        Self::cancel_scheduled_publishing_if_any(post_id);
+
+        T::AfterPostUpdated::after_post_updated(who.clone(), &post, old_data);
+
+        Self::deposit_event(RawEvent::PostUpdated(who, post_id));
+        Ok(())
+    }
+
+    pub(crate) fn ensure_publish_at_in_future(publish_at: T::BlockNumber) -> DispatchResult {
+        ensure!(publish_at > <system::Module<T>>::block_number(), Error::<T>::PublishAtShouldBeInFuture);
+        Ok(())
+    }
+
+    pub(crate) fn scheduled_post_task_name(post_id: PostId) -> Vec<u8> {
+        (SCHEDULED_POSTS_ID, post_id).encode()
+    }
+
+    pub(crate) fn schedule_post_publishing(post_id: PostId, publish_at: T::BlockNumber) -> DispatchResult {
+        T::Scheduler::schedule_named(
+            Self::scheduled_post_task_name(post_id),
+            DispatchTime::At(publish_at),
+            None,
+            1,
+            system::RawOrigin::Root.into(),
+            Call::publish_scheduled_post(post_id).into()
+        ).map_err(|_| Error::<T>::CannotSchedulePostPublishing)?;
+        Ok(())
+    }
+
+    pub(crate) fn cancel_post_publishing(post_id: PostId) {
+        // A task could be already dispatched or canceled, so we ignore a possible error here.
+        let _ = T::Scheduler::cancel_named(Self::scheduled_post_task_name(post_id));
+    }
+
+    /// Cancel publishing of a post if it is scheduled.
+    /// A manual change of post visibility overrides its scheduled publishing.
+    pub(crate) fn cancel_scheduled_publishing_if_any(post_id: PostId) {
+        if <ScheduledPostPublishAt<T>>::take(post_id).is_some() {
+            Self::cancel_post_publishing(post_id);
+        }
+    }
+
     /// Check that there is a `Post` with such `post_id` in the storage
     /// or return`PostNotFound` error.
     pub fn ensure_post_exists(post_id: PostId) -> DispatchResult {
@@ -208,6 +372,15 @@ impl<T: Trait> Module<T> {
         Ok(Self::post_by_id(post_id).ok_or(Error::<T>::PostNotFound)?)
     }
 
+    /// Check that an account is not in a personal blocklist of a post owner.
+    pub fn ensure_not_blocked_by_post_owner(account: &T::AccountId, post: &Post<T>) -> DispatchResult {
+        ensure!(
+            !T::IsBlockedByAccount::is_blocked_by_account(account, &post.owner),
+            UtilsError::<T>::AccountIsBlockedByAccount
+        );
+        Ok(())
+    }
+
     fn share_post(
         account: T::AccountId,
         original_post: &mut Post<T>,
@@ -219,7 +392,7 @@ impl<T: Trait> Module<T> {
 
         let original_post_id = original_post.id;
         PostById::insert(original_post_id, original_post.clone());
-        SharedPostIdsByOriginalPostId::mutate(original_post_id, |ids| ids.push(shared_post_id));
+        Self::add_share_to_post_index(original_post_id, shared_post_id);
 
         Self::deposit_event(RawEvent::PostShared(account, original_post_id));
 
@@ -309,6 +482,166 @@ impl<T: Trait> Module<T> {
     }
     // TODO: maybe add for_each_reply?
 
+    /// Get ids of all replies to a post, in the order they were added.
+    pub fn reply_ids_by_post_id(post_id: PostId) -> Vec<PostId> {
+        Self::all_post_ids_in_index::<_, ReplyIdsByPostId>(post_id)
+    }
+
+    /// Get ids of all posts in a space, in the order they were added.
+    pub fn post_ids_by_space_id(space_id: SpaceId) -> Vec<PostId> {
+        Self::all_post_ids_in_index::<_, PostIdsBySpaceId>(space_id)
+    }
+
+    /// Get ids of all posts that shared an original post, in the order they were added.
+    pub fn shared_post_ids_by_original_post_id(original_post_id: PostId) -> Vec<PostId> {
+        Self::all_post_ids_in_index::<_, SharedPostIdsByOriginalPostId>(original_post_id)
+    }
+
+    /// Get up to `limit` ids of the latest posts in a space, the newest first.
+    ///
+    /// Pass `None` as `before` to start from the latest post. The returned cursor should be
+    /// passed as `before` to get the next (older) page; `None` means there are no more posts.
+    /// Gaps left by removed posts are scanned in a limited number of reads, so a page can have
+    /// fewer than `limit` posts (even none) while the cursor still points to older posts.
+    pub fn latest_post_ids_by_space_id(
+        space_id: SpaceId,
+        before: Option<PostIndex>,
+        limit: u32
+    ) -> (Vec<PostId>, Option<PostIndex>) {
+        Self::latest_post_ids_in_index::<_, PostIdsBySpaceId, NextPostIndexBySpaceId>(space_id, before, limit)
+    }
+
+    /// Get up to `limit` ids of the latest replies to a post, the newest first.
+    /// See `latest_post_ids_by_space_id` for how to use the cursor.
+    pub fn latest_reply_ids_by_post_id(
+        post_id: PostId,
+        before: Option<PostIndex>,
+        limit: u32
+    ) -> (Vec<PostId>, Option<PostIndex>) {
+        Self::latest_post_ids_in_index::<_, ReplyIdsByPostId, NextReplyIndexByPostId>(post_id, before, limit)
+    }
+
+    /// Get up to `limit` ids of the latest posts that shared an original post, the newest first.
+    /// See `latest_post_ids_by_space_id` for how to use the cursor.
+    pub fn latest_shared_post_ids_by_original_post_id(
+        original_post_id: PostId,
+        before: Option<PostIndex>,
+        limit: u32
+    ) -> (Vec<PostId>, Option<PostIndex>) {
+        Self::latest_post_ids_in_index::<_, SharedPostIdsByOriginalPostId, NextSharedPostIndexByOriginalPostId>(
+            original_post_id, before, limit
+        )
+    }
+
+    pub(crate) fn add_post_to_space_index(space_id: SpaceId, post_id: PostId) {
+        Self::append_to_post_index::<_, PostIdsBySpaceId, NextPostIndexBySpaceId, SpacePostIndexByPostId>(
+            space_id, post_id
+        );
+    }
+
+    fn remove_post_from_space_index(space_id: SpaceId, post_id: PostId) {
+        Self::remove_from_post_index::<_, PostIdsBySpaceId, SpacePostIndexByPostId>(space_id, post_id);
+    }
+
+    pub(crate) fn add_reply_to_post_index(parent_id: PostId, reply_id: PostId) {
+        Self::append_to_post_index::<_, ReplyIdsByPostId, NextReplyIndexByPostId, ReplyIndexByReplyId>(
+            parent_id, reply_id
+        );
+    }
+
+    fn remove_reply_from_post_index(parent_id: PostId, reply_id: PostId) {
+        Self::remove_from_post_index::<_, ReplyIdsByPostId, ReplyIndexByReplyId>(parent_id, reply_id);
+    }
+
+    pub(crate) fn add_share_to_post_index(original_post_id: PostId, shared_post_id: PostId) {
+        Self::append_to_post_index::<
+            _, SharedPostIdsByOriginalPostId, NextSharedPostIndexByOriginalPostId, SharedPostIndexBySharedPostId
+        >(original_post_id, shared_post_id);
+    }
+
+    fn remove_share_from_post_index(original_post_id: PostId, shared_post_id: PostId) {
+        Self::remove_from_post_index::<_, SharedPostIdsByOriginalPostId, SharedPostIndexBySharedPostId>(
+            original_post_id, shared_post_id
+        );
+    }
+
+    /// Put a post id at the end of a post index list identified by `key`
+    /// and remember its position, so it can be removed without scanning the list.
+    fn append_to_post_index<K, Ids, NextIndex, IndexOf>(key: K, post_id: PostId)
+    where
+        K: FullEncode + Copy,
+        Ids: StorageDoubleMap<K, PostIndex, PostId, Query = Option<PostId>>,
+        NextIndex: StorageMap<K, PostIndex, Query = PostIndex>,
+        IndexOf: StorageMap<PostId, PostIndex, Query = Option<PostIndex>>,
+    {
+        let index = NextIndex::get(key);
+        Ids::insert(key, index, post_id);
+        NextIndex::insert(key, index.saturating_add(1));
+        IndexOf::insert(post_id, index);
+    }
+
+    fn remove_from_post_index<K, Ids, IndexOf>(key: K, post_id: PostId)
+    where
+        K: FullEncode + Copy,
+        Ids: StorageDoubleMap<K, PostIndex, PostId, Query = Option<PostId>>,
+        IndexOf: StorageMap<PostId, PostIndex, Query = Option<PostIndex>>,
+    {
+        if let Some(index) = IndexOf::take(post_id) {
+            Ids::remove(key, index);
+        }
+    }
+
+    fn clear_post_index<K, Ids, NextIndex>(key: K)
+    where
+        K: FullEncode + Copy,
+        Ids: StorageDoubleMap<K, PostIndex, PostId, Query = Option<PostId>>,
+        NextIndex: StorageMap<K, PostIndex, Query = PostIndex>,
+    {
+        Ids::remove_prefix(key);
+        NextIndex::remove(key);
+    }
+
+    fn all_post_ids_in_index<K, Ids>(key: K) -> Vec<PostId>
+    where
+        K: FullEncode,
+        Ids: IterableStorageDoubleMap<K, PostIndex, PostId>,
+    {
+        // A double map is iterated in the order of key hashes, not in the order of indexes.
+        let mut indexed_ids: Vec<(PostIndex, PostId)> = Ids::iter_prefix(key).collect();
+        indexed_ids.sort_by_key(|(index, _)| *index);
+        indexed_ids.into_iter().map(|(_, post_id)| post_id).collect()
+    }
+
+    fn latest_post_ids_in_index<K, Ids, NextIndex>(
+        key: K,
+        before: Option<PostIndex>,
+        limit: u32
+    ) -> (Vec<PostId>, Option<PostIndex>)
+    where
+        K: FullEncode + Copy,
+        Ids: StorageDoubleMap<K, PostIndex, PostId, Query = Option<PostId>>,
+        NextIndex: StorageMap<K, PostIndex, Query = PostIndex>,
+    {
+        let next_index = NextIndex::get(key);
+        let mut index = before.map_or(next_index, |before| before.min(next_index));
+        let mut post_ids = Vec::new();
+
+        let max_scanned = limit.saturating_mul(SCANNED_INDEXES_PER_POST).min(MAX_SCANNED_INDEXES);
+        let mut scanned: u32 = 0;
+
+        // Removed posts leave gaps in the index, they are skipped here.
+        while index > 0 && post_ids.len() < limit as usize && scanned < max_scanned {
+            index -= 1;
+            scanned += 1;
+            if let Some(post_id) = Ids::get(key, index) {
+                post_ids.push(post_id);
+            }
+        }
+
+        let cursor = if index > 0 { Some(index) } else { None };
+        (post_ids, cursor)
+    }
+
     pub(crate) fn create_comment(
         creator: &T::AccountId,
         new_post_id: PostId,
@@ -317,9 +650,12 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         let mut commented_post_id = root_post.id;
 
+        Self::ensure_not_blocked_by_post_owner(creator, root_post)?;
+
         if let Some(parent_id) = comment_ext.parent_id {
             let parent_comment = Self::post_by_id(parent_id).ok_or(Error::<T>::UnknownParentComment)?;
             ensure!(parent_comment.is_comment(), Error::<T>::NotACommentByParentId);
+            Self::ensure_not_blocked_by_post_owner(creator, &parent_comment)?;
 
             let ancestors = Self::get_post_ancestors(parent_id);
             ensure!(ancestors.len() < T::MaxCommentDepth::get() as usize, Error::<T>::MaxCommentDepthReached);
@@ -332,7 +668,7 @@ impl<T: Trait> Module<T> {
 
         Self::for_each_post_ancestor(commented_post_id, |post| post.inc_replies())?;
         PostById::insert(root_post.id, root_post);
-        ReplyIdsByPostId::mutate(commented_post_id, |reply_ids| reply_ids.push(new_post_id));
+        Self::add_reply_to_post_index(commented_post_id, new_post_id);
 
         Ok(())
     }
@@ -419,7 +755,7 @@ impl<T: Trait> Module<T> {
                         |space| space.score = space.score.saturating_sub(post.score)
                     )?;
 
-                    PostIdsBySpaceId::mutate(old_space_id, |post_ids| remove_from_vec(post_ids, post.id));
+                    Self::remove_post_from_space_index(old_space_id, post.id);
                 }
 
                 // Increase the number of posts on the new space
@@ -435,7 +771,7 @@ impl<T: Trait> Module<T> {
                     |space| space.score = space.score.saturating_add(post.score)
                 )?;
 
-                PostIdsBySpaceId::mutate(new_space_id, |post_ids| post_ids.push(post.id));
+                Self::add_post_to_space_index(new_space_id, post.id);
 
                 post.space_id = Some(new_space_id);
                 PostById::<T>::insert(post.id, post);
@@ -490,7 +826,7 @@ impl<T: Trait> Module<T> {
             )?;
 
             post.space_id = None;
-            PostIdsBySpaceId::mutate(space_id, |post_ids| remove_from_vec(post_ids, post_id));
+            Self::remove_post_from_space_index(space_id, post_id);
         }
 
         PostById::<T>::insert(post.id, post);
@@ -498,6 +834,79 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Remove a post and all its nested replies from the storage
+    /// and update the counters of the space, the parent posts and the original post (if shared).
+    pub(crate) fn delete_post_with_replies(deleter: T::AccountId, post: &Post<T>) -> DispatchResult {
+        // The post itself goes first, then all its nested replies.
+        let posts_to_delete = Self::try_get_post_replies(post.id);
+
+        let deleted_count = posts_to_delete.len() as u16;
+        let deleted_hidden_count = posts_to_delete.iter().filter(|p| p.hidden).count() as u16;
+
+        match post.extension {
+            PostExtension::Comment(comment_ext) => {
+                let root_post = &mut Self::require_post(comment_ext.root_post_id)?;
+                let commented_post_id = comment_ext.parent_id.unwrap_or(root_post.id);
+
+                let dec_replies_count = move |p: &mut Post<T>| {
+                    p.replies_count = p.replies_count.saturating_sub(deleted_count);
+                    p.hidden_replies_count = p.hidden_replies_count.saturating_sub(deleted_hidden_count);
+                };
+
+                if let Some(parent_id) = comment_ext.parent_id {
+                    Self::for_each_post_ancestor(parent_id, dec_replies_count)?;
+                }
+
+                dec_replies_count(root_post);
+                PostById::<T>::insert(root_post.id, root_post.clone());
+                Self::remove_reply_from_post_index(commented_post_id, post.id);
+            },
+            PostExtension::RegularPost | PostExtension::SharedPost(_) => {
+                if let Some(space_id) = post.space_id {
+
+                    // Decrease the number of posts on the space
+                    Self::mutate_posts_count_on_space(
+                        space_id,
+                        post,
+                        |counter| *counter = counter.saturating_sub(1)
+                    )?;
+
+                    // Decrease a score on the space
+                    Spaces::<T>::mutate_space_by_id(
+                        space_id,
+                        |space| space.score = space.score.saturating_sub(post.score)
+                    )?;
+
+                    Self::remove_post_from_space_index(space_id, post.id);
+                }
+
+                Self::cancel_scheduled_publishing_if_any(post.id);
+            }
+        }
+
+        // The original post could be already deleted, so we ignore a possible error here.
+        if let PostExtension::SharedPost(original_post_id) = post.extension {
+            let _ = Self::mutate_post_by_id(original_post_id, Post::dec_shares);
+            Self::remove_share_from_post_index(original_post_id, post.id);
+        }
+
+        for deleted_post in posts_to_delete.iter() {
+            Utils::<T>::unreserve_content_deposit(&ContentHolder::Post(deleted_post.id));
+            PostById::<T>::remove(deleted_post.id);
+            ReplyIndexByReplyId::remove(deleted_post.id);
+            Self::clear_post_index::<_, ReplyIdsByPostId, NextReplyIndexByPostId>(deleted_post.id);
+            // The number of shares is not bounded, so each share leaves the index when it is deleted.
+            NextSharedPostIndexByOriginalPostId::remove(deleted_post.id);
+        }
+
+        for deleted_post in posts_to_delete.iter() {
+            T::AfterPostDeleted::after_post_deleted(deleter.clone(), deleted_post);
+            Self::deposit_event(RawEvent::PostDeleted(deleter.clone(), deleted_post.id));
+        }
+
+        Ok(())
+    }
+
     /// Rewrite ancestor counters when Post hidden status changes
     /// Warning: This will affect storage state!
     pub(crate) fn update_counters_on_comment_hidden_change(
@@ -3,24 +3,45 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, fail,
-    dispatch::{DispatchError, DispatchResult}, ensure, traits::Get,
+    dispatch::{DispatchError, DispatchResult}, ensure,
+    traits::{Get, LockIdentifier, schedule::{DispatchTime, Named as ScheduleNamed}},
+    weights::Weight,
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_root};
 
-use df_traits::moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked};
-use pallet_permissions::SpacePermission;
+use df_traits::{
+    IsBlockedByAccount, PermissionChecker,
+    moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked},
+};
+use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions};
 use pallet_spaces::{Module as Spaces, Space, SpaceById};
 use pallet_utils::{
     Module as Utils, Error as UtilsError,
-    SpaceId, WhoAndWhen, Content
+    SpaceId, WhoAndWhen, Content, ContentHolder,
 };
 
 pub mod functions;
+pub mod migration;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock_scheduler;
+
+/// A prefix of scheduler task names that publish posts.
+const SCHEDULED_POSTS_ID: LockIdentifier = *b"schdpost";
+
+/// How many indexes can be scanned per requested post id when reading the latest posts.
+const SCANNED_INDEXES_PER_POST: u32 = 4;
+
+/// The max number of indexes scanned by one read of the latest posts, whatever the limit is.
+const MAX_SCANNED_INDEXES: u32 = 1_000;
 
 pub type PostId = u64;
 
+/// A position of a post id in one of the post indexes, e.g. in the list of space posts.
+/// Indexes only grow, so the greater the index, the later a post was added to the list.
+pub type PostIndex = u64;
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Post<T: Trait> {
     pub id: PostId,
@@ -43,6 +64,9 @@ pub struct Post<T: Trait> {
     pub downvotes_count: u16,
 
     pub score: i32,
+
+    /// Overrides of space permissions for this post and its comments.
+    pub permissions: Option<SpacePermissions>,
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
@@ -74,6 +98,23 @@ impl Default for PostExtension {
     }
 }
 
+// A value placed in storage that represents the current version of the Posts storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
@@ -87,9 +128,24 @@ pub trait Trait: system::Trait
 
     type PostScores: PostScores<Self>;
 
+    /// Max number of nested replies that can be removed together with a post.
+    type MaxRepliesToProcessPerDeletePost: Get<u16>;
+
     type AfterPostUpdated: AfterPostUpdated<Self>;
 
+    type AfterPostDeleted: AfterPostDeleted<Self>;
+
     type IsPostBlocked: IsPostBlocked<PostId>;
+
+    type IsBlockedByAccount: IsBlockedByAccount<Self::AccountId>;
+
+    /// A call that publishes a scheduled post.
+    type ScheduledCall: From<Call<Self>>;
+
+    /// An origin that is used to dispatch scheduled calls.
+    type PalletsOrigin: From<system::RawOrigin<Self::AccountId>>;
+
+    type Scheduler: ScheduleNamed<Self::BlockNumber, Self::ScheduledCall, Self::PalletsOrigin>;
 }
 
 pub trait PostScores<T: Trait> {
@@ -111,6 +167,13 @@ pub trait AfterPostUpdated<T: Trait> {
     fn after_post_updated(account: T::AccountId, post: &Post<T>, old_data: PostUpdate);
 }
 
+/// Handler that will be called for every post (or comment) right after it was removed
+/// from the storage. Use it to clean up the data that other pallets store for this post.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterPostDeleted<T: Trait> {
+    fn after_post_deleted(account: T::AccountId, post: &Post<T>);
+}
+
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as PostsModule {
@@ -118,27 +181,71 @@ decl_storage! {
 
         pub PostById get(fn post_by_id): map hasher(twox_64_concat) PostId => Option<Post<T>>;
 
-        pub ReplyIdsByPostId get(fn reply_ids_by_post_id):
-            map hasher(twox_64_concat) PostId => Vec<PostId>;
+        /// Reply ids of a post (1st key) by their index (2nd key) in the list of its replies.
+        pub ReplyIdsByPostId:
+            double_map hasher(twox_64_concat) PostId, hasher(twox_64_concat) PostIndex
+            => Option<PostId>;
+
+        /// An index that will be given to the next reply of a post.
+        pub NextReplyIndexByPostId get(fn next_reply_index_by_post_id):
+            map hasher(twox_64_concat) PostId => PostIndex;
+
+        /// An index of a reply in the list of replies of its parent post.
+        pub ReplyIndexByReplyId get(fn reply_index_by_reply_id):
+            map hasher(twox_64_concat) PostId => Option<PostIndex>;
+
+        /// Post ids of a space (1st key) by their index (2nd key) in the list of space posts.
+        pub PostIdsBySpaceId:
+            double_map hasher(twox_64_concat) SpaceId, hasher(twox_64_concat) PostIndex
+            => Option<PostId>;
 
-        pub PostIdsBySpaceId get(fn post_ids_by_space_id):
-            map hasher(twox_64_concat) SpaceId => Vec<PostId>;
+        /// An index that will be given to the next post added to a space.
+        pub NextPostIndexBySpaceId get(fn next_post_index_by_space_id):
+            map hasher(twox_64_concat) SpaceId => PostIndex;
+
+        /// An index of a post in the list of posts of its space.
+        pub SpacePostIndexByPostId get(fn space_post_index_by_post_id):
+            map hasher(twox_64_concat) PostId => Option<PostIndex>;
 
         // TODO rename 'Shared...' to 'Sharing...'
-        pub SharedPostIdsByOriginalPostId get(fn shared_post_ids_by_original_post_id):
-            map hasher(twox_64_concat) PostId => Vec<PostId>;
+        /// Sharing post ids of an original post (1st key) by their index (2nd key)
+        /// in the list of its shares.
+        pub SharedPostIdsByOriginalPostId:
+            double_map hasher(twox_64_concat) PostId, hasher(twox_64_concat) PostIndex
+            => Option<PostId>;
+
+        /// An index that will be given to the next share of an original post.
+        pub NextSharedPostIndexByOriginalPostId get(fn next_shared_post_index_by_original_post_id):
+            map hasher(twox_64_concat) PostId => PostIndex;
+
+        /// An index of a sharing post in the list of shares of its original post.
+        pub SharedPostIndexBySharedPostId get(fn shared_post_index_by_shared_post_id):
+            map hasher(twox_64_concat) PostId => Option<PostIndex>;
+
+        /// A block number at which a hidden post will be published automatically.
+        pub ScheduledPostPublishAt get(fn scheduled_post_publish_at):
+            map hasher(twox_64_concat) PostId => Option<T::BlockNumber>;
+
+        StorageVersion build(|_: &GenesisConfig| Releases::V3_0_0): Releases;
     }
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
+        <T as system::Trait>::BlockNumber,
     {
         PostCreated(AccountId, PostId),
         PostUpdated(AccountId, PostId),
         PostDeleted(AccountId, PostId),
         PostShared(AccountId, PostId),
         PostMoved(AccountId, PostId),
+        /// A post will be published at a given block.
+        PostScheduled(AccountId, PostId, BlockNumber),
+        /// A scheduled publishing of a post was canceled, the post stays hidden.
+        PostScheduleCanceled(AccountId, PostId),
+        /// A scheduled post was published.
+        ScheduledPostPublished(PostId),
     }
 );
 
@@ -161,6 +268,8 @@ decl_error! {
         NoRepliesOnPost,
         /// Cannot move a post to the same space.
         CannotMoveToSameSpace,
+        /// Post has too many nested replies to be deleted in one transaction.
+        TooManyRepliesToDelete,
 
         // Sharing related errors:
 
@@ -198,6 +307,37 @@ decl_error! {
         NoPermissionToUpdateOwnPosts,
         /// A comment owner is not allowed to update their own comments in this space.
         NoPermissionToUpdateOwnComments,
+        /// User is not a post author and has no permission to delete posts in this space.
+        NoPermissionToDeleteAnyPost,
+        /// A post owner is not allowed to delete their own posts in this space.
+        NoPermissionToDeleteOwnPosts,
+        /// A comment owner is not allowed to delete their own comments in this space.
+        NoPermissionToDeleteOwnComments,
+        /// User has no permission to override permissions of posts in this space.
+        NoPermissionToOverridePostPermissions,
+        /// Permissions can be overridden only on root posts, not on comments.
+        CannotOverridePermissionsOnComment,
+        /// User is not a post author and has no permission to hide posts in this space.
+        NoPermissionToHideAnyPost,
+        /// A post owner is not allowed to hide their own posts in this space.
+        NoPermissionToHideOwnPosts,
+        /// User is not a comment author and has no permission to hide comments in this space.
+        NoPermissionToHideAnyComment,
+        /// A comment owner is not allowed to hide their own comments in this space.
+        NoPermissionToHideOwnComments,
+        /// Post is already hidden.
+        PostIsAlreadyHidden,
+        /// Post is not hidden.
+        PostIsNotHidden,
+
+        // Scheduling related errors:
+
+        /// A block to publish a post at should be in the future.
+        PublishAtShouldBeInFuture,
+        /// Post is not scheduled to be published.
+        PostIsNotScheduled,
+        /// Failed to schedule publishing of a post.
+        CannotSchedulePostPublishing,
     }
 }
 
@@ -206,12 +346,18 @@ decl_module! {
 
     const MaxCommentDepth: u32 = T::MaxCommentDepth::get();
 
+    const MaxRepliesToProcessPerDeletePost: u16 = T::MaxRepliesToProcessPerDeletePost::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
     #[weight = 100_000 + T::DbWeight::get().reads_writes(8, 8)]
     pub fn create_post(
       origin,
@@ -245,22 +391,36 @@ decl_module! {
         error_on_permission_failed = Error::<T>::NoPermissionToCreateComments;
       }
 
-      Spaces::ensure_account_has_space_permission(
+      // Comments should respect permission overrides of their root post.
+      Self::ensure_account_has_post_permission(
         creator.clone(),
+        &root_post,
         &space,
         permission_to_check,
         error_on_permission_failed.into()
       )?;
 
-      match extension {
-        PostExtension::RegularPost => space.inc_posts(),
-        PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id, space)?,
-        PostExtension::Comment(comment_ext) => Self::create_comment(&creator, new_post_id, comment_ext, root_post)?,
+      // Reserve a deposit before any storage write, as it fails if the creator cannot afford it.
+      let deposit_holder = ContentHolder::Post(new_post_id);
+      Utils::<T>::reserve_content_deposit(&creator, deposit_holder.clone(), &new_post.content)?;
+
+      let extension_result = match extension {
+        PostExtension::RegularPost => {
+          space.inc_posts();
+          Ok(())
+        },
+        PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id, space),
+        PostExtension::Comment(comment_ext) => Self::create_comment(&creator, new_post_id, comment_ext, root_post),
+      };
+
+      if let Err(error) = extension_result {
+        Utils::<T>::unreserve_content_deposit(&deposit_holder);
+        return Err(error);
       }
 
       if new_post.is_root_post() {
         SpaceById::insert(space.id, space.clone());
-        PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
+        Self::add_post_to_space_index(space.id, new_post_id);
       }
 
       PostById::insert(new_post_id, new_post);
@@ -270,7 +430,7 @@ decl_module! {
       Ok(())
     }
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(5, 3)]
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(6, 4)]
     pub fn update_post(origin, post_id: PostId, update: PostUpdate) -> DispatchResult {
       let editor = ensure_signed(origin)?;
 
@@ -286,6 +446,10 @@ decl_module! {
       if let Some(space) = &space_opt {
         ensure!(T::IsAccountBlocked::is_allowed_account(editor.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
         Self::ensure_account_can_update_post(&editor, &post, space)?;
+
+        if update.hidden.map_or(false, |hidden| hidden != post.hidden) {
+          Self::ensure_account_can_hide_post(&editor, &post, space)?;
+        }
       }
 
       let mut is_update_applied = false;
@@ -310,18 +474,19 @@ decl_module! {
 
       if let Some(hidden) = update.hidden {
         if hidden != post.hidden {
-          space_opt = space_opt.map(|mut space| {
-            if hidden {
-              space.inc_hidden_posts();
-            } else {
-              space.dec_hidden_posts();
-            }
-
-            space
-          });
-
-          if let PostExtension::Comment(comment_ext) = post.extension {
-            Self::update_counters_on_comment_hidden_change(&comment_ext, hidden)?;
+          // Only root posts are counted in the hidden posts of a space.
+          if post.is_root_post() {
+            space_opt = space_opt.map(|mut space| {
+              if hidden {
+                space.inc_hidden_posts();
+              } else {
+                space.dec_hidden_posts();
+              }
+
+              space
+            });
+          } else {
+            space_opt = None;
           }
 
           old_data.hidden = Some(post.hidden);
@@ -332,6 +497,18 @@ decl_module! {
 
       // Update this post only if at least one field should be updated:
       if is_update_applied {
+        // The editor pays for new content, so reserve a deposit before any storage write.
+        if old_data.content.is_some() {
+          Utils::<T>::update_content_deposit(&editor, ContentHolder::Post(post_id), &post.content)?;
+        }
+
+        if old_data.hidden.is_some() {
+          if let PostExtension::Comment(comment_ext) = post.extension {
+            Self::update_counters_on_comment_hidden_change(&comment_ext, post.hidden)?;
+          }
+          Self::cancel_scheduled_publishing_if_any(post_id);
+        }
+
         post.updated = Some(WhoAndWhen::<T>::new(editor.clone()));
 
         if let Some(space) = space_opt {
@@ -379,5 +556,182 @@ decl_module! {
       Self::deposit_event(RawEvent::PostMoved(who, post_id));
       Ok(())
     }
+
+    /// Hide a post or a comment. Requires `HideOwnPosts`/`HideAnyPost` for posts
+    /// and `HideOwnComments`/`HideAnyComment` for comments.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(6, 4)]
+    pub fn hide_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      Self::try_update_post_hidden(who, post_id, true)
+    }
+
+    /// Make a hidden post or comment visible again. Requires the same permissions as `hide_post`.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(6, 4)]
+    pub fn unhide_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      Self::try_update_post_hidden(who, post_id, false)
+    }
+
+    /// Create a regular post that stays hidden until the `publish_at` block
+    /// and then is published automatically by the scheduler.
+    #[weight = 200_000 + T::DbWeight::get().reads_writes(9, 10)]
+    pub fn create_scheduled_post(
+      origin,
+      space_id: SpaceId,
+      content: Content,
+      publish_at: T::BlockNumber
+    ) -> DispatchResult {
+      let creator = ensure_signed(origin)?;
+
+      Self::ensure_publish_at_in_future(publish_at)?;
+
+      // The scheduler can reject a task, so schedule it before the post is written.
+      let post_id = Self::next_post_id();
+      Self::schedule_post_publishing(post_id, publish_at)?;
+
+      if let Err(error) = Self::create_post(
+        system::RawOrigin::Signed(creator.clone()).into(),
+        Some(space_id),
+        PostExtension::RegularPost,
+        content
+      ) {
+        Self::cancel_post_publishing(post_id);
+        return Err(error);
+      }
+
+      let mut post = Self::require_post(post_id)?;
+      let mut space = post.get_space()?;
+
+      post.hidden = true;
+      space.inc_hidden_posts();
+
+      <PostById<T>>::insert(post_id, post);
+      <SpaceById<T>>::insert(space.id, space);
+      <ScheduledPostPublishAt<T>>::insert(post_id, publish_at);
+
+      Self::deposit_event(RawEvent::PostScheduled(creator, post_id, publish_at));
+      Ok(())
+    }
+
+    /// Change a block at which a scheduled post will be published.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 2)]
+    pub fn reschedule_post(origin, post_id: PostId, publish_at: T::BlockNumber) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      post.ensure_owner(&who)?;
+
+      ensure!(<ScheduledPostPublishAt<T>>::contains_key(post_id), Error::<T>::PostIsNotScheduled);
+      Self::ensure_publish_at_in_future(publish_at)?;
+
+      T::Scheduler::reschedule_named(Self::scheduled_post_task_name(post_id), DispatchTime::At(publish_at))?;
+      <ScheduledPostPublishAt<T>>::insert(post_id, publish_at);
+
+      Self::deposit_event(RawEvent::PostScheduled(who, post_id, publish_at));
+      Ok(())
+    }
+
+    /// Cancel a scheduled publishing of a post. The post stays hidden.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 2)]
+    pub fn cancel_scheduled_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+      post.ensure_owner(&who)?;
+
+      ensure!(<ScheduledPostPublishAt<T>>::contains_key(post_id), Error::<T>::PostIsNotScheduled);
+
+      Self::cancel_post_publishing(post_id);
+      <ScheduledPostPublishAt<T>>::remove(post_id);
+
+      Self::deposit_event(RawEvent::PostScheduleCanceled(who, post_id));
+      Ok(())
+    }
+
+    /// Publish a scheduled post. This call is dispatched by the scheduler.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 3)]
+    pub fn publish_scheduled_post(origin, post_id: PostId) -> DispatchResult {
+      ensure_root(origin)?;
+
+      ensure!(<ScheduledPostPublishAt<T>>::contains_key(post_id), Error::<T>::PostIsNotScheduled);
+      <ScheduledPostPublishAt<T>>::remove(post_id);
+
+      let mut post = Self::require_post(post_id)?;
+
+      // Changing visibility of a post manually cancels its publishing,
+      // so this check only guards against an inconsistent state.
+      if post.hidden {
+        if let Some(mut space) = post.try_get_space() {
+          space.dec_hidden_posts();
+          <SpaceById<T>>::insert(space.id, space);
+        }
+
+        post.hidden = false;
+        <PostById<T>>::insert(post_id, post);
+      }
+
+      Self::deposit_event(RawEvent::ScheduledPostPublished(post_id));
+      Ok(())
+    }
+
+    /// Override space permissions for a root post and its comments.
+    /// Pass `None` to reset the overrides and use permissions of the post's space.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(4, 1)]
+    pub fn update_post_permissions(
+      origin,
+      post_id: PostId,
+      permissions_opt: Option<SpacePermissions>
+    ) -> DispatchResult {
+      let editor = ensure_signed(origin)?;
+
+      let mut post = Self::require_post(post_id)?;
+      ensure!(!post.is_comment(), Error::<T>::CannotOverridePermissionsOnComment);
+
+      let space = post.get_space()?;
+      ensure!(T::IsAccountBlocked::is_allowed_account(editor.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+      Spaces::ensure_account_has_space_permission(
+        editor.clone(),
+        &space,
+        SpacePermission::OverridePostPermissions,
+        Error::<T>::NoPermissionToOverridePostPermissions.into()
+      )?;
+
+      let new_permissions = permissions_opt.map(Permissions::<T>::override_permissions);
+      ensure!(post.permissions != new_permissions, Error::<T>::NoUpdatesForPost);
+
+      post.permissions = new_permissions;
+      post.updated = Some(WhoAndWhen::<T>::new(editor.clone()));
+      <PostById<T>>::insert(post_id, post);
+
+      Self::deposit_event(RawEvent::PostUpdated(editor, post_id));
+      Ok(())
+    }
+
+    /// Delete a post (or a comment) together with all its nested replies.
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(8, 8)
+      // The post itself and up to `MaxRepliesToProcessPerDeletePost` replies get removed.
+      + (100_000 + T::DbWeight::get().reads_writes(6, 10))
+        * (T::MaxRepliesToProcessPerDeletePost::get() as u64 + 1)
+    ]
+    pub fn delete_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+
+      if let Some(space) = post.try_get_space() {
+        ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        Self::ensure_account_can_delete_post(&who, &post, &space)?;
+      } else {
+        post.ensure_owner(&who)?;
+      }
+
+      ensure!(
+        post.replies_count <= T::MaxRepliesToProcessPerDeletePost::get(),
+        Error::<T>::TooManyRepliesToDelete
+      );
+
+      Self::delete_post_with_replies(who, &post)
+    }
   }
 }
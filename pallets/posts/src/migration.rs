@@ -0,0 +1,123 @@
+use frame_support::{
+    Twox64Concat,
+    storage::{migration::StorageKeyIterator, IterableStorageMap, StorageValue},
+};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    if StorageVersion::get() == Releases::V2_0_0 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+    }
+
+    weight
+}
+
+/// A post as it was stored before permission overrides were added.
+#[derive(Decode)]
+struct OldPost<T: Trait> {
+    id: PostId,
+    created: WhoAndWhen<T>,
+    updated: Option<WhoAndWhen<T>>,
+    owner: T::AccountId,
+    extension: PostExtension,
+    space_id: Option<SpaceId>,
+    content: Content,
+    hidden: bool,
+    replies_count: u16,
+    hidden_replies_count: u16,
+    shares_count: u16,
+    upvotes_count: u16,
+    downvotes_count: u16,
+    score: i32,
+}
+
+/// Add empty permission overrides to every existing post.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    // Post ids are sequential, so this is the upper bound of posts in the storage.
+    let posts_count: Weight = NextPostId::get().saturating_sub(1);
+
+    PostById::<T>::translate::<OldPost<T>, _>(|_, old_post| {
+        Some(Post {
+            id: old_post.id,
+            created: old_post.created,
+            updated: old_post.updated,
+            owner: old_post.owner,
+            extension: old_post.extension,
+            space_id: old_post.space_id,
+            content: old_post.content,
+            hidden: old_post.hidden,
+            replies_count: old_post.replies_count,
+            hidden_replies_count: old_post.hidden_replies_count,
+            shares_count: old_post.shares_count,
+            upvotes_count: old_post.upvotes_count,
+            downvotes_count: old_post.downvotes_count,
+            score: old_post.score,
+            permissions: None,
+        })
+    });
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    T::DbWeight::get().reads_writes(posts_count + 1, posts_count + 1)
+}
+
+const MODULE_PREFIX: &[u8] = b"PostsModule";
+
+/// Drain a map of vectors of post ids, as it was stored before paged post indexes were introduced.
+fn drain_old_post_ids_map(item: &[u8]) -> Vec<(u64, Vec<PostId>)> {
+    // Old values share a storage prefix with new double maps, so they should be read out first.
+    StorageKeyIterator::<u64, Vec<PostId>, Twox64Concat>::new(MODULE_PREFIX, item)
+        .drain()
+        .collect()
+}
+
+/// Move post ids of spaces, replies and shares from vectors to paged post indexes,
+/// keeping the order in which they were added.
+fn migrate_to_v3<T: Trait>() -> Weight {
+    let reply_ids_by_post_id = drain_old_post_ids_map(b"ReplyIdsByPostId");
+    let post_ids_by_space_id = drain_old_post_ids_map(b"PostIdsBySpaceId");
+    let shared_post_ids_by_original_post_id = drain_old_post_ids_map(b"SharedPostIdsByOriginalPostId");
+
+    let mut appended: Weight = 0;
+
+    for (post_id, reply_ids) in reply_ids_by_post_id.iter() {
+        for reply_id in reply_ids {
+            Module::<T>::add_reply_to_post_index(*post_id, *reply_id);
+        }
+        appended = appended.saturating_add(reply_ids.len() as Weight);
+    }
+
+    for (space_id, post_ids) in post_ids_by_space_id.iter() {
+        for post_id in post_ids {
+            Module::<T>::add_post_to_space_index(*space_id, *post_id);
+        }
+        appended = appended.saturating_add(post_ids.len() as Weight);
+    }
+
+    for (original_post_id, shared_post_ids) in shared_post_ids_by_original_post_id.iter() {
+        for shared_post_id in shared_post_ids {
+            Module::<T>::add_share_to_post_index(*original_post_id, *shared_post_id);
+        }
+        appended = appended.saturating_add(shared_post_ids.len() as Weight);
+    }
+
+    StorageVersion::put(Releases::V3_0_0);
+
+    let drained = (reply_ids_by_post_id.len()
+        + post_ids_by_space_id.len()
+        + shared_post_ids_by_original_post_id.len()) as Weight;
+
+    // Every drained vector is read and removed, every appended post id is one read and three writes.
+    T::DbWeight::get().reads_writes(
+        drained.saturating_add(appended).saturating_add(1),
+        drained.saturating_add(appended.saturating_mul(3)).saturating_add(1)
+    )
+}
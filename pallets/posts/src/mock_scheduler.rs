@@ -0,0 +1,107 @@
+use frame_support::{
+    storage::unhashed,
+    traits::{UnfilteredDispatchable, schedule::{Period, Priority}},
+};
+use sp_runtime::traits::{One, Saturating};
+use sp_std::marker::PhantomData;
+
+use super::*;
+
+/// A storage key of the list of scheduled tasks.
+const TASKS_KEY: &[u8] = b":mock_scheduler:tasks";
+
+type Task<T> = (Vec<u8>, <T as system::Trait>::BlockNumber, Call<T>);
+
+/// A scheduler for tests: it keeps named tasks in the storage
+/// and dispatches them with a root origin when `dispatch_due_tasks` is called.
+pub struct MockScheduler<T>(PhantomData<T>);
+
+impl<T: Trait> MockScheduler<T> {
+    fn tasks() -> Vec<Task<T>> {
+        unhashed::get_or_default(TASKS_KEY)
+    }
+
+    fn put_tasks(tasks: Vec<Task<T>>) {
+        if tasks.is_empty() {
+            unhashed::kill(TASKS_KEY);
+        } else {
+            unhashed::put(TASKS_KEY, &tasks);
+        }
+    }
+
+    fn block_to_dispatch_at(when: DispatchTime<T::BlockNumber>) -> T::BlockNumber {
+        match when {
+            DispatchTime::At(at) => at,
+            // Like the real scheduler, never dispatch a task in the current block.
+            DispatchTime::After(after) => <system::Module<T>>::block_number()
+                .saturating_add(after)
+                .saturating_add(One::one()),
+        }
+    }
+
+    /// Dispatch and forget all tasks that are scheduled at or before a given block.
+    pub fn dispatch_due_tasks(now: T::BlockNumber) {
+        let (due_tasks, tasks): (Vec<_>, Vec<_>) = Self::tasks().into_iter()
+            .partition(|(_, when, _)| *when <= now);
+
+        Self::put_tasks(tasks);
+
+        for (_, _, call) in due_tasks {
+            // A failed task is dropped, as it would be by the real scheduler.
+            let _ = call.dispatch_bypass_filter(system::RawOrigin::Root.into());
+        }
+    }
+}
+
+impl<T: Trait> ScheduleNamed<T::BlockNumber, Call<T>, system::RawOrigin<T::AccountId>> for MockScheduler<T> {
+    type Address = ();
+
+    fn schedule_named(
+        id: Vec<u8>,
+        when: DispatchTime<T::BlockNumber>,
+        _maybe_periodic: Option<Period<T::BlockNumber>>,
+        _priority: Priority,
+        _origin: system::RawOrigin<T::AccountId>,
+        call: Call<T>,
+    ) -> Result<Self::Address, ()> {
+        let now = <system::Module<T>>::block_number();
+        let when = Self::block_to_dispatch_at(when);
+
+        let mut tasks = Self::tasks();
+        if when <= now || tasks.iter().any(|(task_id, ..)| *task_id == id) {
+            return Err(());
+        }
+
+        tasks.push((id, when, call));
+        Self::put_tasks(tasks);
+        Ok(())
+    }
+
+    fn cancel_named(id: Vec<u8>) -> Result<(), ()> {
+        let mut tasks = Self::tasks();
+        let index = tasks.iter().position(|(task_id, ..)| *task_id == id).ok_or(())?;
+
+        tasks.remove(index);
+        Self::put_tasks(tasks);
+        Ok(())
+    }
+
+    fn reschedule_named(id: Vec<u8>, when: DispatchTime<T::BlockNumber>) -> Result<Self::Address, DispatchError> {
+        let mut tasks = Self::tasks();
+        let task = tasks.iter_mut()
+            .find(|(task_id, ..)| *task_id == id)
+            .ok_or(DispatchError::Other("Scheduled task not found"))?;
+
+        task.1 = Self::block_to_dispatch_at(when);
+
+        Self::put_tasks(tasks);
+        Ok(())
+    }
+
+    fn next_dispatch_time(id: Vec<u8>) -> Result<T::BlockNumber, ()> {
+        Self::tasks().into_iter()
+            .find(|(task_id, ..)| *task_id == id)
+            .map(|(_, when, _)| when)
+            .ok_or(())
+    }
+}
@@ -1,15 +1,38 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    storage::IterableStorageDoubleMap,
+    traits::Get,
+    weights::Weight,
 };
+use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
+use df_traits::{CleanUpReapedAccount, IsBlockedByAccount};
 use pallet_profiles::{Module as Profiles, SocialAccountById};
-use pallet_utils::remove_from_vec;
+use pallet_utils::Error as UtilsError;
+
+pub mod migration;
+
+// A value placed in storage that represents the current version of the Profile Follows storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
 
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
@@ -22,19 +45,41 @@ pub trait Trait: system::Trait
     type BeforeAccountFollowed: BeforeAccountFollowed<Self>;
 
     type BeforeAccountUnfollowed: BeforeAccountUnfollowed<Self>;
+
+    type IsBlockedByAccount: IsBlockedByAccount<Self::AccountId>;
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as ProfileFollowsModule {
-        pub AccountFollowers get(fn account_followers):
-            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+        /// Followers (2nd key) of an account (1st key).
+        pub AccountFollowers:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
 
         pub AccountFollowedByAccount get(fn account_followed_by_account):
             map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
 
-        pub AccountsFollowedByAccount get(fn accounts_followed_by_account):
-            map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+        /// Accounts (2nd key) followed by an account (1st key).
+        pub AccountsFollowedByAccount:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Whether an account has to approve follow requests before they become followers.
+        pub FollowApprovalRequired get(fn follow_approval_required):
+            map hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Accounts (2nd key) with pending requests to follow an account (1st key).
+        pub FollowRequestsToAccount:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Accounts (2nd key) that an account (1st key) has pending requests to follow.
+        pub FollowRequestsByAccount:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        pub FollowRequestedByAccount get(fn follow_requested_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
+
+        /// The current storage version of this pallet, used by `on_runtime_upgrade` to run migrations.
+        StorageVersion build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
 }
 
@@ -44,6 +89,11 @@ decl_event!(
     {
         AccountFollowed(/* follower */ AccountId, /* following */ AccountId),
         AccountUnfollowed(/* follower */ AccountId, /* unfollowing */ AccountId),
+        FollowApprovalUpdated(AccountId, /* required */ bool),
+        FollowRequestSent(/* requester */ AccountId, /* account */ AccountId),
+        FollowRequestAccepted(/* account */ AccountId, /* requester */ AccountId),
+        FollowRequestRejected(/* account */ AccountId, /* requester */ AccountId),
+        FollowRequestCanceled(/* requester */ AccountId, /* account */ AccountId),
     }
 );
 
@@ -63,6 +113,11 @@ decl_error! {
         AlreadyAccountFollower,
         /// Account (Alice) is not a follower of another account (Bob).
         NotAccountFollower,
+
+        /// Account has already requested to follow this account.
+        FollowRequestAlreadySent,
+        /// There is no pending follow request from this account.
+        FollowRequestNotFound,
     }
 }
 
@@ -75,6 +130,10 @@ decl_module! {
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
     #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
     pub fn follow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
@@ -82,23 +141,27 @@ decl_module! {
       ensure!(follower != account, Error::<T>::AccountCannotFollowItself);
       ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
         Error::<T>::AlreadyAccountFollower);
+      ensure!(!T::IsBlockedByAccount::is_blocked_by_account(&follower, &account),
+        UtilsError::<T>::AccountIsBlockedByAccount);
 
-      let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
-      let mut followed_account = Profiles::get_or_new_social_account(account.clone());
+      if Self::follow_approval_required(&account) {
+        ensure!(!Self::follow_requested_by_account((follower.clone(), account.clone())),
+          Error::<T>::FollowRequestAlreadySent);
 
-      follower_account.inc_following_accounts();
-      followed_account.inc_followers();
+        <FollowRequestsToAccount<T>>::insert(&account, &follower, true);
+        <FollowRequestsByAccount<T>>::insert(&follower, &account, true);
+        <FollowRequestedByAccount<T>>::insert((follower.clone(), account.clone()), true);
 
-      T::BeforeAccountFollowed::before_account_followed(
-        follower.clone(), follower_account.reputation, account.clone())?;
+        Self::deposit_event(RawEvent::FollowRequestSent(follower, account));
+        return Ok(());
+      }
 
-      <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
-      <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
+      Self::add_account_follower(follower.clone(), account.clone())?;
 
-      Self::deposit_event(RawEvent::AccountFollowed(follower, account));
+      // A request could be left pending, if an account stopped requiring approval.
+      if Self::follow_requested_by_account((follower.clone(), account.clone())) {
+        Self::remove_follow_request(&follower, &account);
+      }
       Ok(())
     }
 
@@ -119,16 +182,186 @@ decl_module! {
 
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
       <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |account_ids| remove_from_vec(account_ids, follower.clone()));
+      <AccountsFollowedByAccount<T>>::remove(&follower, &account);
+      <AccountFollowers<T>>::remove(&account, &follower);
       <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
 
       Self::deposit_event(RawEvent::AccountUnfollowed(follower, account));
       Ok(())
     }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(0, 1)]
+    pub fn set_follow_approval_required(origin, required: bool) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      if required {
+        <FollowApprovalRequired<T>>::insert(who.clone(), true);
+      } else {
+        <FollowApprovalRequired<T>>::remove(who.clone());
+      }
+
+      Self::deposit_event(RawEvent::FollowApprovalUpdated(who, required));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 7)]
+    pub fn accept_follow_request(origin, requester: T::AccountId) -> DispatchResult {
+      let account = ensure_signed(origin)?;
+
+      Self::ensure_follow_request_exists(&requester, &account)?;
+
+      Self::add_account_follower(requester.clone(), account.clone())?;
+      Self::remove_follow_request(&requester, &account);
+
+      Self::deposit_event(RawEvent::FollowRequestAccepted(account, requester));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 3)]
+    pub fn reject_follow_request(origin, requester: T::AccountId) -> DispatchResult {
+      let account = ensure_signed(origin)?;
+
+      Self::ensure_follow_request_exists(&requester, &account)?;
+      Self::remove_follow_request(&requester, &account);
+
+      Self::deposit_event(RawEvent::FollowRequestRejected(account, requester));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 3)]
+    pub fn cancel_follow_request(origin, account: T::AccountId) -> DispatchResult {
+      let requester = ensure_signed(origin)?;
+
+      Self::ensure_follow_request_exists(&requester, &account)?;
+      Self::remove_follow_request(&requester, &account);
+
+      Self::deposit_event(RawEvent::FollowRequestCanceled(requester, account));
+      Ok(())
+    }
   }
 }
 
+impl<T: Trait> Module<T> {
+    /// Get at most `limit` followers of an account, skipping the first `offset` of them.
+    pub fn account_followers(account: &T::AccountId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <AccountFollowers<T>>::iter_prefix(account)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(follower, _)| follower)
+            .collect()
+    }
+
+    /// Get at most `limit` accounts followed by an account, skipping the first `offset` of them.
+    pub fn accounts_followed_by_account(follower: &T::AccountId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <AccountsFollowedByAccount<T>>::iter_prefix(follower)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(account, _)| account)
+            .collect()
+    }
+
+    /// Get at most `limit` accounts that requested to follow an account, skipping the first `offset` of them.
+    pub fn follow_requests_to_account(account: &T::AccountId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <FollowRequestsToAccount<T>>::iter_prefix(account)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(requester, _)| requester)
+            .collect()
+    }
+
+    /// Get at most `limit` accounts that an account requested to follow, skipping the first `offset` of them.
+    pub fn follow_requests_by_account(requester: &T::AccountId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <FollowRequestsByAccount<T>>::iter_prefix(requester)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(account, _)| account)
+            .collect()
+    }
+
+    fn add_account_follower(follower: T::AccountId, account: T::AccountId) -> DispatchResult {
+        ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
+            Error::<T>::AlreadyAccountFollower);
+
+        let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
+        let mut followed_account = Profiles::get_or_new_social_account(account.clone());
+
+        follower_account.inc_following_accounts();
+        followed_account.inc_followers();
+
+        T::BeforeAccountFollowed::before_account_followed(
+            follower.clone(), follower_account.reputation, account.clone())?;
+
+        <SocialAccountById<T>>::insert(follower.clone(), follower_account);
+        <SocialAccountById<T>>::insert(account.clone(), followed_account);
+        <AccountsFollowedByAccount<T>>::insert(&follower, &account, true);
+        <AccountFollowers<T>>::insert(&account, &follower, true);
+        <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
+
+        Self::deposit_event(RawEvent::AccountFollowed(follower, account));
+        Ok(())
+    }
+
+    fn ensure_follow_request_exists(requester: &T::AccountId, account: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::follow_requested_by_account((requester.clone(), account.clone())),
+            Error::<T>::FollowRequestNotFound
+        );
+        Ok(())
+    }
+
+    fn remove_follow_request(requester: &T::AccountId, account: &T::AccountId) {
+        <FollowRequestsToAccount<T>>::remove(account, requester);
+        <FollowRequestsByAccount<T>>::remove(requester, account);
+        <FollowRequestedByAccount<T>>::remove((requester.clone(), account.clone()));
+    }
+}
+
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+    /// Remove follows and follow requests of a reaped account
+    /// and update counters of accounts it was linked to.
+    fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+        <FollowApprovalRequired<T>>::remove(who);
+
+        let mut removed: u32 = 0;
+
+        for (following, _) in <AccountsFollowedByAccount<T>>::drain_prefix(who).take(limit as usize) {
+            <AccountFollowedByAccount<T>>::remove((who.clone(), following.clone()));
+            <AccountFollowers<T>>::remove(&following, who);
+            <SocialAccountById<T>>::mutate(following, |social_account_opt| {
+                if let Some(social_account) = social_account_opt {
+                    social_account.dec_followers();
+                }
+            });
+            removed += 1;
+        }
+
+        for (follower, _) in <AccountFollowers<T>>::drain_prefix(who).take((limit - removed) as usize) {
+            <AccountFollowedByAccount<T>>::remove((follower.clone(), who.clone()));
+            <AccountsFollowedByAccount<T>>::remove(&follower, who);
+            <SocialAccountById<T>>::mutate(follower, |social_account_opt| {
+                if let Some(social_account) = social_account_opt {
+                    social_account.dec_following_accounts();
+                }
+            });
+            removed += 1;
+        }
+
+        for (account, _) in <FollowRequestsByAccount<T>>::drain_prefix(who).take((limit - removed) as usize) {
+            <FollowRequestedByAccount<T>>::remove((who.clone(), account.clone()));
+            <FollowRequestsToAccount<T>>::remove(&account, who);
+            removed += 1;
+        }
+
+        for (requester, _) in <FollowRequestsToAccount<T>>::drain_prefix(who).take((limit - removed) as usize) {
+            <FollowRequestedByAccount<T>>::remove((requester.clone(), who.clone()));
+            <FollowRequestsByAccount<T>>::remove(&requester, who);
+            removed += 1;
+        }
+
+        removed
+    }
+}
+
 /// Handler that will be called right before the account is followed.
 pub trait BeforeAccountFollowed<T: Trait> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult;
@@ -0,0 +1,54 @@
+use frame_support::{
+    Blake2_128Concat,
+    storage::{migration::StorageKeyIterator, StorageDoubleMap, StorageValue},
+};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    weight
+}
+
+const MODULE_PREFIX: &[u8] = b"ProfileFollowsModule";
+
+/// Drain a map of vectors of accounts, as it was stored before double maps were introduced.
+fn drain_old_accounts_map<T: Trait>(item: &[u8]) -> Vec<(T::AccountId, Vec<T::AccountId>)> {
+    // Old values share a storage prefix with new double maps, so they should be read out first.
+    StorageKeyIterator::<T::AccountId, Vec<T::AccountId>, Blake2_128Concat>::new(MODULE_PREFIX, item)
+        .drain()
+        .collect()
+}
+
+/// Move followers and followed accounts from vectors to double maps.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    let followers_by_account = drain_old_accounts_map::<T>(b"AccountFollowers");
+    let followed_by_account = drain_old_accounts_map::<T>(b"AccountsFollowedByAccount");
+
+    let mut writes: Weight = 0;
+
+    for (account, followers) in followers_by_account.iter() {
+        for follower in followers {
+            AccountFollowers::<T>::insert(account, follower, true);
+        }
+        writes = writes.saturating_add(followers.len() as Weight + 1);
+    }
+
+    for (follower, accounts) in followed_by_account.iter() {
+        for account in accounts {
+            AccountsFollowedByAccount::<T>::insert(follower, account, true);
+        }
+        writes = writes.saturating_add(accounts.len() as Weight + 1);
+    }
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    let reads = (followers_by_account.len() + followed_by_account.len()) as Weight;
+    T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+}
@@ -4,13 +4,17 @@ use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    traits::{Currency, Get, OnKilledAccount, ReservableCurrency},
+    weights::Weight,
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use pallet_utils::{Module as Utils, WhoAndWhen, Content};
+use df_traits::{CleanUpReapedAccount, IsHandleReserved};
+use pallet_utils::{Module as Utils, WhoAndWhen, Content, ContentHolder};
+
+pub mod migration;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SocialAccount<T: Trait> {
@@ -25,6 +29,7 @@ pub struct SocialAccount<T: Trait> {
 pub struct Profile<T: Trait> {
     pub created: WhoAndWhen<T>,
     pub updated: Option<WhoAndWhen<T>>,
+    pub handle: Option<Vec<u8>>,
     pub content: Content
 }
 
@@ -33,6 +38,25 @@ pub struct ProfileUpdate {
     pub content: Option<Content>,
 }
 
+// A value placed in storage that represents the current version of the Profiles storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
+type BalanceOf<T> =
+    <<T as pallet_utils::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
@@ -41,6 +65,20 @@ pub trait Trait: system::Trait
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type AfterProfileUpdated: AfterProfileUpdated<Self>;
+
+    /// An amount reserved from an account while its profile has a handle.
+    type HandleDeposit: Get<BalanceOf<Self>>;
+
+    /// Handles that cannot be taken by profiles. Set to spaces to share one namespace
+    /// between space and profile handles, or to `()` to keep them separate.
+    type IsHandleReserved: IsHandleReserved;
+
+    /// Pallets that clean up their storage linked to a reaped account.
+    /// It is done in batches from `on_initialize`, as there can be too much of it for one block.
+    type ReapedAccountCleanup: CleanUpReapedAccount<Self::AccountId>;
+
+    /// A max number of storage entries of reaped accounts to remove per block.
+    type MaxReapedAccountEntriesPerBlock: Get<u32>;
 }
 
 // This pallet's storage items.
@@ -48,6 +86,23 @@ decl_storage! {
     trait Store for Module<T: Trait> as ProfilesModule {
         pub SocialAccountById get(fn social_account_by_id):
             map hasher(blake2_128_concat) T::AccountId => Option<SocialAccount<T>>;
+
+        /// An account id of a profile that reserved this lowercase handle (key).
+        pub ProfileIdByHandle get(fn profile_id_by_handle):
+            map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+
+        /// Reaped accounts in the order of their cleanup by `ReapedAccountCleanup`.
+        pub ReapedAccountByIndex get(fn reaped_account_by_index):
+            map hasher(twox_64_concat) u64 => Option<T::AccountId>;
+
+        /// An index of the reaped account that is cleaned up now.
+        pub FirstReapedAccountIndex get(fn first_reaped_account_index): u64;
+
+        /// An index of the next account to be reaped.
+        pub NextReapedAccountIndex get(fn next_reaped_account_index): u64;
+
+        /// The current storage version of this pallet, used by `on_runtime_upgrade` to run migrations.
+        StorageVersion build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
 }
 
@@ -70,6 +125,8 @@ decl_error! {
         NoUpdatesForProfile,
         /// Account has no profile yet.
         AccountHasNoProfile,
+        /// Profile handle is not unique.
+        ProfileHandleIsNotUnique,
     }
 }
 
@@ -79,9 +136,21 @@ decl_module! {
     // Initializing errors
     type Error = Error<T>;
 
+    const HandleDeposit: BalanceOf<T> = T::HandleDeposit::get();
+
+    const MaxReapedAccountEntriesPerBlock: u32 = T::MaxReapedAccountEntriesPerBlock::get();
+
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
+    fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+      Self::clean_up_next_reaped_accounts()
+    }
+
     #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
     pub fn create_profile(origin, content: Content) -> DispatchResult {
       let owner = ensure_signed(origin)?;
@@ -91,10 +160,13 @@ decl_module! {
       let mut social_account = Self::get_or_new_social_account(owner.clone());
       ensure!(social_account.profile.is_none(), Error::<T>::ProfileAlreadyCreated);
 
+      Utils::<T>::reserve_content_deposit(&owner, ContentHolder::Profile(owner.clone()), &content)?;
+
       social_account.profile = Some(
         Profile {
           created: WhoAndWhen::<T>::new(owner.clone()),
           updated: None,
+          handle: None,
           content
         }
       );
@@ -120,6 +192,7 @@ decl_module! {
       if let Some(content) = update.content {
         if content != profile.content {
           Utils::<T>::is_valid_content(content.clone())?;
+          Utils::<T>::update_content_deposit(&owner, ContentHolder::Profile(owner.clone()), &content)?;
           old_data.content = Some(profile.content);
           profile.content = content;
           is_update_applied = true;
@@ -137,6 +210,53 @@ decl_module! {
       }
       Ok(())
     }
+
+    /// Set, change or release (if `None`) a unique handle of the caller's profile.
+    /// A handle deposit is reserved while the profile has a handle.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 4)]
+    pub fn update_profile_handle(origin, handle_opt: Option<Vec<u8>>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
+      let mut profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
+
+      ensure!(handle_opt != profile.handle, Error::<T>::NoUpdatesForProfile);
+
+      let old_handle_lc_opt = profile.handle.clone().map(Utils::<T>::lowercase_handle);
+      let new_handle_lc_opt = match handle_opt.clone() {
+        Some(handle) => {
+          let handle_in_lowercase = Utils::<T>::lowercase_and_validate_a_handle(handle)?;
+
+          // Only a letter case could be changed, if the handle stays the same.
+          if Some(&handle_in_lowercase) != old_handle_lc_opt.as_ref() {
+            Self::ensure_handle_is_unique(&handle_in_lowercase)?;
+          }
+          Some(handle_in_lowercase)
+        },
+        None => None,
+      };
+
+      match (&old_handle_lc_opt, &new_handle_lc_opt) {
+        (None, Some(_)) => Self::reserve_handle_deposit(&owner)?,
+        (Some(_), None) => { Self::unreserve_handle_deposit(&owner); },
+        _ => (),
+      }
+
+      if let Some(old_handle_lc) = old_handle_lc_opt {
+        ProfileIdByHandle::<T>::remove(old_handle_lc);
+      }
+      if let Some(new_handle_lc) = new_handle_lc_opt {
+        ProfileIdByHandle::<T>::insert(new_handle_lc, owner.clone());
+      }
+
+      profile.handle = handle_opt;
+      profile.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+      social_account.profile = Some(profile);
+      <SocialAccountById<T>>::insert(owner.clone(), social_account);
+
+      Self::deposit_event(RawEvent::ProfileUpdated(owner));
+      Ok(())
+    }
   }
 }
 
@@ -197,6 +317,84 @@ impl<T: Trait> Module<T> {
             }
         )
     }
+
+    /// Ensure that a lowercase handle is not reserved by any profile
+    /// or by another registry of handles (see `Trait::IsHandleReserved`).
+    fn ensure_handle_is_unique(handle_in_lowercase: &[u8]) -> DispatchResult {
+        ensure!(
+            !ProfileIdByHandle::<T>::contains_key(handle_in_lowercase) &&
+            !T::IsHandleReserved::is_handle_reserved(handle_in_lowercase),
+            Error::<T>::ProfileHandleIsNotUnique
+        );
+        Ok(())
+    }
+
+    pub fn reserve_handle_deposit(account: &T::AccountId) -> DispatchResult {
+        <T as pallet_utils::Trait>::Currency::reserve(account, T::HandleDeposit::get())
+    }
+
+    pub fn unreserve_handle_deposit(account: &T::AccountId) -> BalanceOf<T> {
+        <T as pallet_utils::Trait>::Currency::unreserve(account, T::HandleDeposit::get())
+    }
+
+    /// Remove at most `MaxReapedAccountEntriesPerBlock` storage entries of reaped accounts,
+    /// starting from where the previous block stopped.
+    fn clean_up_next_reaped_accounts() -> Weight {
+        let first_index = Self::first_reaped_account_index();
+        let next_index = Self::next_reaped_account_index();
+        if first_index == next_index {
+            return T::DbWeight::get().reads(2)
+        }
+
+        let limit = T::MaxReapedAccountEntriesPerBlock::get();
+        let mut removed: u32 = 0;
+        let mut index = first_index;
+
+        while index < next_index && removed < limit {
+            if let Some(account) = Self::reaped_account_by_index(index) {
+                let remaining = limit - removed;
+                let removed_of_account = T::ReapedAccountCleanup::clean_up_reaped_account(&account, remaining);
+                removed = removed.saturating_add(removed_of_account);
+
+                if removed_of_account >= remaining {
+                    // This account may have more entries to remove in the next block.
+                    break;
+                }
+            }
+            ReapedAccountByIndex::<T>::remove(index);
+            index = index.saturating_add(1);
+        }
+
+        FirstReapedAccountIndex::put(index);
+
+        let processed_accounts = index.saturating_sub(first_index).saturating_add(1);
+        T::DbWeight::get().reads_writes(processed_accounts.saturating_add(2), processed_accounts)
+            // A removed entry can update a few other entries, e.g. counters of followers.
+            .saturating_add(T::DbWeight::get().reads_writes(2, 3).saturating_mul(removed as Weight))
+    }
+}
+
+impl<T: Trait> OnKilledAccount<T::AccountId> for Module<T> {
+    /// Remove a social account and a profile handle of a reaped account
+    /// and queue the account for a cleanup by other pallets (see `Trait::ReapedAccountCleanup`).
+    fn on_killed_account(who: &T::AccountId) {
+        let profile_opt = <SocialAccountById<T>>::take(who).and_then(|social_account| social_account.profile);
+
+        if let Some(handle) = profile_opt.and_then(|profile| profile.handle) {
+            ProfileIdByHandle::<T>::remove(Utils::<T>::lowercase_handle(handle));
+        }
+        Utils::<T>::unreserve_content_deposit(&ContentHolder::Profile(who.clone()));
+
+        let index = Self::next_reaped_account_index();
+        ReapedAccountByIndex::<T>::insert(index, who);
+        NextReapedAccountIndex::put(index.saturating_add(1));
+    }
+}
+
+impl<T: Trait> IsHandleReserved for Module<T> {
+    fn is_handle_reserved(handle: &[u8]) -> bool {
+        ProfileIdByHandle::<T>::contains_key(handle)
+    }
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
@@ -0,0 +1,60 @@
+use frame_support::storage::{IterableStorageMap, StorageValue};
+use sp_std::cell::Cell;
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    weight
+}
+
+/// A profile as it was stored before handles were added.
+#[derive(Decode)]
+struct OldProfile<T: Trait> {
+    created: WhoAndWhen<T>,
+    updated: Option<WhoAndWhen<T>>,
+    content: Content,
+}
+
+/// A social account as it was stored before profile handles were added.
+#[derive(Decode)]
+struct OldSocialAccount<T: Trait> {
+    followers_count: u32,
+    following_accounts_count: u16,
+    following_spaces_count: u16,
+    reputation: u32,
+    profile: Option<OldProfile<T>>,
+}
+
+/// Add an empty handle to every existing profile.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    let accounts_count: Cell<Weight> = Cell::new(0);
+
+    SocialAccountById::<T>::translate::<OldSocialAccount<T>, _>(|_, old_account| {
+        accounts_count.set(accounts_count.get().saturating_add(1));
+
+        Some(SocialAccount {
+            followers_count: old_account.followers_count,
+            following_accounts_count: old_account.following_accounts_count,
+            following_spaces_count: old_account.following_spaces_count,
+            reputation: old_account.reputation,
+            profile: old_account.profile.map(|old_profile| Profile {
+                created: old_profile.created,
+                updated: old_profile.updated,
+                handle: None,
+                content: old_profile.content,
+            }),
+        })
+    });
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    let accounts_count = accounts_count.get();
+    T::DbWeight::get().reads_writes(accounts_count + 1, accounts_count + 1)
+}
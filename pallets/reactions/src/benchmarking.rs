@@ -0,0 +1,157 @@
+//! Benchmarks for the reactions pallet.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use pallet_posts::PostExtension;
+use pallet_utils::Content;
+
+const SEED: u32 = 0;
+
+fn space_content() -> Content {
+    Content::IPFS(b"bafyreib3mgbou4xln42qqcgj6qlt3cif35x4ribisxgq7unhpun525l54e".to_vec())
+}
+
+fn post_content() -> Content {
+    Content::IPFS(b"bafyreidzue2dtxpj6n4x5mktrt7las5wz5diqma47zr25uau743dhe76we".to_vec())
+}
+
+fn profile_content() -> Content {
+    Content::IPFS(b"bafyreigcy4nytbcmxazewqsgxfwg7ocfpxexyyyeqz3kp3vsyrovqbuvgm".to_vec())
+}
+
+fn create_space<T: Trait>(owner: T::AccountId) -> Result<SpaceId, &'static str> {
+    let space_id = Spaces::<T>::next_space_id();
+    Spaces::<T>::create_space(RawOrigin::Signed(owner).into(), None, None, space_content(), None)?;
+    Ok(space_id)
+}
+
+/// Create a post owned by another account than the one who reacts,
+/// so that votes go through the scoring logic, which is the heaviest path.
+fn create_post<T: Trait>() -> Result<PostId, &'static str> {
+    let owner: T::AccountId = account("owner", 0, SEED);
+    let space_id = create_space::<T>(owner.clone())?;
+    create_post_in_space::<T>(owner, space_id)
+}
+
+fn create_post_in_space<T: Trait>(owner: T::AccountId, space_id: SpaceId) -> Result<PostId, &'static str> {
+    let post_id = Posts::<T>::next_post_id();
+    Posts::<T>::create_post(
+        RawOrigin::Signed(owner).into(),
+        Some(space_id),
+        PostExtension::RegularPost,
+        post_content()
+    )?;
+    Ok(post_id)
+}
+
+fn upvote_post<T: Trait>(account: T::AccountId, post_id: PostId) -> Result<ReactionId, &'static str> {
+    let reaction_id = Module::<T>::next_reaction_id();
+    Module::<T>::create_post_reaction(RawOrigin::Signed(account).into(), post_id, ReactionKind::Upvote)?;
+    Ok(reaction_id)
+}
+
+fn first_supported_emoji<T: Trait>() -> Result<EmojiCode, &'static str> {
+    T::SupportedEmojis::get().first().copied().ok_or("Runtime supports no emojis")
+}
+
+benchmarks! {
+    _ { }
+
+    create_reaction {
+        let post_id = create_post::<T>()?;
+        let caller: T::AccountId = whitelisted_caller();
+    }: create_post_reaction(RawOrigin::Signed(caller.clone()), post_id, ReactionKind::Upvote)
+    verify {
+        assert!(Module::<T>::has_reacted(&caller, &ReactionTarget::Post(post_id), ReactionKind::Upvote));
+    }
+
+    create_emoji_reaction {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let space_id = create_space::<T>(owner.clone())?;
+        let post_id = create_post_in_space::<T>(owner.clone(), space_id)?;
+
+        let emoji = ReactionKind::Emoji(first_supported_emoji::<T>()?);
+        Module::<T>::set_allowed_space_reactions(RawOrigin::Signed(owner).into(), space_id, Some([emoji].to_vec()))?;
+
+        let caller: T::AccountId = whitelisted_caller();
+    }: create_post_reaction(RawOrigin::Signed(caller.clone()), post_id, emoji)
+    verify {
+        assert!(Module::<T>::has_reacted(&caller, &ReactionTarget::Post(post_id), emoji));
+    }
+
+    create_space_reaction {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let space_id = create_space::<T>(owner)?;
+        let target = ReactionTarget::Space(space_id);
+        let caller: T::AccountId = whitelisted_caller();
+    }: create_reaction(RawOrigin::Signed(caller.clone()), target.clone(), ReactionKind::Upvote)
+    verify {
+        assert!(Module::<T>::has_reacted(&caller, &target, ReactionKind::Upvote));
+    }
+
+    create_profile_reaction {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        pallet_profiles::Module::<T>::create_profile(RawOrigin::Signed(owner.clone()).into(), profile_content())?;
+
+        let emoji = ReactionKind::Emoji(first_supported_emoji::<T>()?);
+        let target = ReactionTarget::Profile(owner);
+        let caller: T::AccountId = whitelisted_caller();
+    }: create_reaction(RawOrigin::Signed(caller.clone()), target.clone(), emoji)
+    verify {
+        assert!(Module::<T>::has_reacted(&caller, &target, emoji));
+    }
+
+    update_reaction {
+        let post_id = create_post::<T>()?;
+        let caller: T::AccountId = whitelisted_caller();
+        let reaction_id = upvote_post::<T>(caller.clone(), post_id)?;
+    }: update_post_reaction(RawOrigin::Signed(caller), post_id, reaction_id, ReactionKind::Downvote)
+    verify {
+        assert_eq!(Module::<T>::reaction_by_id(reaction_id).map(|r| r.kind), Some(ReactionKind::Downvote));
+    }
+
+    delete_reaction {
+        let post_id = create_post::<T>()?;
+        let caller: T::AccountId = whitelisted_caller();
+        let reaction_id = upvote_post::<T>(caller.clone(), post_id)?;
+    }: delete_post_reaction(RawOrigin::Signed(caller), post_id, reaction_id)
+    verify {
+        assert!(Module::<T>::reaction_by_id(reaction_id).is_none());
+    }
+
+    set_allowed_space_reactions {
+        // Upvote, downvote and the seven emojis supported by the runtime.
+        let n in 0 .. 9;
+
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let space_id = create_space::<T>(owner.clone())?;
+
+        // Longer lists of reactions are rejected before any storage access.
+        let supported_emojis = T::SupportedEmojis::get();
+        let max_reactions = supported_emojis.len() + 2;
+        let reactions: Vec<ReactionKind> = [ReactionKind::Upvote, ReactionKind::Downvote]
+            .iter()
+            .cloned()
+            .chain(supported_emojis.into_iter().map(ReactionKind::Emoji))
+            .take((n as usize).min(max_reactions))
+            .collect();
+    }: _(RawOrigin::Signed(owner), space_id, Some(reactions))
+    verify {
+        assert!(Module::<T>::allowed_reactions_by_space_id(space_id).is_some());
+    }
+
+    remove_reactions {
+        let r in 0 .. 1_000;
+
+        let post_id = create_post::<T>()?;
+        for i in 0 .. r {
+            upvote_post::<T>(account("reactor", i, SEED), post_id)?;
+        }
+    }: {
+        Module::<T>::remove_reactions_on_target(&ReactionTarget::Post(post_id), r);
+    }
+    verify {
+        assert!(Module::<T>::reaction_ids_by_post_id(post_id).is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+use frame_support::{
+    dispatch::DispatchError,
+    storage::{IterableStorageDoubleMap, StorageDoubleMap},
+};
+
+use df_traits::{IsBlockedByAccount, moderation::IsAccountBlocked};
+use pallet_profiles::{Module as Profiles, Error as ProfilesError};
+use pallet_utils::Error as UtilsError;
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+
+    pub(crate) fn do_create_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        kind: ReactionKind
+    ) -> DispatchResult {
+        Self::ensure_can_react_to(&owner, &target, kind)?;
+        ensure!(!Self::has_reacted(&owner, &target, kind), Error::<T>::AccountAlreadyReacted);
+
+        if let ReactionTarget::Post(post_id) = target {
+            if kind.is_vote() {
+                let post = &mut Posts::require_post(post_id)?;
+                match kind {
+                    ReactionKind::Upvote => post.inc_upvotes(),
+                    _ => post.inc_downvotes(),
+                }
+
+                if post.is_owner(&owner) {
+                    <PostById<T>>::insert(post_id, post.clone());
+                }
+
+                T::PostReactionScores::score_post_on_reaction(owner.clone(), post, kind)?;
+            }
+        }
+
+        let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
+
+        <ReactionIdsByTarget<T>>::insert(&target, reaction_id, true);
+        Self::insert_reaction_id_by_account(&owner, &target, kind, reaction_id);
+        <ReactionsCountByKind<T>>::mutate(&target, kind, |count| *count = count.saturating_add(1));
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionCreated(owner, post_id, reaction_id),
+            _ => RawEvent::ReactionCreated(owner, target, reaction_id),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn do_update_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        reaction_id: ReactionId,
+        new_kind: ReactionKind
+    ) -> DispatchResult {
+        let mut reaction = Self::require_reaction(reaction_id)?;
+
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+        ensure!(reaction.kind.is_vote() && new_kind.is_vote(), Error::<T>::CannotUpdateEmojiReaction);
+        ensure!(
+            Self::find_reaction_id(&owner, &target, reaction.kind) == Some(reaction_id),
+            Error::<T>::ReactionByAccountNotFound
+        );
+        ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
+
+        Self::ensure_can_react_to(&owner, &target, new_kind)?;
+
+        let old_kind = reaction.kind;
+        reaction.kind = new_kind;
+        reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+
+        if let ReactionTarget::Post(post_id) = target {
+            let post = &mut Posts::require_post(post_id)?;
+            match new_kind {
+                ReactionKind::Upvote => {
+                    post.inc_upvotes();
+                    post.dec_downvotes();
+                },
+                _ => {
+                    post.inc_downvotes();
+                    post.dec_upvotes();
+                },
+            }
+
+            T::PostReactionScores::score_post_on_reaction(owner.clone(), post, old_kind)?;
+            T::PostReactionScores::score_post_on_reaction(owner.clone(), post, new_kind)?;
+
+            <PostById<T>>::insert(post_id, post);
+        }
+
+        <ReactionById<T>>::insert(reaction_id, reaction);
+        <ReactionsCountByKind<T>>::mutate(&target, old_kind, |count| *count = count.saturating_sub(1));
+        <ReactionsCountByKind<T>>::mutate(&target, new_kind, |count| *count = count.saturating_add(1));
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionUpdated(owner, post_id, reaction_id),
+            _ => RawEvent::ReactionUpdated(owner, target, reaction_id),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn do_delete_reaction(
+        owner: T::AccountId,
+        target: ReactionTarget<T::AccountId>,
+        reaction_id: ReactionId
+    ) -> DispatchResult {
+        let reaction = Self::require_reaction(reaction_id)?;
+
+        ensure!(
+            Self::find_reaction_id(&owner, &target, reaction.kind) == Some(reaction_id),
+            Error::<T>::ReactionByAccountNotFound
+        );
+        ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
+        Self::ensure_not_blocked_in_target_scope(&owner, &target)?;
+
+        if let ReactionTarget::Post(post_id) = target {
+            if reaction.kind.is_vote() {
+                let post = &mut Posts::require_post(post_id)?;
+                match reaction.kind {
+                    ReactionKind::Upvote => post.dec_upvotes(),
+                    _ => post.dec_downvotes(),
+                }
+
+                T::PostReactionScores::score_post_on_reaction(owner.clone(), post, reaction.kind)?;
+                <PostById<T>>::insert(post_id, post.clone());
+            }
+        }
+
+        <ReactionById<T>>::remove(reaction_id);
+        <ReactionIdsByTarget<T>>::remove(&target, reaction_id);
+        Self::remove_reaction_id_by_account(&owner, &target, reaction.kind);
+        <ReactionsCountByKind<T>>::mutate(&target, reaction.kind, |count| *count = count.saturating_sub(1));
+
+        Self::deposit_event(match target {
+            ReactionTarget::Post(post_id) => RawEvent::PostReactionDeleted(owner, post_id, reaction_id),
+            _ => RawEvent::ReactionDeleted(owner, target, reaction_id),
+        });
+        Ok(())
+    }
+
+    pub fn insert_new_reaction(account: T::AccountId, kind: ReactionKind) -> ReactionId {
+        let id = Self::next_reaction_id();
+        let reaction: Reaction<T> = Reaction {
+            id,
+            created: WhoAndWhen::<T>::new(account),
+            updated: None,
+            kind
+        };
+
+        <ReactionById<T>>::insert(id, reaction);
+        NextReactionId::mutate(|n| { *n += 1; });
+
+        id
+    }
+
+    /// Get `Reaction` by id from the storage or return `ReactionNotFound` error.
+    pub fn require_reaction(reaction_id: ReactionId) -> Result<Reaction<T>, DispatchError> {
+        Ok(Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?)
+    }
+
+    /// Get ids of all reactions on a target, in the order they were created.
+    pub fn reaction_ids_by_target(target: &ReactionTarget<T::AccountId>) -> Vec<ReactionId> {
+        // A double map is iterated in the order of key hashes, not in the order of reaction ids.
+        let mut reaction_ids: Vec<ReactionId> = <ReactionIdsByTarget<T>>::iter_prefix(target)
+            .map(|(reaction_id, _)| reaction_id)
+            .collect();
+        reaction_ids.sort_unstable();
+        reaction_ids
+    }
+
+    /// Get ids of all reactions on a post or a comment.
+    pub fn reaction_ids_by_post_id(post_id: PostId) -> Vec<ReactionId> {
+        Self::reaction_ids_by_target(&ReactionTarget::Post(post_id))
+    }
+
+    /// Whether an account has already given a reaction of this kind to a target.
+    /// An account can either upvote or downvote a target, but can add several different emojis.
+    pub fn has_reacted(account: &T::AccountId, target: &ReactionTarget<T::AccountId>, kind: ReactionKind) -> bool {
+        Self::find_reaction_id(account, target, kind).is_some()
+    }
+
+    /// Find an id of a reaction of this kind that an account gave to a target.
+    /// Upvotes and downvotes share the same slot, so either of them is found by any vote kind.
+    pub fn find_reaction_id(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+        kind: ReactionKind
+    ) -> Option<ReactionId> {
+        match kind {
+            ReactionKind::Emoji(code) => Self::emoji_reaction_id_by_account(target, (account, code)),
+            _ => Self::reaction_id_by_account(target, account),
+        }
+    }
+
+    pub(crate) fn insert_reaction_id_by_account(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+        kind: ReactionKind,
+        reaction_id: ReactionId
+    ) {
+        match kind {
+            ReactionKind::Emoji(code) => <EmojiReactionIdByAccount<T>>::insert(target, (account, code), reaction_id),
+            _ => <ReactionIdByAccount<T>>::insert(target, account, reaction_id),
+        }
+    }
+
+    fn remove_reaction_id_by_account(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+        kind: ReactionKind
+    ) {
+        match kind {
+            ReactionKind::Emoji(code) => <EmojiReactionIdByAccount<T>>::remove(target, (account, code)),
+            _ => <ReactionIdByAccount<T>>::remove(target, account),
+        }
+    }
+
+    /// Queue a deleted target, so that its reactions are removed in batches from `on_initialize`.
+    pub(crate) fn queue_reactions_cleanup(target: ReactionTarget<T::AccountId>) {
+        let index = Self::next_deleted_target_index();
+        DeletedTargetByIndex::<T>::insert(index, target);
+        NextDeletedTargetIndex::put(index.saturating_add(1));
+    }
+
+    /// Remove at most `MaxReactionsToCleanUpPerBlock` reactions on deleted targets,
+    /// continuing from the target where the previous block stopped.
+    pub(crate) fn clean_up_reactions_on_deleted_targets() -> Weight {
+        let first_index = Self::first_deleted_target_index();
+        let next_index = Self::next_deleted_target_index();
+        if first_index == next_index {
+            return T::DbWeight::get().reads(2)
+        }
+
+        let limit = T::MaxReactionsToCleanUpPerBlock::get();
+        let mut removed: u32 = 0;
+        let mut index = first_index;
+
+        while index < next_index && removed < limit {
+            if let Some(target) = Self::deleted_target_by_index(index) {
+                let remaining = limit - removed;
+                let removed_on_target = Self::remove_reactions_on_target(&target, remaining);
+                removed = removed.saturating_add(removed_on_target);
+
+                if removed_on_target >= remaining {
+                    // This target may have more reactions to remove in the next block.
+                    break;
+                }
+                // There are only a few counters per target: one per reaction kind.
+                <ReactionsCountByKind<T>>::remove_prefix(&target);
+            }
+            DeletedTargetByIndex::<T>::remove(index);
+            index = index.saturating_add(1);
+        }
+
+        FirstDeletedTargetIndex::put(index);
+
+        let processed_targets = index.saturating_sub(first_index).saturating_add(1);
+        <T as Trait>::WeightInfo::remove_reactions(removed)
+            .saturating_add(T::DbWeight::get().reads_writes(processed_targets.saturating_add(2), processed_targets))
+    }
+
+    /// Remove at most `limit` reactions on a target and return how many were removed.
+    pub(crate) fn remove_reactions_on_target(target: &ReactionTarget<T::AccountId>, limit: u32) -> u32 {
+        let mut removed: u32 = 0;
+        for (reaction_id, _) in <ReactionIdsByTarget<T>>::drain_prefix(target).take(limit as usize) {
+            if let Some(reaction) = <ReactionById<T>>::take(reaction_id) {
+                Self::remove_reaction_id_by_account(&reaction.created.account, target, reaction.kind);
+            }
+            removed += 1;
+        }
+        removed
+    }
+
+    /// A weight of creating a reaction, as it depends on the kind of a target and a reaction.
+    /// Only votes for posts go through the scoring logic.
+    pub fn create_reaction_weight(target: &ReactionTarget<T::AccountId>, kind: &ReactionKind) -> Weight {
+        match target {
+            ReactionTarget::Post(_) if kind.is_vote() => <T as Trait>::WeightInfo::create_reaction(),
+            ReactionTarget::Post(_) => <T as Trait>::WeightInfo::create_emoji_reaction(),
+            ReactionTarget::Space(_) => <T as Trait>::WeightInfo::create_space_reaction(),
+            ReactionTarget::Profile(_) => <T as Trait>::WeightInfo::create_profile_reaction(),
+        }
+    }
+
+    /// Check that an account can react with this kind to a target,
+    /// following permissions and moderation rules of the target's scope:
+    /// a post is checked against its space, a space against itself,
+    /// and a profile only against a blocklist of its owner.
+    pub fn ensure_can_react_to(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>,
+        kind: ReactionKind
+    ) -> DispatchResult {
+        match target {
+            ReactionTarget::Post(post_id) => {
+                let post = Posts::<T>::require_post(*post_id)?;
+                let space = post.get_space()?;
+                ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+                ensure!(Posts::<T>::is_root_post_visible(*post_id)?, Error::<T>::CannotReactWhenPostHidden);
+
+                ensure!(
+                    T::IsAccountBlocked::is_allowed_account(account.clone(), space.id),
+                    UtilsError::<T>::AccountIsBlocked
+                );
+                Posts::ensure_not_blocked_by_post_owner(account, &post)?;
+
+                Self::ensure_reaction_allowed_in_space(space.id, kind)?;
+                Self::ensure_account_can_react(account.clone(), &post, &space, kind)
+            },
+            ReactionTarget::Space(space_id) => {
+                let space = Spaces::<T>::require_space(*space_id)?;
+                ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+
+                ensure!(
+                    T::IsAccountBlocked::is_allowed_account(account.clone(), space.id),
+                    UtilsError::<T>::AccountIsBlocked
+                );
+                Self::ensure_not_blocked_by_account(account, &space.owner)?;
+
+                Self::ensure_reaction_allowed_in_space(space.id, kind)?;
+                Spaces::<T>::ensure_account_has_space_permission(
+                    account.clone(),
+                    &space,
+                    Self::permission_to_react(kind),
+                    Self::no_permission_to_react_error(kind).into(),
+                )
+            },
+            ReactionTarget::Profile(profile_owner) => {
+                let has_profile = Profiles::<T>::social_account_by_id(profile_owner)
+                    .map_or(false, |social_account| social_account.profile.is_some());
+                ensure!(has_profile, ProfilesError::<T>::AccountHasNoProfile);
+
+                Self::ensure_not_blocked_by_account(account, profile_owner)?;
+
+                // Profiles are not a part of any space, so they accept votes and any supported emoji.
+                if let ReactionKind::Emoji(code) = kind {
+                    ensure!(T::SupportedEmojis::get().contains(&code), Error::<T>::UnsupportedEmoji);
+                }
+                Ok(())
+            },
+        }
+    }
+
+    /// Check that an account is not blocked by moderators in the scope of a target.
+    fn ensure_not_blocked_in_target_scope(
+        account: &T::AccountId,
+        target: &ReactionTarget<T::AccountId>
+    ) -> DispatchResult {
+        let space_id_opt = match target {
+            ReactionTarget::Post(post_id) => Posts::<T>::require_post(*post_id)?.try_get_space_id(),
+            ReactionTarget::Space(space_id) => Some(*space_id),
+            ReactionTarget::Profile(_) => None,
+        };
+
+        if let Some(space_id) = space_id_opt {
+            ensure!(
+                T::IsAccountBlocked::is_allowed_account(account.clone(), space_id),
+                UtilsError::<T>::AccountIsBlocked
+            );
+        }
+        Ok(())
+    }
+
+    fn ensure_not_blocked_by_account(account: &T::AccountId, blocker: &T::AccountId) -> DispatchResult {
+        ensure!(
+            !<T as pallet_posts::Trait>::IsBlockedByAccount::is_blocked_by_account(account, blocker),
+            UtilsError::<T>::AccountIsBlockedByAccount
+        );
+        Ok(())
+    }
+
+    /// Check that a space allows reactions of this kind.
+    /// Spaces without a custom set of reactions allow only upvotes and downvotes.
+    pub fn ensure_reaction_allowed_in_space(space_id: SpaceId, kind: ReactionKind) -> DispatchResult {
+        let is_allowed = match Self::allowed_reactions_by_space_id(space_id) {
+            Some(allowed_reactions) => allowed_reactions.contains(&kind),
+            None => kind.is_vote(),
+        };
+        ensure!(is_allowed, Error::<T>::ReactionNotAllowedInSpace);
+        Ok(())
+    }
+
+    /// Check that account has permission to react with this kind to this post in a given space,
+    /// taking into account permission overrides of the post.
+    pub fn ensure_account_can_react(
+        account: T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>,
+        kind: ReactionKind,
+    ) -> DispatchResult {
+        Posts::<T>::ensure_account_has_post_permission(
+            account,
+            post,
+            space,
+            Self::permission_to_react(kind),
+            Self::no_permission_to_react_error(kind).into()
+        )
+    }
+
+    fn permission_to_react(kind: ReactionKind) -> SpacePermission {
+        match kind {
+            ReactionKind::Upvote => SpacePermission::Upvote,
+            ReactionKind::Downvote => SpacePermission::Downvote,
+            ReactionKind::Emoji(_) => SpacePermission::React,
+        }
+    }
+
+    fn no_permission_to_react_error(kind: ReactionKind) -> Error<T> {
+        match kind {
+            ReactionKind::Upvote => Error::<T>::NoPermissionToUpvote,
+            ReactionKind::Downvote => Error::<T>::NoPermissionToDownvote,
+            ReactionKind::Emoji(_) => Error::<T>::NoPermissionToReact,
+        }
+    }
+}
@@ -4,24 +4,46 @@ use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    traits::Get,
+    weights::Weight,
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use df_traits::moderation::IsAccountBlocked;
+use df_traits::CleanUpReapedAccount;
 use pallet_permissions::SpacePermission;
-use pallet_posts::{Module as Posts, Post, PostById, PostId};
-use pallet_spaces::Module as Spaces;
-use pallet_utils::{Error as UtilsError, remove_from_vec, WhoAndWhen};
+use pallet_posts::{Module as Posts, Post, PostById, PostId, AfterPostDeleted};
+use pallet_spaces::{Module as Spaces, Space, AfterSpaceDeleted};
+use pallet_utils::{SpaceId, WhoAndWhen};
+
+pub mod functions;
+pub mod migration;
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use weights::WeightInfo;
 
 pub type ReactionId = u64;
 
-#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+/// A code of an emoji from the registry of emojis supported by the runtime,
+/// e.g. a Unicode code point.
+pub type EmojiCode = u32;
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
 pub enum ReactionKind {
     Upvote,
     Downvote,
+    Emoji(EmojiCode),
+}
+
+impl ReactionKind {
+    /// Only votes affect a score of a post and reputation of its owner.
+    pub fn is_vote(&self) -> bool {
+        matches!(self, ReactionKind::Upvote | ReactionKind::Downvote)
+    }
 }
 
 impl Default for ReactionKind {
@@ -30,6 +52,16 @@ impl Default for ReactionKind {
     }
 }
 
+/// An entity that can be reacted to.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum ReactionTarget<AccountId> {
+    /// A post or a comment.
+    Post(PostId),
+    Space(SpaceId),
+    /// A profile of an account.
+    Profile(AccountId),
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Reaction<T: Trait> {
     pub id: ReactionId,
@@ -38,16 +70,43 @@ pub struct Reaction<T: Trait> {
     pub kind: ReactionKind,
 }
 
+// A value placed in storage that represents the current version of the Reactions storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
     + pallet_posts::Trait
+    + pallet_profiles::Trait
     + pallet_spaces::Trait
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type PostReactionScores: PostReactionScores<Self>;
+
+    /// A fixed registry of emojis that spaces can allow as reactions.
+    type SupportedEmojis: Get<Vec<EmojiCode>>;
+
+    /// A max number of reactions on deleted targets to remove per block.
+    type MaxReactionsToCleanUpPerBlock: Get<u32>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 // This pallet's storage items.
@@ -58,21 +117,59 @@ decl_storage! {
         pub ReactionById get(fn reaction_by_id):
             map hasher(twox_64_concat) ReactionId => Option<Reaction<T>>;
 
-        pub ReactionIdsByPostId get(fn reaction_ids_by_post_id):
-            map hasher(twox_64_concat) PostId => Vec<ReactionId>;
+        /// Whether a reaction (2nd key) was given to a target (1st key).
+        pub ReactionIdsByTarget:
+            double_map hasher(blake2_128_concat) ReactionTarget<T::AccountId>, hasher(twox_64_concat) ReactionId
+            => bool;
+
+        /// An id of an upvote or a downvote that an account (2nd key) gave to a target (1st key).
+        pub ReactionIdByAccount get(fn reaction_id_by_account):
+            double_map hasher(blake2_128_concat) ReactionTarget<T::AccountId>, hasher(blake2_128_concat) T::AccountId
+            => Option<ReactionId>;
+
+        /// An id of an emoji reaction that an account added to a target (1st key).
+        /// The 2nd key is a pair of the account and the emoji.
+        pub EmojiReactionIdByAccount get(fn emoji_reaction_id_by_account):
+            double_map hasher(blake2_128_concat) ReactionTarget<T::AccountId>,
+                hasher(blake2_128_concat) (T::AccountId, EmojiCode)
+            => Option<ReactionId>;
+
+        /// The number of reactions of a given kind (2nd key) on a target (1st key).
+        pub ReactionsCountByKind get(fn reactions_count_by_kind):
+            double_map hasher(blake2_128_concat) ReactionTarget<T::AccountId>, hasher(twox_64_concat) ReactionKind
+            => u32;
+
+        /// Reaction kinds allowed in a space. If not set, only upvotes and downvotes are allowed.
+        pub AllowedReactionsBySpaceId get(fn allowed_reactions_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<Vec<ReactionKind>>;
 
-        pub PostReactionIdByAccount get(fn post_reaction_id_by_account):
-            map hasher(twox_64_concat) (T::AccountId, PostId) => ReactionId;
+        /// Deleted targets in the order of removing their reactions from `on_initialize`.
+        pub DeletedTargetByIndex get(fn deleted_target_by_index):
+            map hasher(twox_64_concat) u64 => Option<ReactionTarget<T::AccountId>>;
+
+        /// An index of the next deleted target whose reactions should be removed.
+        pub FirstDeletedTargetIndex get(fn first_deleted_target_index): u64;
+
+        /// An index that will be given to the next deleted target.
+        pub NextDeletedTargetIndex get(fn next_deleted_target_index): u64;
+
+        StorageVersion build(|_: &GenesisConfig| Releases::V3_0_0): Releases;
     }
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
+        ReactionTarget = ReactionTarget<<T as system::Trait>::AccountId>,
     {
         PostReactionCreated(AccountId, PostId, ReactionId),
         PostReactionUpdated(AccountId, PostId, ReactionId),
         PostReactionDeleted(AccountId, PostId, ReactionId),
+        AllowedSpaceReactionsUpdated(AccountId, SpaceId),
+        /// A reaction was given to a space or a profile.
+        ReactionCreated(AccountId, ReactionTarget, ReactionId),
+        ReactionUpdated(AccountId, ReactionTarget, ReactionId),
+        ReactionDeleted(AccountId, ReactionTarget, ReactionId),
     }
 );
 
@@ -98,6 +195,19 @@ decl_error! {
         NoPermissionToUpvote,
         /// User has no permission to downvote posts/comments in this space.
         NoPermissionToDownvote,
+        /// User has no permission to add emoji reactions to posts/comments in this space.
+        NoPermissionToReact,
+        /// User has no permission to update allowed reactions of this space.
+        NoPermissionToUpdateAllowedReactions,
+
+        /// This reaction kind is not allowed in the space.
+        ReactionNotAllowedInSpace,
+        /// Emoji is not in the registry of supported emojis.
+        UnsupportedEmoji,
+        /// Emoji reactions cannot be changed, only added or deleted.
+        CannotUpdateEmojiReaction,
+        /// There are more allowed reactions than supported emojis, upvote and downvote.
+        TooManyAllowedReactions,
     }
 }
 
@@ -107,161 +217,135 @@ decl_module! {
     // Initializing errors
     type Error = Error<T>;
 
+    const MaxReactionsToCleanUpPerBlock: u32 = T::MaxReactionsToCleanUpPerBlock::get();
+
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 5)]
-    pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
-      let owner = ensure_signed(origin)?;
-
-      let post = &mut Posts::require_post(post_id)?;
-      ensure!(
-        !<PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::AccountAlreadyReacted
-      );
-
-      let space = post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
-      ensure!(Posts::<T>::is_root_post_visible(post_id)?, Error::<T>::CannotReactWhenPostHidden);
-
-      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-
-      let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
-
-      match kind {
-        ReactionKind::Upvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Upvote,
-            Error::<T>::NoPermissionToUpvote.into()
-          )?;
-          post.inc_upvotes();
-        },
-        ReactionKind::Downvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Downvote,
-            Error::<T>::NoPermissionToDownvote.into()
-          )?;
-          post.inc_downvotes();
-        }
-      }
-
-      if post.is_owner(&owner) {
-        <PostById<T>>::insert(post_id, post.clone());
-      }
-
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, kind)?;
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
 
-      ReactionIdsByPostId::mutate(post.id, |ids| ids.push(reaction_id));
-      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+    fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+      Self::clean_up_reactions_on_deleted_targets()
+    }
 
-      Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id));
-      Ok(())
+    #[weight = Module::<T>::create_reaction_weight(&ReactionTarget::Post(*post_id), kind)]
+    pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_create_reaction(owner, ReactionTarget::Post(post_id), kind)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+    #[weight = <T as Trait>::WeightInfo::update_reaction()]
     pub fn update_post_reaction(origin, post_id: PostId, reaction_id: ReactionId, new_kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
-
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
-
-      let mut reaction = Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?;
-      let post = &mut Posts::require_post(post_id)?;
-
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
-
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
-
-      let old_kind = reaction.kind;
-      reaction.kind = new_kind;
-      reaction.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
-
-      match new_kind {
-        ReactionKind::Upvote => {
-          post.inc_upvotes();
-          post.dec_downvotes();
-        },
-        ReactionKind::Downvote => {
-          post.inc_downvotes();
-          post.dec_upvotes();
-        },
-      }
-
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, old_kind)?;
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, new_kind)?;
-
-      <ReactionById<T>>::insert(reaction_id, reaction);
-      <PostById<T>>::insert(post_id, post);
-
-      Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id));
-      Ok(())
+      Self::do_update_reaction(owner, ReactionTarget::Post(post_id), reaction_id, new_kind)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[weight = <T as Trait>::WeightInfo::delete_reaction()]
     pub fn delete_post_reaction(origin, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
       let owner = ensure_signed(origin)?;
+      Self::do_delete_reaction(owner, ReactionTarget::Post(post_id), reaction_id)
+    }
 
-      ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
-        Error::<T>::ReactionByAccountNotFound
-      );
+    /// React to a post, a space or a profile.
+    #[weight = Module::<T>::create_reaction_weight(target, kind)]
+    pub fn create_reaction(origin, target: ReactionTarget<T::AccountId>, kind: ReactionKind) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_create_reaction(owner, target, kind)
+    }
 
-      // TODO extract Self::require_reaction(reaction_id)?;
-      let reaction = Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?;
-      let post = &mut Posts::require_post(post_id)?;
+    #[weight = <T as Trait>::WeightInfo::update_reaction()]
+    pub fn update_reaction(
+      origin,
+      target: ReactionTarget<T::AccountId>,
+      reaction_id: ReactionId,
+      new_kind: ReactionKind
+    ) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_update_reaction(owner, target, reaction_id, new_kind)
+    }
 
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
-      if let Some(space_id) = post.try_get_space_id() {
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
-      }
+    #[weight = <T as Trait>::WeightInfo::delete_reaction()]
+    pub fn delete_reaction(origin, target: ReactionTarget<T::AccountId>, reaction_id: ReactionId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+      Self::do_delete_reaction(owner, target, reaction_id)
+    }
 
-      match reaction.kind {
-        ReactionKind::Upvote => post.dec_upvotes(),
-        ReactionKind::Downvote => post.dec_downvotes(),
+    /// Set reaction kinds allowed in a space, or reset them to upvotes and downvotes with `None`.
+    #[weight = <T as Trait>::WeightInfo::set_allowed_space_reactions(
+      reactions_opt.as_ref().map_or(0, |reactions| reactions.len() as u32)
+    )]
+    pub fn set_allowed_space_reactions(
+      origin,
+      space_id: SpaceId,
+      reactions_opt: Option<Vec<ReactionKind>>
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      // Upvote and downvote can be allowed along with all supported emojis.
+      if let Some(reactions) = &reactions_opt {
+        let max_reactions = T::SupportedEmojis::get().len().saturating_add(2);
+        ensure!(reactions.len() <= max_reactions, Error::<T>::TooManyAllowedReactions);
       }
 
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, reaction.kind)?;
-
-      <PostById<T>>::insert(post_id, post.clone());
-      <ReactionById<T>>::remove(reaction_id);
-      ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
-      <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+      let space = Spaces::<T>::require_space(space_id)?;
+      Spaces::<T>::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::UpdateSpaceSettings,
+        Error::<T>::NoPermissionToUpdateAllowedReactions.into(),
+      )?;
+
+      match reactions_opt {
+        Some(mut reactions) => {
+          let supported_emojis = T::SupportedEmojis::get();
+          for kind in reactions.iter() {
+            if let ReactionKind::Emoji(code) = kind {
+              ensure!(supported_emojis.contains(code), Error::<T>::UnsupportedEmoji);
+            }
+          }
+
+          reactions.sort();
+          reactions.dedup();
+          AllowedReactionsBySpaceId::insert(space_id, reactions);
+        },
+        None => AllowedReactionsBySpaceId::remove(space_id),
+      }
 
-      Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id));
+      Self::deposit_event(RawEvent::AllowedSpaceReactionsUpdated(who, space_id));
       Ok(())
     }
   }
 }
 
-impl<T: Trait> Module<T> {
-
-    // FIXME: don't add reaction in storage before the checks in 'create_reaction' are done
-    pub fn insert_new_reaction(account: T::AccountId, kind: ReactionKind) -> ReactionId {
-        let id = Self::next_reaction_id();
-        let reaction: Reaction<T> = Reaction {
-            id,
-            created: WhoAndWhen::<T>::new(account),
-            updated: None,
-            kind
-        };
+impl<T: Trait> AfterPostDeleted<T> for Module<T> {
+    fn after_post_deleted(_account: T::AccountId, post: &Post<T>) {
+        Self::queue_reactions_cleanup(ReactionTarget::Post(post.id));
+    }
+}
 
-        <ReactionById<T>>::insert(id, reaction);
-        NextReactionId::mutate(|n| { *n += 1; });
+impl<T: Trait> AfterSpaceDeleted<T> for Module<T> {
+    fn after_space_deleted(_account: T::AccountId, space: &Space<T>) {
+        Self::queue_reactions_cleanup(ReactionTarget::Space(space.id));
+        AllowedReactionsBySpaceId::remove(space.id);
+    }
+}
 
-        id
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+    /// Remove reactions on a profile of a reaped account.
+    fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+        let target = ReactionTarget::Profile(who.clone());
+        let removed = Self::remove_reactions_on_target(&target, limit);
+        if removed < limit {
+            <ReactionsCountByKind<T>>::remove_prefix(&target);
+        }
+        removed
     }
 }
 
-/// Handler that will be called right before the post reaction is toggled.
+/// Handler that will be called right before the post upvote or downvote is toggled.
+/// Emoji reactions do not affect scores, so it is not called for them.
 pub trait PostReactionScores<T: Trait> {
     fn score_post_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: ReactionKind) -> DispatchResult;
 }
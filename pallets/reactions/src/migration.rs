@@ -0,0 +1,119 @@
+use frame_support::{
+    Blake2_128Concat, Twox64Concat,
+    storage::{
+        migration::{remove_storage_prefix, StorageKeyIterator},
+        StorageDoubleMap, StorageValue,
+    },
+};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_post_reactions_to_targets::<T>());
+    }
+
+    if StorageVersion::get() == Releases::V2_0_0 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+    }
+
+    weight
+}
+
+const MODULE_PREFIX: &[u8] = b"ReactionsModule";
+
+/// Move reactions on posts to the storage keyed by reaction targets.
+///
+/// Indexes by account and counters by kind are rebuilt from the reactions themselves,
+/// so only the list of reaction ids per post is read from the old storage.
+/// Reactions are written straight to the latest storage layout.
+fn migrate_post_reactions_to_targets<T: Trait>() -> Weight {
+    let reaction_ids_by_post_id: Vec<(PostId, Vec<ReactionId>)> =
+        StorageKeyIterator::<PostId, Vec<ReactionId>, Twox64Concat>::new(MODULE_PREFIX, b"ReactionIdsByPostId")
+            .drain()
+            .collect();
+
+    for item in &[&b"PostReactionIdByAccount"[..], b"PostEmojiReactionIdByAccount", b"PostReactionsCountByKind"] {
+        remove_storage_prefix(MODULE_PREFIX, item, &[]);
+    }
+
+    let mut reactions_count: Weight = 0;
+
+    for (post_id, reaction_ids) in reaction_ids_by_post_id.iter() {
+        let target = ReactionTarget::Post(*post_id);
+
+        for reaction_id in reaction_ids {
+            if let Some(reaction) = Module::<T>::reaction_by_id(reaction_id) {
+                <ReactionIdsByTarget<T>>::insert(&target, reaction_id, true);
+                Module::<T>::insert_reaction_id_by_account(
+                    &reaction.created.account, &target, reaction.kind, *reaction_id
+                );
+                <ReactionsCountByKind<T>>::mutate(&target, reaction.kind, |count| *count = count.saturating_add(1));
+            }
+        }
+
+        reactions_count = reactions_count.saturating_add(reaction_ids.len() as Weight);
+    }
+
+    StorageVersion::put(Releases::V3_0_0);
+
+    let posts_count = reaction_ids_by_post_id.len() as Weight;
+
+    // Every reaction is read once and written to the list of the target, the index and the counter.
+    T::DbWeight::get().reads_writes(
+        posts_count.saturating_add(reactions_count.saturating_mul(2)).saturating_add(1),
+        posts_count.saturating_add(reactions_count.saturating_mul(3)).saturating_add(4)
+    )
+}
+
+/// Move reaction lists and indexes by account from maps with tuple keys to double maps.
+fn migrate_to_v3<T: Trait>() -> Weight {
+    // Old values share a storage prefix with new double maps, so they should be read out first.
+    let reaction_ids_by_target: Vec<(ReactionTarget<T::AccountId>, Vec<ReactionId>)> =
+        StorageKeyIterator::<_, Vec<ReactionId>, Blake2_128Concat>::new(MODULE_PREFIX, b"ReactionIdsByTarget")
+            .drain()
+            .collect();
+
+    let reaction_id_by_account: Vec<((T::AccountId, ReactionTarget<T::AccountId>), ReactionId)> =
+        StorageKeyIterator::<_, ReactionId, Blake2_128Concat>::new(MODULE_PREFIX, b"ReactionIdByAccount")
+            .drain()
+            .collect();
+
+    let emoji_reaction_id_by_account: Vec<((T::AccountId, ReactionTarget<T::AccountId>, EmojiCode), ReactionId)> =
+        StorageKeyIterator::<_, ReactionId, Blake2_128Concat>::new(MODULE_PREFIX, b"EmojiReactionIdByAccount")
+            .drain()
+            .collect();
+
+    let mut writes: Weight = 0;
+
+    for (target, reaction_ids) in reaction_ids_by_target.iter() {
+        for reaction_id in reaction_ids {
+            <ReactionIdsByTarget<T>>::insert(target, reaction_id, true);
+        }
+        writes = writes.saturating_add(reaction_ids.len() as Weight);
+    }
+
+    for ((account, target), reaction_id) in reaction_id_by_account.iter() {
+        <ReactionIdByAccount<T>>::insert(target, account, reaction_id);
+    }
+
+    for ((account, target, code), reaction_id) in emoji_reaction_id_by_account.iter() {
+        <EmojiReactionIdByAccount<T>>::insert(target, (account, code), reaction_id);
+    }
+
+    StorageVersion::put(Releases::V3_0_0);
+
+    let reads = (reaction_ids_by_target.len()
+        + reaction_id_by_account.len()
+        + emoji_reaction_id_by_account.len()) as Weight;
+
+    writes = writes
+        .saturating_add(reaction_id_by_account.len() as Weight)
+        .saturating_add(emoji_reaction_id_by_account.len() as Weight);
+
+    // Every old entry is also removed.
+    T::DbWeight::get().reads_writes(reads.saturating_add(1), reads.saturating_add(writes).saturating_add(1))
+}
@@ -0,0 +1,124 @@
+//! Weights for `pallet_reactions`.
+//!
+//! NOTE: These values are estimates based on the storage reads and writes of each extrinsic,
+//! they were not produced by the benchmarking CLI yet. Regenerate this file with:
+//!
+//! ```sh
+//! ./target/release/subsocial-node benchmark \
+//!     --chain dev --execution wasm --wasm-execution compiled \
+//!     --pallet pallet_reactions --extrinsic '*' --steps 50 --repeat 20 \
+//!     --output ./pallets/reactions/src/weights.rs
+//! ```
+//! and keep the `WeightInfo` trait and the `()` implementation below.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_reactions`.
+pub trait WeightInfo {
+    fn create_reaction() -> Weight;
+    fn create_emoji_reaction() -> Weight;
+    fn create_space_reaction() -> Weight;
+    fn create_profile_reaction() -> Weight;
+    fn update_reaction() -> Weight;
+    fn delete_reaction() -> Weight;
+    fn set_allowed_space_reactions(n: u32) -> Weight;
+    fn remove_reactions(r: u32) -> Weight;
+}
+
+/// Weights for `pallet_reactions` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_reaction() -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn create_emoji_reaction() -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn create_space_reaction() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn create_profile_reaction() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn update_reaction() -> Weight {
+        (130_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn delete_reaction() -> Weight {
+        (100_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn set_allowed_space_reactions(n: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_reactions(r: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((12_000_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(r as Weight)))
+    }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+    fn create_reaction() -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn create_emoji_reaction() -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn create_space_reaction() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn create_profile_reaction() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn update_reaction() -> Weight {
+        (130_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn delete_reaction() -> Weight {
+        (100_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    fn set_allowed_space_reactions(n: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn remove_reactions(r: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((12_000_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(r as Weight)))
+    }
+}
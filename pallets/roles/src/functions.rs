@@ -1,7 +1,13 @@
 use super::*;
 
-use frame_support::dispatch::DispatchError;
+use frame_support::{
+  dispatch::DispatchError,
+  storage::IterableStorageDoubleMap,
+};
+use df_traits::CleanUpReapedAccount;
 use pallet_permissions::SpacePermissionsContext;
+use pallet_spaces::{AfterSpaceDeleted, Space};
+use pallet_utils::remove_from_vec;
 
 impl<T: Trait> Module<T> {
 
@@ -31,6 +37,20 @@ impl<T: Trait> Module<T> {
     )
   }
 
+  /// Ensure that this account is not blocked and has 'UpdateSpaceSettings' permission in a given space
+  pub fn ensure_min_reputation_manager(account: T::AccountId, space_id: SpaceId) -> DispatchResult {
+    ensure!(
+      T::IsAccountBlocked::is_allowed_account(account.clone(), space_id),
+      UtilsError::<T>::AccountIsBlocked
+    );
+    Self::ensure_user_has_space_permission_with_load_space(
+      User::Account(account),
+      space_id,
+      SpacePermission::UpdateSpaceSettings,
+      Error::<T>::NoPermissionToUpdateMinReputation.into()
+    )
+  }
+
   fn ensure_user_has_space_permission_with_load_space(
     user: User<T::AccountId>,
     space_id: SpaceId,
@@ -59,7 +79,9 @@ impl<T: Trait> Module<T> {
         space_id,
         is_space_owner: is_owner,
         is_space_follower: is_follower,
-        space_perms: space.permissions
+        space_perms: space.permissions,
+        inherited_perms: space.inherited_permissions,
+        post_perms: None,
       },
       permission,
       error
@@ -73,31 +95,66 @@ impl<T: Trait> Module<T> {
     error: DispatchError,
   ) -> DispatchResult {
 
+    // Roles and reputation requirements of parent spaces apply here too,
+    // if this space inherits their permissions.
+    let space_ids: Vec<SpaceId> = sp_std::iter::once(ctx.space_id)
+      .chain(ctx.inherited_perms.iter().map(|(space_id, _)| *space_id))
+      .collect();
+
+    let is_space_owner = ctx.is_space_owner;
+
     match Permissions::<T>::has_user_a_space_permission(
-      ctx.clone(),
+      ctx,
       permission.clone()
     ) {
-      Some(true) => return Ok(()),
+      Some(true) => (),
       Some(false) => return Err(error),
-      _ => (/* Need to check in dynamic roles */)
+      _ => Self::has_permission_in_space_roles(
+        user.clone(),
+        space_ids.clone(),
+        permission.clone(),
+        error
+      )?,
     }
 
-    Self::has_permission_in_space_roles(
-      user,
-      ctx.space_id,
-      permission,
-      error
-    )
+    // The space owner should not be locked out of their space.
+    if !is_space_owner {
+      Self::ensure_user_has_min_reputation(&user, &space_ids, &permission)?;
+    }
+
+    Ok(())
+  }
+
+  /// Ensure that an account has at least the highest reputation required for a permission
+  /// in the given spaces. Reputation is not required from spaces that act as users.
+  fn ensure_user_has_min_reputation(
+    user: &User<T::AccountId>,
+    space_ids: &[SpaceId],
+    permission: &SpacePermission,
+  ) -> DispatchResult {
+
+    if let User::Account(account) = user {
+      let min_reputation_opt = space_ids.iter()
+        .filter_map(|space_id| Self::min_reputation_by_space_permission(space_id, permission))
+        .max();
+
+      if let Some(min_reputation) = min_reputation_opt {
+        ensure!(T::Reputation::reputation_of(account) >= min_reputation, Error::<T>::NotEnoughReputation);
+      }
+    }
+
+    Ok(())
   }
 
   fn has_permission_in_space_roles(
     user: User<T::AccountId>,
-    space_id: SpaceId,
+    space_ids: Vec<SpaceId>,
     permission: SpacePermission,
     error: DispatchError,
   ) -> DispatchResult {
 
-    let role_ids = Self::role_ids_by_user_in_space(user, space_id);
+    let role_ids = space_ids.into_iter()
+      .flat_map(|space_id| Self::role_ids_by_user_in_space(&user, space_id));
 
     for role_id in role_ids {
       if let Some(role) = Self::role_by_id(role_id) {
@@ -122,6 +179,172 @@ impl<T: Trait> Module<T> {
   }
 }
 
+impl<T: Trait> Module<T> {
+
+  /// Move a role from the list of roles that expire at an old block to the list of a new block.
+  pub(crate) fn move_role_to_expire_block(
+    role_id: RoleId,
+    old_expires_at: Option<T::BlockNumber>,
+    new_expires_at: Option<T::BlockNumber>,
+  ) {
+    if let Some(expires_at) = old_expires_at {
+      <RoleIdsByExpireBlock<T>>::mutate(expires_at, |role_ids| remove_from_vec(role_ids, role_id));
+    }
+    if let Some(expires_at) = new_expires_at {
+      <RoleIdsByExpireBlock<T>>::mutate(expires_at, |role_ids| role_ids.push(role_id));
+    }
+  }
+
+  /// Revoke roles that expired at a given block, and those left from the previous blocks,
+  /// from at most `MaxExpiredRoleEntriesPerBlock` roles and users in total.
+  pub(crate) fn revoke_expired_roles(expired_at: T::BlockNumber) -> Weight {
+    let expired_at_block_count = <RoleIdsByExpireBlock<T>>::decode_len(expired_at).unwrap_or(0);
+    if expired_at_block_count == 0 && ExpiredRoleIds::decode_len().unwrap_or(0) == 0 {
+      return T::DbWeight::get().reads(2)
+    }
+
+    let mut role_ids = ExpiredRoleIds::take();
+    for role_id in <RoleIdsByExpireBlock<T>>::take(expired_at) {
+      if !role_ids.contains(&role_id) {
+        role_ids.push(role_id);
+      }
+    }
+
+    let limit = T::MaxExpiredRoleEntriesPerBlock::get();
+    let mut processed: u32 = 0;
+    let mut revoked_users: u32 = 0;
+    let mut expired_roles: usize = 0;
+
+    while expired_roles < role_ids.len() && processed < limit {
+      processed += 1;
+      let (revoked, is_revoked_from_all) = Self::expire_role(role_ids[expired_roles], limit - processed);
+      processed = processed.saturating_add(revoked);
+      revoked_users = revoked_users.saturating_add(revoked);
+
+      if !is_revoked_from_all {
+        // This role has more users to be revoked from in the next block.
+        break;
+      }
+      expired_roles += 1;
+    }
+
+    ExpiredRoleIds::put(role_ids.split_off(expired_roles));
+
+    let roles_count = processed.saturating_sub(revoked_users) as Weight;
+    T::DbWeight::get().reads_writes(2, 2)
+      // A role and its users are read, and the users are updated.
+      .saturating_add(T::DbWeight::get().reads_writes(2, 1).saturating_mul(roles_count))
+      // Role ids of a user in a space are updated.
+      .saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(revoked_users as Weight))
+  }
+
+  /// Remove at most `MaxRoleEntriesToCleanUpPerBlock` roles of deleted spaces and their users,
+  /// continuing from the space where the previous block stopped.
+  pub(crate) fn clean_up_roles_of_deleted_spaces() -> Weight {
+    let first_index = Self::first_deleted_space_index();
+    let next_index = Self::next_deleted_space_index();
+    if first_index == next_index {
+      return T::DbWeight::get().reads(2)
+    }
+
+    let limit = T::MaxRoleEntriesToCleanUpPerBlock::get();
+    let mut removed: u32 = 0;
+    let mut index = first_index;
+
+    while index < next_index && removed < limit {
+      if let Some(space_id) = Self::deleted_space_id_by_index(index) {
+        let (removed_of_space, is_space_cleaned_up) = Self::remove_roles_of_space(space_id, limit - removed);
+        removed = removed.saturating_add(removed_of_space);
+
+        if !is_space_cleaned_up {
+          // This space has more roles to remove in the next block.
+          break;
+        }
+      }
+      DeletedSpaceIdByIndex::remove(index);
+      index = index.saturating_add(1);
+    }
+
+    FirstDeletedSpaceIndex::put(index);
+
+    let processed_spaces = index.saturating_sub(first_index).saturating_add(1);
+    T::DbWeight::get().reads_writes(processed_spaces.saturating_mul(2).saturating_add(2), processed_spaces.saturating_mul(2))
+      // A role is removed with its expiry and content deposit, or a user of a role is removed.
+      .saturating_add(T::DbWeight::get().reads_writes(3, 3).saturating_mul(removed as Weight))
+  }
+
+  /// Remove at most `limit` roles of a deleted space and users of these roles.
+  /// Return the number of removed roles and users, and whether no roles of the space are left.
+  fn remove_roles_of_space(space_id: SpaceId, limit: u32) -> (u32, bool) {
+    let mut role_ids = Self::role_ids_by_space_id(space_id);
+    let mut removed: u32 = 0;
+
+    while let Some(&role_id) = role_ids.last() {
+      if removed >= limit {
+        break;
+      }
+
+      let mut users = <UsersByRoleId<T>>::take(role_id);
+      let removed_users = users.split_off(users.len().saturating_sub((limit - removed) as usize));
+      for user in removed_users.iter() {
+        // All roles of the space are removed, so the whole list of roles of a user goes too.
+        <RoleIdsByUserInSpace<T>>::remove(user, space_id);
+      }
+      removed = removed.saturating_add(removed_users.len() as u32);
+
+      if !users.is_empty() {
+        // This role has more users to remove in the next block.
+        <UsersByRoleId<T>>::insert(role_id, users);
+        break;
+      }
+      if removed >= limit {
+        break;
+      }
+
+      if let Some(role) = <RoleById<T>>::take(role_id) {
+        Self::move_role_to_expire_block(role_id, role.expires_at, None);
+        Utils::<T>::unreserve_content_deposit(&ContentHolder::Role(role_id));
+      }
+      role_ids.pop();
+      removed += 1;
+    }
+
+    let is_space_cleaned_up = role_ids.is_empty();
+    if is_space_cleaned_up {
+      RoleIdsBySpaceId::remove(space_id);
+    } else {
+      RoleIdsBySpaceId::insert(space_id, role_ids);
+    }
+    (removed, is_space_cleaned_up)
+  }
+
+  /// Revoke an expired role from at most `limit` users, but keep the role itself,
+  /// so that it can be granted again after its expiry is extended or cleared.
+  /// Return the number of users it was revoked from, and whether no users are left.
+  pub(crate) fn expire_role(role_id: RoleId, limit: u32) -> (u32, bool) {
+    if let Some(role) = Self::role_by_id(role_id) {
+      // Skip a role if its expiry was extended or cleared after it was added to the list.
+      let now = <system::Module<T>>::block_number();
+      if !role.expires_at.map_or(false, |expires_at| expires_at <= now) {
+        return (0, true)
+      }
+
+      let mut users = Self::users_by_role_id(role_id);
+      let is_revoked_from_all = users.len() <= limit as usize;
+      users.truncate(limit as usize);
+
+      let revoked = users.len() as u32;
+      role.revoke_from_users(users);
+
+      if is_revoked_from_all {
+        Self::deposit_event(RawEvent::RoleExpired(role_id));
+      }
+      return (revoked, is_revoked_from_all)
+    }
+    (0, true)
+  }
+}
+
 impl<T: Trait> Role<T> {
 
   pub fn new(
@@ -136,7 +359,7 @@ impl<T: Trait> Role<T> {
 
     let mut expires_at: Option<T::BlockNumber> = None;
     if let Some(ttl) = time_to_live {
-      expires_at = Some(ttl + <system::Module<T>>::block_number());
+      expires_at = Some(ttl.saturating_add(<system::Module<T>>::block_number()));
     }
 
     let new_role = Role::<T> {
@@ -186,6 +409,19 @@ impl<T: Trait> Role<T> {
   }
 }
 
+impl<T: Trait + pallet_spaces::Trait> AfterSpaceDeleted<T> for Module<T> {
+  fn after_space_deleted(_account: T::AccountId, space: &Space<T>) {
+    // This is bounded by the number of space permissions.
+    MinReputationBySpacePermission::remove_prefix(space.id);
+
+    // A space can have too many roles and users to remove them in one block,
+    // so they are removed in batches from `on_initialize`.
+    let index = Self::next_deleted_space_index();
+    DeletedSpaceIdByIndex::insert(index, space.id);
+    NextDeletedSpaceIndex::put(index.saturating_add(1));
+  }
+}
+
 impl<T: Trait> PermissionChecker for Module<T> {
   type AccountId = T::AccountId;
 
@@ -204,3 +440,20 @@ impl<T: Trait> PermissionChecker for Module<T> {
     )
   }
 }
+
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+  /// Revoke roles granted to a reaped account in all spaces.
+  fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+    let user = User::Account(who.clone());
+    let mut removed: u32 = 0;
+
+    for (_space_id, role_ids) in <RoleIdsByUserInSpace<T>>::drain_prefix(&user).take(limit as usize) {
+      for role_id in role_ids {
+        <UsersByRoleId<T>>::mutate(role_id, |users| remove_from_vec(users, user.clone()));
+      }
+      removed += 1;
+    }
+
+    removed
+  }
+}
@@ -5,20 +5,22 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     ensure,
     traits::Get,
-    dispatch::DispatchResult
+    dispatch::DispatchResult,
+    weights::Weight,
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::{One, Saturating}};
 use sp_std::{collections::btree_set::BTreeSet, iter::FromIterator, prelude::*};
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    PermissionChecker, SpaceFollowsProvider, SpaceForRolesProvider,
+    PermissionChecker, ReputationProvider, SpaceFollowsProvider, SpaceForRolesProvider,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissionSet};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content};
+use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content, ContentHolder};
 
 pub mod functions;
+pub mod migration;
 
 #[cfg(test)]
 mod mock;
@@ -41,10 +43,28 @@ pub struct Role<T: Trait> {
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
-pub struct RoleUpdate {
+pub struct RoleUpdate<BlockNumber> {
     pub disabled: Option<bool>,
     pub content: Option<Content>,
     pub permissions: Option<SpacePermissionSet>,
+    /// A new time to live counted from the current block, or `Some(None)` to make a role permanent.
+    pub time_to_live: Option<Option<BlockNumber>>,
+}
+
+// A value placed in storage that represents the current version of the Roles storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
 }
 
 /// The pallet's configuration trait.
@@ -57,6 +77,13 @@ pub trait Trait: system::Trait
 
     type MaxUsersToProcessPerDeleteRole: Get<u16>;
 
+    /// The max number of expired roles and their users to process in `on_initialize` of one block.
+    /// Expired roles that are left are revoked in the next blocks.
+    type MaxExpiredRoleEntriesPerBlock: Get<u32>;
+
+    /// The max number of roles of deleted spaces and their users to remove in `on_initialize` of one block.
+    type MaxRoleEntriesToCleanUpPerBlock: Get<u32>;
+
     type Spaces: SpaceForRolesProvider<AccountId=Self::AccountId>;
 
     type SpaceFollows: SpaceFollowsProvider<AccountId=Self::AccountId>;
@@ -64,6 +91,8 @@ pub trait Trait: system::Trait
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
     type IsContentBlocked: IsContentBlocked;
+
+    type Reputation: ReputationProvider<Self::AccountId>;
 }
 
 decl_event!(
@@ -75,6 +104,10 @@ decl_event!(
         RoleDeleted(AccountId, RoleId),
         RoleGranted(AccountId, RoleId, Vec<User<AccountId>>),
         RoleRevoked(AccountId, RoleId, Vec<User<AccountId>>),
+        /// A role expired and was revoked from all users.
+        RoleExpired(RoleId),
+        /// A minimum reputation required for a permission in a space was set or removed.
+        MinReputationUpdated(AccountId, SpaceId, SpacePermission, Option<u32>),
     }
 );
 
@@ -98,6 +131,10 @@ decl_error! {
         RoleAlreadyDisabled,
         /// Cannot enable a role that is already enabled.
         RoleAlreadyEnabled,
+        /// Account has no permission to update minimum reputation required in this space.
+        NoPermissionToUpdateMinReputation,
+        /// Account has not enough reputation to use this permission in this space.
+        NotEnoughReputation,
     }
 }
 
@@ -125,6 +162,31 @@ decl_storage! {
             hasher(blake2_128_concat) User<T::AccountId>,
             hasher(twox_64_concat) SpaceId
             => Vec<RoleId>;
+
+        /// A list of role ids that expire at this block.
+        pub RoleIdsByExpireBlock get(fn role_ids_by_expire_block):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<RoleId>;
+
+        /// Expired roles that are still to be revoked from some of their users.
+        pub ExpiredRoleIds get(fn expired_role_ids): Vec<RoleId>;
+
+        /// Deleted spaces in the order of removing their roles from `on_initialize`.
+        pub DeletedSpaceIdByIndex get(fn deleted_space_id_by_index):
+            map hasher(twox_64_concat) u64 => Option<SpaceId>;
+
+        /// An index of the next deleted space whose roles should be removed.
+        pub FirstDeletedSpaceIndex get(fn first_deleted_space_index): u64;
+
+        /// An index that will be given to the next deleted space.
+        pub NextDeletedSpaceIndex get(fn next_deleted_space_index): u64;
+
+        /// The minimum reputation an account needs to use a permission (2nd key) in a space (1st key).
+        pub MinReputationBySpacePermission get(fn min_reputation_by_space_permission): double_map
+            hasher(twox_64_concat) SpaceId,
+            hasher(twox_64_concat) SpacePermission
+            => Option<u32>;
+
+        StorageVersion build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
 }
 
@@ -134,17 +196,33 @@ decl_module! {
 
     const MaxUsersToProcessPerDeleteRole: u16 = T::MaxUsersToProcessPerDeleteRole::get();
 
+    const MaxExpiredRoleEntriesPerBlock: u32 = T::MaxExpiredRoleEntriesPerBlock::get();
+
+    const MaxRoleEntriesToCleanUpPerBlock: u32 = T::MaxRoleEntriesToCleanUpPerBlock::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
+    /// Revoke roles that expired at the previous block from all users,
+    /// and remove roles of deleted spaces in batches.
+    /// Roles cannot get a new expiry in the past, so the list of that block is complete.
+    fn on_initialize(block_number: T::BlockNumber) -> Weight {
+      Self::revoke_expired_roles(block_number.saturating_sub(One::one()))
+        .saturating_add(Self::clean_up_roles_of_deleted_spaces())
+    }
+
     /// Create a new role in a space with a list of permissions.
     /// `content` points to the off-chain content with such additional info about this role
     /// as its name, description, color, etc.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 4)]
     pub fn create_role(
       origin,
       space_id: SpaceId,
@@ -166,25 +244,32 @@ decl_module! {
 
       // TODO review strange code:
       let next_role_id = new_role.id.checked_add(1).ok_or(Error::<T>::RoleIdOverflow)?;
+
+      Utils::<T>::reserve_content_deposit(&who, ContentHolder::Role(new_role.id), &new_role.content)?;
       NextRoleId::put(next_role_id);
 
       <RoleById<T>>::insert(new_role.id, new_role.clone());
       RoleIdsBySpaceId::mutate(space_id, |role_ids| { role_ids.push(new_role.id) });
 
+      if let Some(expires_at) = new_role.expires_at {
+        <RoleIdsByExpireBlock<T>>::mutate(expires_at, |role_ids| { role_ids.push(new_role.id) });
+      }
+
       Self::deposit_event(RawEvent::RoleCreated(who, space_id, new_role.id));
       Ok(())
     }
 
     /// Update an existing role by its id.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
-    pub fn update_role(origin, role_id: RoleId, update: RoleUpdate) -> DispatchResult {
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 3)]
+    pub fn update_role(origin, role_id: RoleId, update: RoleUpdate<T::BlockNumber>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
       let has_updates =
         update.disabled.is_some() ||
         update.content.is_some() ||
-        update.permissions.is_some();
+        update.permissions.is_some() ||
+        update.time_to_live.is_some();
 
       ensure!(has_updates, Error::<T>::NoUpdatesProvided);
 
@@ -206,6 +291,7 @@ decl_module! {
           Utils::<T>::is_valid_content(content.clone())?;
           ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), role.space_id), UtilsError::<T>::ContentIsBlocked);
 
+          Utils::<T>::update_content_deposit(&who, ContentHolder::Role(role_id), &content)?;
           role.content = content;
           is_update_applied = true;
         }
@@ -222,6 +308,16 @@ decl_module! {
         }
       }
 
+      if let Some(time_to_live) = update.time_to_live {
+        let expires_at = time_to_live.map(|ttl| ttl.saturating_add(<system::Module<T>>::block_number()));
+
+        if expires_at != role.expires_at {
+          Self::move_role_to_expire_block(role_id, role.expires_at, expires_at);
+          role.expires_at = expires_at;
+          is_update_applied = true;
+        }
+      }
+
       if is_update_applied {
         role.updated = Some(WhoAndWhen::<T>::new(who.clone()));
 
@@ -255,7 +351,9 @@ decl_module! {
       }
 
       role.revoke_from_users(users);
+      Self::move_role_to_expire_block(role_id, role.expires_at, None);
 
+      Utils::<T>::unreserve_content_deposit(&ContentHolder::Role(role_id));
       <RoleById<T>>::remove(role_id);
       <UsersByRoleId<T>>::remove(role_id);
 
@@ -306,5 +404,28 @@ decl_module! {
       Self::deposit_event(RawEvent::RoleRevoked(who, role_id, users));
       Ok(())
     }
+
+    /// Require a minimum reputation from accounts that use a permission in a space,
+    /// or remove this requirement with `None`. The space owner is not affected.
+    /// Only the space owner or a user with `UpdateSpaceSettings` permission call this dispatch.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn set_min_reputation_for_permission(
+      origin,
+      space_id: SpaceId,
+      permission: SpacePermission,
+      min_reputation: Option<u32>
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::ensure_min_reputation_manager(who.clone(), space_id)?;
+
+      match min_reputation {
+        Some(reputation) => MinReputationBySpacePermission::insert(space_id, &permission, reputation),
+        None => MinReputationBySpacePermission::remove(space_id, &permission),
+      }
+
+      Self::deposit_event(RawEvent::MinReputationUpdated(who, space_id, permission, min_reputation));
+      Ok(())
+    }
   }
 }
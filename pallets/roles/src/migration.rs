@@ -0,0 +1,46 @@
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    weight
+}
+
+/// Add roles with an expiry to the lists of roles that expire at a block,
+/// so they are revoked from users in `on_initialize`.
+/// Roles that have already expired are revoked in the next blocks.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    let now = <system::Module<T>>::block_number();
+
+    let mut roles_count: Weight = 0;
+    let mut expiring_count: Weight = 0;
+    let mut expired_role_ids = ExpiredRoleIds::get();
+
+    for (role_id, role) in <RoleById<T>>::iter() {
+        roles_count = roles_count.saturating_add(1);
+
+        if let Some(expires_at) = role.expires_at {
+            if expires_at < now {
+                expired_role_ids.push(role_id);
+            } else {
+                <RoleIdsByExpireBlock<T>>::mutate(expires_at, |role_ids| role_ids.push(role_id));
+                expiring_count = expiring_count.saturating_add(1);
+            }
+        }
+    }
+
+    ExpiredRoleIds::put(expired_role_ids);
+    StorageVersion::put(Releases::V2_0_0);
+
+    T::DbWeight::get().reads_writes(
+        roles_count.saturating_add(expiring_count).saturating_add(2),
+        expiring_count.saturating_add(2),
+    )
+}
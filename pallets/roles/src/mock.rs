@@ -94,6 +94,8 @@ impl pallet_balances::Trait for Test {
 parameter_types! {
     pub const MinHandleLen: u32 = 5;
     pub const MaxHandleLen: u32 = 50;
+    pub const MaxRawContentLen: u32 = 1024;
+    pub const RawContentDepositPerByte: u64 = 0;
 }
 
 impl pallet_utils::Trait for Test {
@@ -101,6 +103,8 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type MaxRawContentLen = MaxRawContentLen;
+    type RawContentDepositPerByte = RawContentDepositPerByte;
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -111,18 +115,23 @@ impl pallet_permissions::Trait for Test {
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 20;
+  pub const MaxExpiredRoleEntriesPerBlock: u32 = 3;
+  pub const MaxRoleEntriesToCleanUpPerBlock: u32 = 3;
 }
 
 impl Trait for Test {
     type Event = ();
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxExpiredRoleEntriesPerBlock = MaxExpiredRoleEntriesPerBlock;
+    type MaxRoleEntriesToCleanUpPerBlock = MaxRoleEntriesToCleanUpPerBlock;
     type Spaces = Roles;
     type SpaceFollows = Roles;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type Reputation = ();
 }
 
-type System = system::Module<Test>;
+pub(crate) type System = system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
 pub(crate) type Roles = Module<Test>;
 
@@ -136,7 +145,7 @@ impl<T: Trait> SpaceForRolesProvider for Module<T> {
     // Currently, we have a list of valid space id's to check
     fn get_space(id: SpaceId) -> Result<SpaceForRoles<Self::AccountId>, DispatchError> {
         if self::valid_space_ids().contains(&id) {
-            return Ok(SpaceForRoles { owner: ACCOUNT1, permissions: None })
+            return Ok(SpaceForRoles { owner: ACCOUNT1, permissions: None, inherited_permissions: Vec::new() })
         }
 
         Err("SpaceNotFound".into())
@@ -244,11 +253,21 @@ pub(crate) fn permission_set_empty() -> Vec<SpacePermission> {
     vec![]
 }
 
-pub(crate) fn role_update(disabled: Option<bool>, content: Option<Content>, permissions: Option<BTreeSet<SpacePermission>>) -> RoleUpdate {
+pub(crate) fn role_update(disabled: Option<bool>, content: Option<Content>, permissions: Option<BTreeSet<SpacePermission>>) -> RoleUpdate<BlockNumber> {
     RoleUpdate {
         disabled,
         content,
         permissions,
+        time_to_live: None,
+    }
+}
+
+pub(crate) fn role_update_time_to_live(time_to_live: Option<BlockNumber>) -> RoleUpdate<BlockNumber> {
+    RoleUpdate {
+        disabled: None,
+        content: None,
+        permissions: None,
+        time_to_live: Some(time_to_live),
     }
 }
 
@@ -280,7 +299,7 @@ pub(crate) fn _update_default_role() -> DispatchResult {
 pub(crate) fn _update_role(
     origin: Option<Origin>,
     role_id: Option<RoleId>,
-    update: Option<RoleUpdate>
+    update: Option<RoleUpdate<BlockNumber>>
 ) -> DispatchResult {
     Roles::update_role(
         origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
@@ -3,6 +3,7 @@ use crate::*;
 
 use frame_support::{
     assert_ok, assert_noop,
+    traits::OnInitialize,
 };
 use pallet_utils::{Error as UtilsError};
 
@@ -590,3 +591,120 @@ fn delete_role_should_fail_with_a_few_roles_no_permission() {
         );
     });
 }
+
+#[test]
+fn on_initialize_should_revoke_expired_role_from_users() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+
+        assert_eq!(Roles::role_ids_by_expire_block(11), vec![ROLE1]);
+
+        // Roles that expired at the previous block are revoked
+        System::set_block_number(12);
+        Roles::on_initialize(12);
+
+        // Check whether the role was revoked, but not deleted
+        assert!(Roles::role_by_id(ROLE1).is_some());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
+        assert!(Roles::role_ids_by_expire_block(11).is_empty());
+    });
+}
+
+#[test]
+fn update_role_should_extend_role_expiry() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+
+        assert_ok!(_update_role(None, None, Some(self::role_update_time_to_live(Some(20)))));
+
+        assert_eq!(Roles::role_by_id(ROLE1).unwrap().expires_at, Some(21));
+        assert!(Roles::role_ids_by_expire_block(11).is_empty());
+        assert_eq!(Roles::role_ids_by_expire_block(21), vec![ROLE1]);
+
+        // The role should not expire at its old expiry block
+        System::set_block_number(12);
+        Roles::on_initialize(12);
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![User::Account(ACCOUNT2)]);
+    });
+}
+
+#[test]
+fn on_initialize_should_revoke_expired_roles_in_batches() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+        assert_ok!(_grant_role(None, None, Some(vec![
+            User::Account(ACCOUNT2),
+            User::Account(ACCOUNT3),
+            User::Space(SPACE2),
+        ])));
+
+        // Only `MaxExpiredRoleEntriesPerBlock` (3) roles and users are processed per block:
+        // the role itself and two of its users.
+        System::set_block_number(12);
+        Roles::on_initialize(12);
+        assert_eq!(Roles::users_by_role_id(ROLE1).len(), 1);
+        assert_eq!(Roles::expired_role_ids(), vec![ROLE1]);
+
+        System::set_block_number(13);
+        Roles::on_initialize(13);
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::expired_role_ids().is_empty());
+        assert!(Roles::role_by_id(ROLE1).is_some());
+    });
+}
+
+#[test]
+fn update_role_should_not_overflow_role_expiry() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_ok!(_update_role(None, None, Some(self::role_update_time_to_live(Some(BlockNumber::max_value())))));
+        assert_eq!(Roles::role_by_id(ROLE1).unwrap().expires_at, Some(BlockNumber::max_value()));
+    });
+}
+
+#[test]
+fn update_role_should_clear_role_expiry() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+
+        assert_ok!(_update_role(None, None, Some(self::role_update_time_to_live(None))));
+
+        assert!(Roles::role_by_id(ROLE1).unwrap().expires_at.is_none());
+        assert!(Roles::role_ids_by_expire_block(11).is_empty());
+    });
+}
+
+#[test]
+fn delete_role_should_remove_role_from_expire_block() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+        assert_ok!(_delete_default_role());
+
+        assert!(Roles::role_ids_by_expire_block(11).is_empty());
+    });
+}
+
+#[test]
+fn migration_should_add_roles_to_expire_blocks() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, None)); // RoleId 1 expires at block 11
+        assert_ok!(_create_role(None, None, Some(Some(1)), None, None)); // RoleId 2 expires at block 2
+
+        // Roles were not added to expire blocks before the migration
+        RoleIdsByExpireBlock::<Test>::remove(11);
+        RoleIdsByExpireBlock::<Test>::remove(2);
+
+        System::set_block_number(5);
+        migration::migrate::<Test>();
+
+        assert_eq!(Roles::role_ids_by_expire_block(11), vec![ROLE1]);
+
+        // An already expired role is revoked in the next blocks
+        assert!(Roles::role_ids_by_expire_block(2).is_empty());
+        assert_eq!(Roles::expired_role_ids(), vec![ROLE2]);
+    });
+}
@@ -0,0 +1,21 @@
+//! Runtime API definition for the scores pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_posts::PostId;
+use pallet_scores::ScoreContribution;
+
+sp_api::decl_runtime_apis! {
+    pub trait ScoresApi<AccountId> where
+        AccountId: Codec,
+    {
+        /// Get score diffs that actors made to a post.
+        fn get_post_score_contributions(post_id: PostId) -> Vec<ScoreContribution<AccountId>>;
+
+        /// Get reputation diffs that actors made to an account.
+        fn get_account_reputation_contributions(account: AccountId) -> Vec<ScoreContribution<AccountId>>;
+    }
+}
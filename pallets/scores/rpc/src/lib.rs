@@ -0,0 +1,88 @@
+//! Node-specific RPC methods for interaction with the scores pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use pallet_posts::PostId;
+use pallet_scores::ScoreContribution;
+pub use pallet_scores_rpc_runtime_api::ScoresApi as ScoresRuntimeApi;
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Scores RPC methods.
+#[rpc]
+pub trait ScoresApi<BlockHash, AccountId> {
+    /// Get score diffs that actors made to a post.
+    #[rpc(name = "scores_getPostScoreContributions")]
+    fn get_post_score_contributions(
+        &self,
+        post_id: PostId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ScoreContribution<AccountId>>>;
+
+    /// Get reputation diffs that actors made to an account.
+    #[rpc(name = "scores_getAccountReputationContributions")]
+    fn get_account_reputation_contributions(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ScoreContribution<AccountId>>>;
+}
+
+/// An implementation of scores specific RPC methods.
+pub struct Scores<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Scores<C, B> {
+    /// Create new `Scores` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+impl<C, Block, AccountId> ScoresApi<<Block as BlockT>::Hash, AccountId> for Scores<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: ScoresRuntimeApi<Block, AccountId>,
+    AccountId: Codec,
+{
+    fn get_post_score_contributions(
+        &self,
+        post_id: PostId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ScoreContribution<AccountId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_post_score_contributions(&at, post_id).map_err(runtime_error_into_rpc_err)
+    }
+
+    fn get_account_reputation_contributions(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ScoreContribution<AccountId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_account_reputation_contributions(&at, account).map_err(runtime_error_into_rpc_err)
+    }
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Runtime error".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
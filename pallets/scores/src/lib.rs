@@ -3,21 +3,32 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult, ensure, traits::Get,
+    dispatch::DispatchResult, ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Weight,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    RuntimeDebug, SaturatedConversion,
 };
-use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system};
 
-use pallet_posts::{PostScores, Post, PostById, PostExtension, PostId};
+use df_traits::{CleanUpReapedAccount, ReputationProvider};
+use pallet_posts::{Module as Posts, PostScores, Post, PostById, PostExtension, PostId, AfterPostDeleted};
 use pallet_profile_follows::{BeforeAccountFollowed, BeforeAccountUnfollowed};
-use pallet_profiles::{Module as Profiles, SocialAccountById};
+use pallet_profiles::{Module as Profiles, SocialAccount, SocialAccountById};
 use pallet_reactions::{PostReactionScores, ReactionKind};
 use pallet_space_follows::{BeforeSpaceFollowed, BeforeSpaceUnfollowed};
 use pallet_spaces::{Space, SpaceById};
 use pallet_utils::log_2;
 
-#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub mod migration;
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ScoringAction {
     UpvotePost,
     DownvotePost,
@@ -36,6 +47,36 @@ impl Default for ScoringAction {
     }
 }
 
+/// A score or reputation diff that an actor made to a post or an account with an action.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ScoreContribution<AccountId> {
+    pub actor: AccountId,
+    pub action: ScoringAction,
+    pub diff: i16,
+}
+
+/// An index of an account in the queue of accounts whose reputation decays over time.
+pub type DecayIndex = u64;
+
+// A value placed in storage that represents the current version of the Scores storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+    V4_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
@@ -49,7 +90,7 @@ pub trait Trait: system::Trait
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
-    // Weights of the social actions
+    // Default weights of the social actions, used unless a weight is set in storage
     type FollowSpaceActionWeight: Get<i16>;
     type FollowAccountActionWeight: Get<i16>;
 
@@ -61,6 +102,24 @@ pub trait Trait: system::Trait
     type ShareCommentActionWeight: Get<i16>;
     type UpvoteCommentActionWeight: Get<i16>;
     type DownvoteCommentActionWeight: Get<i16>;
+
+    /// The number of blocks after which account reputation decays once. Zero disables decay.
+    type ReputationDecayPeriod: Get<Self::BlockNumber>;
+
+    /// The percent of reputation above `ReputationDecayFloor` that is lost every decay period.
+    type ReputationDecayPercent: Get<u8>;
+
+    /// Reputation never decays below this value.
+    type ReputationDecayFloor: Get<u32>;
+
+    /// The max number of accounts whose reputation is decayed in `on_initialize` of one block.
+    type MaxDecayedAccountsPerBlock: Get<u32>;
+
+    /// The max number of scores of deleted posts to remove in `on_initialize` of one block.
+    type MaxPostScoresToCleanUpPerBlock: Get<u32>;
+
+    /// The origin which may update weights of scoring actions.
+    type UpdateWeightsOrigin: EnsureOrigin<Self::Origin>;
 }
 
 decl_error! {
@@ -84,6 +143,60 @@ decl_storage! {
 
         pub PostScoreByAccount get(fn post_score_by_account):
             map hasher(blake2_128_concat) (/* actor */ T::AccountId, /* subject */ PostId, ScoringAction) => Option<i16>;
+
+        /// Whether an actor changed reputation of an account (1st key) with an action.
+        /// The 2nd key is a pair of the actor and the action.
+        pub ReputationActorsByAccount:
+            double_map hasher(blake2_128_concat) T::AccountId,
+                hasher(blake2_128_concat) (T::AccountId, ScoringAction)
+            => bool;
+
+        /// Whether an actor changed a score of a post (1st key) with an action.
+        /// The 2nd key is a pair of the actor and the action.
+        pub ScoreActorsByPostId:
+            double_map hasher(twox_64_concat) PostId, hasher(blake2_128_concat) (T::AccountId, ScoringAction)
+            => bool;
+
+        /// A block from which decay periods of account reputation are counted.
+        pub ReputationDecayedAt get(fn reputation_decayed_at):
+            map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// Accounts whose reputation is decayed in batches from `on_initialize`.
+        pub DecayingAccountByIndex get(fn decaying_account_by_index):
+            map hasher(twox_64_concat) DecayIndex => Option<T::AccountId>;
+
+        pub DecayingAccountsCount get(fn decaying_accounts_count): DecayIndex;
+
+        /// An index of an account in the queue of decaying accounts.
+        pub DecayIndexByAccount get(fn decay_index_by_account):
+            map hasher(blake2_128_concat) T::AccountId => Option<DecayIndex>;
+
+        /// An index of the account to be decayed first in the next batch.
+        pub NextDecayIndex get(fn next_decay_index): DecayIndex;
+
+        /// A weight of a scoring action that overrides its default weight from the runtime.
+        pub WeightByScoringAction get(fn weight_by_scoring_action):
+            map hasher(twox_64_concat) ScoringAction => Option<i16>;
+
+        /// Deleted posts in the order of removing their scores from `on_initialize`.
+        pub DeletedPostIdByIndex get(fn deleted_post_id_by_index):
+            map hasher(twox_64_concat) u64 => Option<PostId>;
+
+        /// An index of the next deleted post whose scores should be removed.
+        pub FirstDeletedPostIndex get(fn first_deleted_post_index): u64;
+
+        /// An index that will be given to the next deleted post.
+        pub NextDeletedPostIndex get(fn next_deleted_post_index): u64;
+
+        StorageVersion build(|_: &GenesisConfig| Releases::V4_0_0): Releases;
+    }
+    add_extra_genesis {
+        config(action_weights): Vec<(ScoringAction, i16)>;
+        build(|config| {
+            for (action, weight) in config.action_weights.iter() {
+                WeightByScoringAction::insert(action, weight);
+            }
+        });
     }
 }
 
@@ -92,6 +205,10 @@ decl_event!(
         <T as system::Trait>::AccountId,
     {
         AccountReputationChanged(AccountId, ScoringAction, u32),
+        /// Reputation of an account decayed to a new value.
+        AccountReputationDecayed(AccountId, u32),
+        /// A weight of a scoring action was set, or reset to its default with `None`.
+        ScoringActionWeightUpdated(ScoringAction, Option<i16>),
     }
 );
 
@@ -99,7 +216,7 @@ decl_event!(
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
-        /// Weights of the related social account actions
+        /// Default weights of the related social account actions
         const FollowSpaceActionWeight: i16 = T::FollowSpaceActionWeight::get();
         const FollowAccountActionWeight: i16 = T::FollowAccountActionWeight::get();
         const UpvotePostActionWeight: i16 = T::UpvotePostActionWeight::get();
@@ -110,30 +227,68 @@ decl_module! {
         const DownvoteCommentActionWeight: i16 = T::DownvoteCommentActionWeight::get();
         const ShareCommentActionWeight: i16 = T::ShareCommentActionWeight::get();
 
+        /// Parameters of account reputation decay
+        const ReputationDecayPeriod: T::BlockNumber = T::ReputationDecayPeriod::get();
+        const ReputationDecayPercent: u8 = T::ReputationDecayPercent::get();
+        const ReputationDecayFloor: u32 = T::ReputationDecayFloor::get();
+        const MaxDecayedAccountsPerBlock: u32 = T::MaxDecayedAccountsPerBlock::get();
+
+        const MaxPostScoresToCleanUpPerBlock: u32 = T::MaxPostScoresToCleanUpPerBlock::get();
+
         // Initializing errors
         type Error = Error<T>;
 
         // Initializing events
         fn deposit_event() = default;
+
+        fn on_runtime_upgrade() -> Weight {
+            migration::migrate::<T>()
+        }
+
+        /// Decay reputation of the next batch of accounts, so that inactive accounts lose it too,
+        /// and remove scores of deleted posts in batches.
+        fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+            Self::decay_next_accounts()
+                .saturating_add(Self::clean_up_scores_of_deleted_posts())
+        }
+
+        /// Set a weight of a scoring action, or reset it to the default weight with `None`.
+        /// New weights apply only to actions done after this update.
+        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        pub fn set_scoring_action_weight(origin, action: ScoringAction, weight: Option<i16>) -> DispatchResult {
+            T::UpdateWeightsOrigin::ensure_origin(origin)?;
+
+            match weight {
+                Some(weight) => WeightByScoringAction::insert(action, weight),
+                None => WeightByScoringAction::remove(action),
+            }
+
+            Self::deposit_event(RawEvent::ScoringActionWeightUpdated(action, weight));
+            Ok(())
+        }
     }
 }
 
 impl<T: Trait> Module<T> {
 
+    /// Get a scoring action of a reaction on a post. Emoji reactions are not scored.
     pub fn scoring_action_by_post_extension(
         extension: PostExtension,
         reaction_kind: ReactionKind,
-    ) -> ScoringAction {
-        match extension {
+    ) -> Option<ScoringAction> {
+        let action = match extension {
             PostExtension::RegularPost | PostExtension::SharedPost(_) => match reaction_kind {
                 ReactionKind::Upvote => ScoringAction::UpvotePost,
                 ReactionKind::Downvote => ScoringAction::DownvotePost,
+                ReactionKind::Emoji(_) => return None,
             },
             PostExtension::Comment(_) => match reaction_kind {
                 ReactionKind::Upvote => ScoringAction::UpvoteComment,
                 ReactionKind::Downvote => ScoringAction::DownvoteComment,
+                ReactionKind::Emoji(_) => return None,
             },
-        }
+        };
+        Some(action)
     }
 
     fn change_post_score_with_reaction(
@@ -147,8 +302,10 @@ impl<T: Trait> Module<T> {
             return Ok(())
         }
 
-        let action = Self::scoring_action_by_post_extension(post.extension, reaction_kind);
-        Self::change_post_score(actor, post, action)
+        match Self::scoring_action_by_post_extension(post.extension, reaction_kind) {
+            Some(action) => Self::change_post_score(actor, post, action),
+            None => Ok(()),
+        }
     }
 
     pub fn change_post_score(
@@ -195,7 +352,7 @@ impl<T: Trait> Module<T> {
             post.change_score(-score_diff);
             space.change_score(-score_diff);
             Self::change_social_account_reputation(post.owner.clone(), account.clone(), -reputation_diff, action)?;
-            <PostScoreByAccount<T>>::remove((account, post_id, action));
+            Self::remove_post_score(account, post_id, action);
         } else {
             match action {
                 ScoringAction::UpvotePost => {
@@ -212,11 +369,12 @@ impl<T: Trait> Module<T> {
                 }
                 _ => (),
             }
-            let score_diff = Self::score_diff_for_action(social_account.reputation, action);
+            let reputation = Self::decayed_reputation(&account, social_account.reputation);
+            let score_diff = Self::score_diff_for_action(reputation, action);
             post.change_score(score_diff);
             space.change_score(score_diff);
             Self::change_social_account_reputation(post.owner.clone(), account.clone(), score_diff, action)?;
-            <PostScoreByAccount<T>>::insert((account, post_id, action), score_diff);
+            Self::insert_post_score(account, post_id, action, score_diff);
         }
 
         <PostById<T>>::insert(post_id, post.clone());
@@ -254,7 +412,7 @@ impl<T: Trait> Module<T> {
             // Revert this score diff:
             comment.change_score(-score_diff);
             Self::change_social_account_reputation(comment.owner.clone(), account.clone(), -reputation_diff, action)?;
-            <PostScoreByAccount<T>>::remove((account, comment_id, action));
+            Self::remove_post_score(account, comment_id, action);
         } else {
             match action {
                 ScoringAction::UpvoteComment => {
@@ -273,10 +431,11 @@ impl<T: Trait> Module<T> {
                 }
                 _ => (),
             }
-            let score_diff = Self::score_diff_for_action(social_account.reputation, action);
+            let reputation = Self::decayed_reputation(&account, social_account.reputation);
+            let score_diff = Self::score_diff_for_action(reputation, action);
             comment.change_score(score_diff);
             Self::change_social_account_reputation(comment.owner.clone(), account.clone(), score_diff, action)?;
-            <PostScoreByAccount<T>>::insert((account, comment_id, action), score_diff);
+            Self::insert_post_score(account, comment_id, action, score_diff);
         }
         <PostById<T>>::insert(comment_id, comment.clone());
 
@@ -295,6 +454,7 @@ impl<T: Trait> Module<T> {
 
         // TODO seems like we can pass a &mut social account as an arg to this func
         let mut social_account = Profiles::get_or_new_social_account(account.clone());
+        Self::apply_reputation_decay(&account, &mut social_account);
 
         if social_account.reputation as i64 + score_diff as i64 <= 1 {
             social_account.reputation = 1;
@@ -304,9 +464,11 @@ impl<T: Trait> Module<T> {
         social_account.change_reputation(score_diff);
 
         if Self::account_reputation_diff_by_account((scorer.clone(), account.clone(), action)).is_some() {
-            <AccountReputationDiffByAccount<T>>::remove((scorer, account.clone(), action));
+            <AccountReputationDiffByAccount<T>>::remove((scorer.clone(), account.clone(), action));
+            <ReputationActorsByAccount<T>>::remove(&account, (scorer, action));
         } else {
-            <AccountReputationDiffByAccount<T>>::insert((scorer, account.clone(), action), score_diff);
+            <AccountReputationDiffByAccount<T>>::insert((scorer.clone(), account.clone(), action), score_diff);
+            <ReputationActorsByAccount<T>>::insert(&account, (scorer, action), true);
         }
 
         <SocialAccountById<T>>::insert(account.clone(), social_account.clone());
@@ -316,8 +478,45 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn insert_post_score(account: T::AccountId, post_id: PostId, action: ScoringAction, score_diff: i16) {
+        <PostScoreByAccount<T>>::insert((account.clone(), post_id, action), score_diff);
+        <ScoreActorsByPostId<T>>::insert(post_id, (account, action), true);
+    }
+
+    fn remove_post_score(account: T::AccountId, post_id: PostId, action: ScoringAction) {
+        <PostScoreByAccount<T>>::remove((account.clone(), post_id, action));
+        <ScoreActorsByPostId<T>>::remove(post_id, (account, action));
+    }
+
+    /// Get score diffs that actors made to a post, sorted by actor and action.
+    pub fn post_score_contributions(post_id: PostId) -> Vec<ScoreContribution<T::AccountId>> {
+        let mut contributions: Vec<_> = <ScoreActorsByPostId<T>>::iter_prefix(post_id)
+            .filter_map(|((actor, action), _)| {
+                Self::post_score_by_account((actor.clone(), post_id, action))
+                    .map(|diff| ScoreContribution { actor, action, diff })
+            })
+            .collect();
+
+        contributions.sort_by(|a, b| (&a.actor, a.action).cmp(&(&b.actor, b.action)));
+        contributions
+    }
+
+    /// Get reputation diffs that actors made to an account, sorted by actor and action.
+    /// Reputation lost to decay is not listed here.
+    pub fn account_reputation_contributions(account: T::AccountId) -> Vec<ScoreContribution<T::AccountId>> {
+        let mut contributions: Vec<_> = <ReputationActorsByAccount<T>>::iter_prefix(&account)
+            .filter_map(|((actor, action), _)| {
+                Self::account_reputation_diff_by_account((actor.clone(), account.clone(), action))
+                    .map(|diff| ScoreContribution { actor, action, diff })
+            })
+            .collect();
+
+        contributions.sort_by(|a, b| (&a.actor, a.action).cmp(&(&b.actor, b.action)));
+        contributions
+    }
+
     pub fn score_diff_for_action(reputation: u32, action: ScoringAction) -> i16 {
-        Self::smooth_reputation(reputation) as i16 * Self::weight_of_scoring_action(action)
+        (Self::smooth_reputation(reputation) as i16).saturating_mul(Self::weight_of_scoring_action(action))
     }
 
     fn smooth_reputation(reputation: u32) -> u8 {
@@ -330,7 +529,188 @@ impl<T: Trait> Module<T> {
         })
     }
 
-    fn weight_of_scoring_action(action: ScoringAction) -> i16 {
+    /// Get reputation of an account with decay for all periods passed since it decayed last time.
+    /// Nothing is written to storage, so it can be used for accounts that only act.
+    pub fn decayed_reputation(account: &T::AccountId, reputation: u32) -> u32 {
+        let (periods, _) = Self::decay_periods_passed(account);
+        Self::decay_reputation(reputation, periods)
+    }
+
+    /// Decay reputation of a social account for all periods passed since it decayed last time.
+    /// An account that was not decayed yet starts its decay clock at the current block.
+    pub fn apply_reputation_decay(account: &T::AccountId, social_account: &mut SocialAccount<T>) {
+        let period = T::ReputationDecayPeriod::get();
+        if period.is_zero() {
+            return
+        }
+
+        match Self::decay_periods_passed(account) {
+            (_, None) => {
+                ReputationDecayedAt::<T>::insert(account, <system::Module<T>>::block_number());
+                Self::add_decaying_account(account.clone());
+            },
+            (0, _) => (),
+            (periods, Some(decayed_at)) => {
+                let new_decayed_at = decayed_at.saturating_add(period.saturating_mul(periods.into()));
+                ReputationDecayedAt::<T>::insert(account, new_decayed_at);
+
+                let reputation = Self::decay_reputation(social_account.reputation, periods);
+                if reputation != social_account.reputation {
+                    social_account.reputation = reputation;
+                    Self::deposit_event(RawEvent::AccountReputationDecayed(account.clone(), reputation));
+                }
+            },
+        }
+    }
+
+    /// Get the number of whole decay periods passed since reputation of an account
+    /// decayed last time, together with the block it decayed at.
+    fn decay_periods_passed(account: &T::AccountId) -> (u32, Option<T::BlockNumber>) {
+        let period = T::ReputationDecayPeriod::get();
+        let decayed_at = Self::reputation_decayed_at(account);
+
+        let periods = match decayed_at {
+            Some(decayed_at) if !period.is_zero() => {
+                let now = <system::Module<T>>::block_number();
+                (now.saturating_sub(decayed_at) / period).saturated_into::<u32>()
+            },
+            _ => 0,
+        };
+
+        (periods, decayed_at)
+    }
+
+    /// Decay reputation toward the floor by `ReputationDecayPercent` of the part above the floor,
+    /// once per period. Reputation that is already at or below the floor is not changed.
+    pub fn decay_reputation(mut reputation: u32, periods: u32) -> u32 {
+        let floor = T::ReputationDecayFloor::get();
+        let percent = T::ReputationDecayPercent::get().min(100) as u64;
+
+        if percent == 0 {
+            return reputation
+        }
+
+        // Every period removes at least 1 point above the floor, so this loop is short.
+        for _ in 0..periods {
+            if reputation <= floor {
+                break
+            }
+            let above_floor = (reputation - floor) as u64;
+            reputation = floor + (above_floor * (100 - percent) / 100) as u32;
+        }
+
+        reputation
+    }
+
+    pub(crate) fn add_decaying_account(account: T::AccountId) {
+        let index = Self::decaying_accounts_count();
+        DecayIndexByAccount::<T>::insert(&account, index);
+        DecayingAccountByIndex::<T>::insert(index, account);
+        DecayingAccountsCount::put(index.saturating_add(1));
+    }
+
+    /// Remove an account from the queue of decaying accounts
+    /// and move the last account of the queue to its place, so the queue has no gaps.
+    pub(crate) fn remove_decaying_account(account: &T::AccountId) {
+        let index = match DecayIndexByAccount::<T>::take(account) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let last_index = Self::decaying_accounts_count().saturating_sub(1);
+        if index != last_index {
+            if let Some(last_account) = Self::decaying_account_by_index(last_index) {
+                DecayIndexByAccount::<T>::insert(&last_account, index);
+                DecayingAccountByIndex::<T>::insert(index, last_account);
+            }
+        }
+
+        DecayingAccountByIndex::<T>::remove(last_index);
+        DecayingAccountsCount::put(last_index);
+    }
+
+    /// Decay reputation of at most `MaxDecayedAccountsPerBlock` accounts,
+    /// starting from where the previous batch stopped and wrapping around the queue.
+    fn decay_next_accounts() -> Weight {
+        let count = Self::decaying_accounts_count();
+        if T::ReputationDecayPeriod::get().is_zero() || count == 0 {
+            return T::DbWeight::get().reads(1)
+        }
+
+        let batch_size = count.min(T::MaxDecayedAccountsPerBlock::get() as DecayIndex);
+        let mut index = Self::next_decay_index() % count;
+        let mut writes: Weight = 0;
+
+        for _ in 0..batch_size {
+            if let Some(account) = Self::decaying_account_by_index(index) {
+                if let Some(mut social_account) = Profiles::<T>::social_account_by_id(&account) {
+                    let reputation = social_account.reputation;
+                    Self::apply_reputation_decay(&account, &mut social_account);
+                    if social_account.reputation != reputation {
+                        <SocialAccountById<T>>::insert(account, social_account);
+                        writes = writes.saturating_add(1);
+                    }
+                }
+            }
+            index = (index + 1) % count;
+        }
+
+        NextDecayIndex::put(index);
+
+        // Every account is read from the queue, its social account and decay clock are read
+        // and the clock is updated.
+        T::DbWeight::get().reads_writes(
+            batch_size.saturating_mul(3).saturating_add(2),
+            batch_size.saturating_add(writes).saturating_add(1),
+        )
+    }
+
+    /// Remove at most `MaxPostScoresToCleanUpPerBlock` scores of deleted posts,
+    /// continuing from the post where the previous block stopped.
+    fn clean_up_scores_of_deleted_posts() -> Weight {
+        let first_index = Self::first_deleted_post_index();
+        let next_index = Self::next_deleted_post_index();
+        if first_index == next_index {
+            return T::DbWeight::get().reads(2)
+        }
+
+        let limit = T::MaxPostScoresToCleanUpPerBlock::get();
+        let mut removed: u32 = 0;
+        let mut index = first_index;
+
+        while index < next_index && removed < limit {
+            if let Some(post_id) = Self::deleted_post_id_by_index(index) {
+                let remaining = limit - removed;
+                let mut removed_of_post: u32 = 0;
+                for ((actor, action), _) in <ScoreActorsByPostId<T>>::drain_prefix(post_id).take(remaining as usize) {
+                    <PostScoreByAccount<T>>::remove((actor, post_id, action));
+                    removed_of_post += 1;
+                }
+                removed = removed.saturating_add(removed_of_post);
+
+                if removed_of_post >= remaining {
+                    // This post may have more scores to remove in the next block.
+                    break;
+                }
+            }
+            DeletedPostIdByIndex::remove(index);
+            index = index.saturating_add(1);
+        }
+
+        FirstDeletedPostIndex::put(index);
+
+        let processed_posts = index.saturating_sub(first_index).saturating_add(1);
+        T::DbWeight::get().reads_writes(processed_posts.saturating_add(2), processed_posts)
+            // Every score is drained from the index and removed.
+            .saturating_add(T::DbWeight::get().reads_writes(1, 2).saturating_mul(removed as Weight))
+    }
+
+    /// Get a weight of a scoring action from storage, or its default weight from the runtime.
+    pub fn weight_of_scoring_action(action: ScoringAction) -> i16 {
+        if let Some(weight) = Self::weight_by_scoring_action(action) {
+            return weight
+        }
+
         use ScoringAction::*;
         match action {
             UpvotePost => T::UpvotePostActionWeight::get(),
@@ -346,13 +726,22 @@ impl<T: Trait> Module<T> {
     }
 }
 
+/// Reputation with decay applied, so that inactive accounts lose permissions gated by reputation too.
+impl<T: Trait> ReputationProvider<T::AccountId> for Module<T> {
+    fn reputation_of(account: &T::AccountId) -> u32 {
+        let social_account = Profiles::<T>::get_or_new_social_account(account.clone());
+        Self::decayed_reputation(account, social_account.reputation)
+    }
+}
+
 impl<T: Trait> BeforeSpaceFollowed<T> for Module<T> {
     fn before_space_followed(follower: T::AccountId, follower_reputation: u32, space: &mut Space<T>) -> DispatchResult {
         // Change a space score only if the follower is NOT a space owner.
         if !space.is_owner(&follower) {
             let space_owner = space.owner.clone();
             let action = ScoringAction::FollowSpace;
-            let score_diff = Self::score_diff_for_action(follower_reputation, action);
+            let reputation = Self::decayed_reputation(&follower, follower_reputation);
+            let score_diff = Self::score_diff_for_action(reputation, action);
             space.change_score(score_diff);
             return Self::change_social_account_reputation(
                 space_owner, follower, score_diff, action)
@@ -383,7 +772,8 @@ impl<T: Trait> BeforeSpaceUnfollowed<T> for Module<T> {
 impl<T: Trait> BeforeAccountFollowed<T> for Module<T> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult {
         let action = ScoringAction::FollowAccount;
-        let score_diff = Self::score_diff_for_action(follower_reputation, action);
+        let reputation = Self::decayed_reputation(&follower, follower_reputation);
+        let score_diff = Self::score_diff_for_action(reputation, action);
         Self::change_social_account_reputation(following, follower, score_diff, action)
     }
 }
@@ -425,6 +815,46 @@ impl<T: Trait> PostScores<T> for Module<T> {
     }
 }
 
+impl<T: Trait> AfterPostDeleted<T> for Module<T> {
+    fn after_post_deleted(_account: T::AccountId, post: &Post<T>) {
+        // Subtract the weight of CreateComment from the root post and its space,
+        // if the root post was not deleted together with this comment.
+        if let PostExtension::Comment(comment_ext) = post.extension {
+            let action = ScoringAction::CreateComment;
+            let comment_author = post.owner.clone();
+
+            if let Some(root_post) = &mut Posts::<T>::post_by_id(comment_ext.root_post_id) {
+                if Self::post_score_by_account((comment_author.clone(), root_post.id, action)).is_some() {
+                    let _ = Self::change_root_post_score(comment_author, root_post, action);
+                }
+            }
+        }
+
+        // Queue removal of all scores given to the deleted post: by reactions, comments and shares.
+        // There can be too many of them for one block, so they are removed from `on_initialize`.
+        // Reputation of the post owner stays unchanged.
+        let index = Self::next_deleted_post_index();
+        DeletedPostIdByIndex::insert(index, post.id);
+        NextDeletedPostIndex::put(index.saturating_add(1));
+    }
+}
+
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+    /// Remove a reaped account from the decay queue together with its decay clock,
+    /// and remove reputation diffs that others made to it.
+    fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+        ReputationDecayedAt::<T>::remove(who);
+        Self::remove_decaying_account(who);
+
+        let mut removed: u32 = 0;
+        for ((actor, action), _) in <ReputationActorsByAccount<T>>::drain_prefix(who).take(limit as usize) {
+            <AccountReputationDiffByAccount<T>>::remove((actor, who.clone(), action));
+            removed += 1;
+        }
+        removed
+    }
+}
+
 impl<T: Trait> PostReactionScores<T> for Module<T> {
     fn score_post_on_reaction(
         actor: T::AccountId,
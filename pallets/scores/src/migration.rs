@@ -0,0 +1,104 @@
+use frame_support::storage::{IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    if StorageVersion::get() == Releases::V2_0_0 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+    }
+
+    if StorageVersion::get() == Releases::V3_0_0 {
+        weight = weight.saturating_add(migrate_to_v4::<T>());
+    }
+
+    weight
+}
+
+/// Start the decay clock of every account that has reputation above the decay floor,
+/// so that accounts which never act again decay in batches from `on_initialize` too.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    let now = <system::Module<T>>::block_number();
+    let floor = T::ReputationDecayFloor::get();
+
+    let mut accounts_count: Weight = 0;
+    let mut decaying_count: Weight = 0;
+
+    for (account, social_account) in <SocialAccountById<T>>::iter() {
+        accounts_count = accounts_count.saturating_add(1);
+
+        if social_account.reputation > floor && Module::<T>::reputation_decayed_at(&account).is_none() {
+            ReputationDecayedAt::<T>::insert(&account, now);
+            Module::<T>::add_decaying_account(account);
+            decaying_count = decaying_count.saturating_add(1);
+        }
+    }
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    // Every decaying account gets a clock and an entry in the queue, and the queue length is updated.
+    T::DbWeight::get().reads_writes(
+        accounts_count.saturating_mul(2).saturating_add(decaying_count).saturating_add(1),
+        decaying_count.saturating_mul(3).saturating_add(1),
+    )
+}
+
+/// Build indexes of actors by a scored account and by a scored post.
+fn migrate_to_v3<T: Trait>() -> Weight {
+    let mut reputation_diffs_count: Weight = 0;
+    for ((actor, account, action), _) in <AccountReputationDiffByAccount<T>>::iter() {
+        <ReputationActorsByAccount<T>>::insert(account, (actor, action), true);
+        reputation_diffs_count = reputation_diffs_count.saturating_add(1);
+    }
+
+    let mut post_scores_count: Weight = 0;
+    for ((actor, post_id, action), _) in <PostScoreByAccount<T>>::iter() {
+        <ScoreActorsByPostId<T>>::insert(post_id, (actor, action), true);
+        post_scores_count = post_scores_count.saturating_add(1);
+    }
+
+    StorageVersion::put(Releases::V3_0_0);
+
+    let entries_count = reputation_diffs_count.saturating_add(post_scores_count);
+    T::DbWeight::get().reads_writes(entries_count.saturating_add(1), entries_count.saturating_add(1))
+}
+
+/// Index the queue of decaying accounts by account, so that reaped accounts can leave it.
+/// Accounts that were reaped before are dropped while the queue is rebuilt without gaps.
+fn migrate_to_v4<T: Trait>() -> Weight {
+    let old_count = DecayingAccountsCount::get();
+    let mut new_count: DecayIndex = 0;
+
+    for index in 0..old_count {
+        if let Some(account) = DecayingAccountByIndex::<T>::take(index) {
+            if DecayIndexByAccount::<T>::contains_key(&account) {
+                continue
+            }
+
+            if Profiles::<T>::social_account_by_id(&account).is_some() {
+                DecayIndexByAccount::<T>::insert(&account, new_count);
+                DecayingAccountByIndex::<T>::insert(new_count, account);
+                new_count = new_count.saturating_add(1);
+            } else {
+                ReputationDecayedAt::<T>::remove(account);
+            }
+        }
+    }
+
+    DecayingAccountsCount::put(new_count);
+    NextDecayIndex::put(0);
+    StorageVersion::put(Releases::V4_0_0);
+
+    // Every account in the queue is read together with its index and social account,
+    // then it is either moved with a new index or its decay clock is removed.
+    T::DbWeight::get().reads_writes(
+        old_count.saturating_mul(3).saturating_add(2),
+        old_count.saturating_mul(3).saturating_add(3),
+    )
+}
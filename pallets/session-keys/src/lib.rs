@@ -27,7 +27,7 @@ use frame_support::{
     dispatch::{DispatchError, DispatchResult, PostDispatchInfo},
     traits::{
         Currency, Get, ExistenceRequirement,
-        OriginTrait, IsType, Filter,
+        OriginTrait, IsType, Filter, OnKilledAccount,
     },
     Parameter,
 };
@@ -389,3 +389,21 @@ impl<T: Trait> Module<T> {
         length_fee + weight_fee
     }
 }
+
+impl<T: Trait> OnKilledAccount<T::AccountId> for Module<T> {
+    /// Forget session keys of a reaped owner, or a reaped session key itself.
+    /// Expired keys left in `SessionKeysByExpireBlock` are skipped in `on_finalize`.
+    fn on_killed_account(who: &T::AccountId) {
+        for key in KeysByOwner::<T>::take(who) {
+            KeyDetails::<T>::remove(&key);
+        }
+
+        if let Some(details) = KeyDetails::<T>::take(who) {
+            KeysByOwner::<T>::mutate(details.owner(), |keys| {
+                if let Ok(i) = keys.binary_search(who) {
+                    keys.remove(i);
+                }
+            });
+        }
+    }
+}
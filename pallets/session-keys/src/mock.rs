@@ -69,7 +69,7 @@ impl system::Trait for Test {
     type PalletInfo = ();
     type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
-    type OnKilledAccount = ();
+    type OnKilledAccount = SessionKeys;
     type SystemWeightInfo = ();
 }
 
@@ -104,6 +104,8 @@ parameter_types! {
   pub const IpfsCidLen: u32 = 46;
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  pub const MaxRawContentLen: u32 = 1024;
+  pub const RawContentDepositPerByte: u64 = 0;
 }
 
 impl pallet_utils::Trait for Test {
@@ -111,12 +113,15 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type MaxRawContentLen = MaxRawContentLen;
+    type RawContentDepositPerByte = RawContentDepositPerByte;
 }
 
 impl pallet_profile_follows::Trait for Test {
     type Event = ();
     type BeforeAccountFollowed = ();
     type BeforeAccountUnfollowed = ();
+    type IsBlockedByAccount = ();
 }
 
 parameter_types! {}
@@ -124,6 +129,10 @@ parameter_types! {}
 impl pallet_profiles::Trait for Test {
     type Event = ();
     type AfterProfileUpdated = ();
+    type HandleDeposit = ();
+    type IsHandleReserved = ();
+    type ReapedAccountCleanup = ();
+    type MaxReapedAccountEntriesPerBlock = ();
 }
 
 // TODO export to a common place
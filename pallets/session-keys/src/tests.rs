@@ -202,4 +202,29 @@ fn proxy_should_fail_with_session_key_limit_reached() {
         assert_ok!(_default_proxy());
         assert_noop!(_default_proxy(), Error::<Test>::SessionKeyLimitReached);
     });
-}
\ No newline at end of file
+}
+//--------------------------------------------------------------------------------------------
+
+#[test]
+fn reaped_session_key_should_be_removed() {
+    ExtBuilder::build_with_balance().execute_with(|| {
+        assert_ok!(_add_default_key());
+
+        Balances::make_free_balance_be(&ACCOUNT_PROXY, 0);
+
+        assert!(SessionKeys::keys_by_owner(ACCOUNT_MAIN).is_empty());
+        assert!(SessionKeys::key_details(ACCOUNT_PROXY).is_none());
+    });
+}
+
+#[test]
+fn session_keys_of_reaped_owner_should_be_removed() {
+    ExtBuilder::build_with_balance().execute_with(|| {
+        assert_ok!(_add_default_key());
+
+        Balances::make_free_balance_be(&ACCOUNT_MAIN, 0);
+
+        assert!(SessionKeys::keys_by_owner(ACCOUNT_MAIN).is_empty());
+        assert!(SessionKeys::key_details(ACCOUNT_PROXY).is_none());
+    });
+}
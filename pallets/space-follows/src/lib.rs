@@ -1,20 +1,44 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::DispatchResult,
-    traits::Get
+    storage::IterableStorageDoubleMap,
+    traits::Get,
+    weights::Weight,
 };
+use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
+    CleanUpReapedAccount,
     SpaceFollowsProvider,
     moderation::IsAccountBlocked,
 };
+use pallet_permissions::SpacePermission;
 use pallet_profiles::{Module as Profiles, SocialAccountById};
-use pallet_spaces::{BeforeSpaceCreated, Module as Spaces, Space, SpaceById};
-use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
+use pallet_spaces::{BeforeSpaceCreated, BeforeSpaceDeleted, Module as Spaces, Space, SpaceById};
+use pallet_utils::{Error as UtilsError, SpaceId};
+
+pub mod migration;
+
+// A value placed in storage that represents the current version of the Space Follows storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[allow(non_camel_case_types)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
 
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
@@ -28,6 +52,9 @@ pub trait Trait: system::Trait
     type BeforeSpaceFollowed: BeforeSpaceFollowed<Self>;
 
     type BeforeSpaceUnfollowed: BeforeSpaceUnfollowed<Self>;
+
+    /// The max number of followers and follow requests of deleted spaces to remove per block.
+    type MaxSpaceFollowersToCleanUpPerBlock: Get<u32>;
 }
 
 decl_error! {
@@ -40,20 +67,59 @@ decl_error! {
         NotSpaceFollower,
         /// Not allowed to follow a hidden space.
         CannotFollowHiddenSpace,
+
+        /// Account has already requested to follow this space.
+        FollowRequestAlreadySent,
+        /// There is no pending request from this account to follow this space.
+        FollowRequestNotFound,
+        /// User has no permission to change whether this space requires approval of followers.
+        NoPermissionToUpdateFollowApproval,
+        /// User has no permission to accept or reject requests to follow this space.
+        NoPermissionToManageFollowRequests,
     }
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as SpaceFollowsModule {
-        pub SpaceFollowers get(fn space_followers):
-            map hasher(twox_64_concat) SpaceId => Vec<T::AccountId>;
+        /// Followers (2nd key) of a space (1st key).
+        pub SpaceFollowers:
+            double_map hasher(twox_64_concat) SpaceId, hasher(blake2_128_concat) T::AccountId => bool;
 
         pub SpaceFollowedByAccount get(fn space_followed_by_account):
             map hasher(blake2_128_concat) (T::AccountId, SpaceId) => bool;
 
-        pub SpacesFollowedByAccount get(fn spaces_followed_by_account):
-            map hasher(blake2_128_concat) T::AccountId => Vec<SpaceId>;
+        /// Spaces (2nd key) followed by an account (1st key).
+        pub SpacesFollowedByAccount:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) SpaceId => bool;
+
+        /// Whether follow requests to a space should be approved before accounts become followers.
+        pub FollowApprovalRequired get(fn follow_approval_required):
+            map hasher(twox_64_concat) SpaceId => bool;
+
+        /// Accounts (2nd key) with pending requests to follow a space (1st key).
+        pub FollowRequestsToSpace:
+            double_map hasher(twox_64_concat) SpaceId, hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Spaces (2nd key) that an account (1st key) has pending requests to follow.
+        pub SpaceFollowRequestsByAccount:
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) SpaceId => bool;
+
+        pub SpaceFollowRequestedByAccount get(fn space_follow_requested_by_account):
+            map hasher(blake2_128_concat) (T::AccountId, SpaceId) => bool;
+
+        /// Deleted spaces in the order of removing their followers from `on_initialize`.
+        pub DeletedSpaceByIndex get(fn deleted_space_by_index):
+            map hasher(twox_64_concat) u64 => Option<Space<T>>;
+
+        /// An index of the next deleted space whose followers should be removed.
+        pub FirstDeletedSpaceIndex get(fn first_deleted_space_index): u64;
+
+        /// An index that will be given to the next deleted space.
+        pub NextDeletedSpaceIndex get(fn next_deleted_space_index): u64;
+
+        /// The current storage version of this pallet, used by `on_runtime_upgrade` to run migrations.
+        StorageVersion build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
 }
 
@@ -63,6 +129,11 @@ decl_event!(
     {
         SpaceFollowed(/* follower */ AccountId, /* following */ SpaceId),
         SpaceUnfollowed(/* follower */ AccountId, /* unfollowing */ SpaceId),
+        FollowApprovalUpdated(/* updated by */ AccountId, SpaceId, /* required */ bool),
+        SpaceFollowRequestSent(/* requester */ AccountId, SpaceId),
+        SpaceFollowRequestAccepted(/* accepted by */ AccountId, /* requester */ AccountId, SpaceId),
+        SpaceFollowRequestRejected(/* rejected by */ AccountId, /* requester */ AccountId, SpaceId),
+        SpaceFollowRequestCanceled(/* requester */ AccountId, SpaceId),
     }
 );
 
@@ -72,9 +143,19 @@ decl_module! {
     // Initializing errors
     type Error = Error<T>;
 
+    const MaxSpaceFollowersToCleanUpPerBlock: u32 = T::MaxSpaceFollowersToCleanUpPerBlock::get();
+
     // Initializing events
     fn deposit_event() = default;
 
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
+    fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+      Self::clean_up_followers_of_deleted_spaces()
+    }
+
     #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
     pub fn follow_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
@@ -86,9 +167,28 @@ decl_module! {
 
       ensure!(T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
 
-      Self::add_space_follower(follower, space)?;
+      if Self::follow_approval_required(space_id) {
+        ensure!(
+          !Self::space_follow_requested_by_account((follower.clone(), space_id)),
+          Error::<T>::FollowRequestAlreadySent
+        );
+
+        <FollowRequestsToSpace<T>>::insert(space_id, &follower, true);
+        <SpaceFollowRequestsByAccount<T>>::insert(&follower, space_id, true);
+        <SpaceFollowRequestedByAccount<T>>::insert((follower.clone(), space_id), true);
+
+        Self::deposit_event(RawEvent::SpaceFollowRequestSent(follower, space_id));
+        return Ok(());
+      }
+
+      Self::add_space_follower(follower.clone(), space)?;
       <SpaceById<T>>::insert(space_id, space);
 
+      // A request could be left pending, if a space stopped requiring approval.
+      if Self::space_follow_requested_by_account((follower.clone(), space_id)) {
+        Self::remove_follow_request(&follower, space_id);
+      }
+
       Ok(())
     }
 
@@ -100,10 +200,110 @@ decl_module! {
 
       Self::unfollow_space_by_account(follower, space_id)
     }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    pub fn set_follow_approval_required(origin, space_id: SpaceId, required: bool) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      Spaces::<T>::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::UpdateSpaceSettings,
+        Error::<T>::NoPermissionToUpdateFollowApproval.into(),
+      )?;
+
+      if required {
+        FollowApprovalRequired::insert(space_id, true);
+      } else {
+        FollowApprovalRequired::remove(space_id);
+      }
+
+      Self::deposit_event(RawEvent::FollowApprovalUpdated(who, space_id, required));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 8)]
+    pub fn accept_follow_request(origin, space_id: SpaceId, requester: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = &mut Spaces::require_space(space_id)?;
+      Self::ensure_can_manage_follow_requests(who.clone(), space)?;
+      Self::ensure_follow_request_exists(&requester, space_id)?;
+      ensure!(!Self::space_followed_by_account((requester.clone(), space_id)), Error::<T>::AlreadySpaceFollower);
+
+      Self::add_space_follower(requester.clone(), space)?;
+      <SpaceById<T>>::insert(space_id, space);
+      Self::remove_follow_request(&requester, space_id);
+
+      Self::deposit_event(RawEvent::SpaceFollowRequestAccepted(who, requester, space_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3)]
+    pub fn reject_follow_request(origin, space_id: SpaceId, requester: T::AccountId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      Self::ensure_can_manage_follow_requests(who.clone(), &space)?;
+      Self::ensure_follow_request_exists(&requester, space_id)?;
+
+      Self::remove_follow_request(&requester, space_id);
+
+      Self::deposit_event(RawEvent::SpaceFollowRequestRejected(who, requester, space_id));
+      Ok(())
+    }
+
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 3)]
+    pub fn cancel_follow_request(origin, space_id: SpaceId) -> DispatchResult {
+      let requester = ensure_signed(origin)?;
+
+      Self::ensure_follow_request_exists(&requester, space_id)?;
+      Self::remove_follow_request(&requester, space_id);
+
+      Self::deposit_event(RawEvent::SpaceFollowRequestCanceled(requester, space_id));
+      Ok(())
+    }
   }
 }
 
 impl<T: Trait> Module<T> {
+    /// Get at most `limit` followers of a space, skipping the first `offset` of them.
+    pub fn space_followers(space_id: SpaceId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <SpaceFollowers<T>>::iter_prefix(space_id)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(follower, _)| follower)
+            .collect()
+    }
+
+    /// Get at most `limit` spaces followed by an account, skipping the first `offset` of them.
+    pub fn spaces_followed_by_account(follower: &T::AccountId, offset: u32, limit: u32) -> Vec<SpaceId> {
+        <SpacesFollowedByAccount<T>>::iter_prefix(follower)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(space_id, _)| space_id)
+            .collect()
+    }
+
+    /// Get at most `limit` accounts that requested to follow a space, skipping the first `offset` of them.
+    pub fn follow_requests_to_space(space_id: SpaceId, offset: u32, limit: u32) -> Vec<T::AccountId> {
+        <FollowRequestsToSpace<T>>::iter_prefix(space_id)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(requester, _)| requester)
+            .collect()
+    }
+
+    /// Get at most `limit` spaces that an account requested to follow, skipping the first `offset` of them.
+    pub fn space_follow_requests_by_account(requester: &T::AccountId, offset: u32, limit: u32) -> Vec<SpaceId> {
+        <SpaceFollowRequestsByAccount<T>>::iter_prefix(requester)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(space_id, _)| space_id)
+            .collect()
+    }
+
     fn add_space_follower(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult {
         space.inc_followers();
 
@@ -114,9 +314,9 @@ impl<T: Trait> Module<T> {
             follower.clone(), social_account.reputation, space)?;
 
         let space_id = space.id;
-        <SpaceFollowers<T>>::mutate(space_id, |followers| followers.push(follower.clone()));
+        <SpaceFollowers<T>>::insert(space_id, &follower, true);
         <SpaceFollowedByAccount<T>>::insert((follower.clone(), space_id), true);
-        <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| space_ids.push(space_id));
+        <SpacesFollowedByAccount<T>>::insert(&follower, space_id, true);
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
 
         Self::deposit_event(RawEvent::SpaceFollowed(follower, space_id));
@@ -124,6 +324,94 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn ensure_can_manage_follow_requests(account: T::AccountId, space: &Space<T>) -> DispatchResult {
+        Spaces::<T>::ensure_account_has_space_permission(
+            account,
+            space,
+            SpacePermission::ManageFollowRequests,
+            Error::<T>::NoPermissionToManageFollowRequests.into(),
+        )
+    }
+
+    fn ensure_follow_request_exists(requester: &T::AccountId, space_id: SpaceId) -> DispatchResult {
+        ensure!(
+            Self::space_follow_requested_by_account((requester.clone(), space_id)),
+            Error::<T>::FollowRequestNotFound
+        );
+        Ok(())
+    }
+
+    fn remove_follow_request(requester: &T::AccountId, space_id: SpaceId) {
+        <FollowRequestsToSpace<T>>::remove(space_id, requester);
+        <SpaceFollowRequestsByAccount<T>>::remove(requester, space_id);
+        <SpaceFollowRequestedByAccount<T>>::remove((requester.clone(), space_id));
+    }
+
+    /// Remove at most `MaxSpaceFollowersToCleanUpPerBlock` followers and follow requests
+    /// of deleted spaces, continuing from the space where the previous block stopped.
+    fn clean_up_followers_of_deleted_spaces() -> Weight {
+        let first_index = Self::first_deleted_space_index();
+        let next_index = Self::next_deleted_space_index();
+        if first_index == next_index {
+            return T::DbWeight::get().reads(2)
+        }
+
+        let limit = T::MaxSpaceFollowersToCleanUpPerBlock::get();
+        let mut removed: u32 = 0;
+        let mut index = first_index;
+
+        while index < next_index && removed < limit {
+            if let Some(space) = Self::deleted_space_by_index(index) {
+                let remaining = limit - removed;
+                let removed_of_space = Self::remove_followers_of_deleted_space(space, remaining);
+                removed = removed.saturating_add(removed_of_space);
+
+                if removed_of_space >= remaining {
+                    // This space may have more followers to remove in the next block.
+                    break;
+                }
+            }
+            DeletedSpaceByIndex::<T>::remove(index);
+            index = index.saturating_add(1);
+        }
+
+        FirstDeletedSpaceIndex::put(index);
+
+        let processed_spaces = index.saturating_sub(first_index).saturating_add(1);
+        T::DbWeight::get().reads_writes(processed_spaces.saturating_add(2), processed_spaces)
+            // A follower is unfollowed the same way as by `unfollow_space_by_account`.
+            .saturating_add(T::DbWeight::get().reads_writes(4, 5).saturating_mul(removed as Weight))
+    }
+
+    /// Unfollow a deleted space by at most `limit` followers, then remove its follow requests.
+    /// Return the number of removed followers and follow requests.
+    fn remove_followers_of_deleted_space(mut space: Space<T>, limit: u32) -> u32 {
+        let space_id = space.id;
+        let mut removed: u32 = 0;
+
+        for (follower, _) in <SpaceFollowers<T>>::drain_prefix(space_id).take(limit as usize) {
+            <SpacesFollowedByAccount<T>>::remove(&follower, space_id);
+            <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
+            <SocialAccountById<T>>::mutate(&follower, |social_account_opt| {
+                if let Some(social_account) = social_account_opt {
+                    social_account.dec_following_spaces();
+                }
+            });
+
+            // The space is deleted, so only reputation of its owner is changed.
+            let _ = T::BeforeSpaceUnfollowed::before_space_unfollowed(follower, &mut space);
+            removed += 1;
+        }
+
+        for (requester, _) in <FollowRequestsToSpace<T>>::drain_prefix(space_id).take((limit - removed) as usize) {
+            <SpaceFollowRequestsByAccount<T>>::remove(&requester, space_id);
+            <SpaceFollowRequestedByAccount<T>>::remove((requester, space_id));
+            removed += 1;
+        }
+
+        removed
+    }
+
     pub fn unfollow_space_by_account(follower: T::AccountId, space_id: SpaceId) -> DispatchResult {
         let space = &mut Spaces::require_space(space_id)?;
         space.dec_followers();
@@ -133,8 +421,8 @@ impl<T: Trait> Module<T> {
 
         T::BeforeSpaceUnfollowed::before_space_unfollowed(follower.clone(), space)?;
 
-        <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| remove_from_vec(space_ids, space_id));
-        <SpaceFollowers<T>>::mutate(space_id, |account_ids| remove_from_vec(account_ids, follower.clone()));
+        <SpacesFollowedByAccount<T>>::remove(&follower, space_id);
+        <SpaceFollowers<T>>::remove(space_id, &follower);
         <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
         <SpaceById<T>>::insert(space_id, space);
@@ -159,6 +447,47 @@ impl<T: Trait> BeforeSpaceCreated<T> for Module<T> {
     }
 }
 
+impl<T: Trait> BeforeSpaceDeleted<T> for Module<T> {
+    fn before_space_deleted(_account: T::AccountId, space: &Space<T>) -> DispatchResult {
+        // A space can have too many followers to unfollow it by all of them in one block,
+        // so they are removed in batches from `on_initialize`, including the space owner.
+        let index = Module::<T>::next_deleted_space_index();
+        DeletedSpaceByIndex::<T>::insert(index, space);
+        NextDeletedSpaceIndex::put(index.saturating_add(1));
+
+        FollowApprovalRequired::remove(space.id);
+
+        Ok(())
+    }
+}
+
+impl<T: Trait> CleanUpReapedAccount<T::AccountId> for Module<T> {
+    /// Unfollow spaces followed by a reaped account, update their followers counters
+    /// and remove pending follow requests of this account.
+    fn clean_up_reaped_account(who: &T::AccountId, limit: u32) -> u32 {
+        let mut removed: u32 = 0;
+
+        for (space_id, _) in <SpacesFollowedByAccount<T>>::drain_prefix(who).take(limit as usize) {
+            <SpaceFollowedByAccount<T>>::remove((who.clone(), space_id));
+            <SpaceFollowers<T>>::remove(space_id, who);
+            <SpaceById<T>>::mutate(space_id, |space_opt| {
+                if let Some(space) = space_opt {
+                    space.dec_followers();
+                }
+            });
+            removed += 1;
+        }
+
+        for (space_id, _) in <SpaceFollowRequestsByAccount<T>>::drain_prefix(who).take((limit - removed) as usize) {
+            <SpaceFollowRequestedByAccount<T>>::remove((who.clone(), space_id));
+            <FollowRequestsToSpace<T>>::remove(space_id, who);
+            removed += 1;
+        }
+
+        removed
+    }
+}
+
 /// Handler that will be called right before the space is followed.
 pub trait BeforeSpaceFollowed<T: Trait> {
     fn before_space_followed(follower: T::AccountId, follower_reputation: u32, space: &mut Space<T>) -> DispatchResult;
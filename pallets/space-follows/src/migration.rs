@@ -0,0 +1,54 @@
+use frame_support::{
+    Blake2_128Concat, Twox64Concat,
+    storage::{migration::StorageKeyIterator, StorageDoubleMap, StorageValue},
+};
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    weight
+}
+
+const MODULE_PREFIX: &[u8] = b"SpaceFollowsModule";
+
+/// Move space followers and spaces followed by accounts from vectors to double maps.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    // Old values share storage prefixes with new double maps, so they should be read out first.
+    let followers_by_space: Vec<(SpaceId, Vec<T::AccountId>)> =
+        StorageKeyIterator::<SpaceId, Vec<T::AccountId>, Twox64Concat>::new(MODULE_PREFIX, b"SpaceFollowers")
+            .drain()
+            .collect();
+
+    let spaces_by_follower: Vec<(T::AccountId, Vec<SpaceId>)> =
+        StorageKeyIterator::<T::AccountId, Vec<SpaceId>, Blake2_128Concat>::new(MODULE_PREFIX, b"SpacesFollowedByAccount")
+            .drain()
+            .collect();
+
+    let mut writes: Weight = 0;
+
+    for (space_id, followers) in followers_by_space.iter() {
+        for follower in followers {
+            SpaceFollowers::<T>::insert(space_id, follower, true);
+        }
+        writes = writes.saturating_add(followers.len() as Weight + 1);
+    }
+
+    for (follower, space_ids) in spaces_by_follower.iter() {
+        for space_id in space_ids {
+            SpacesFollowedByAccount::<T>::insert(follower, space_id, true);
+        }
+        writes = writes.saturating_add(space_ids.len() as Weight + 1);
+    }
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    let reads = (followers_by_space.len() + spaces_by_follower.len()) as Weight;
+    T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+}
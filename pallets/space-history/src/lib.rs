@@ -7,7 +7,7 @@ use sp_std::prelude::Vec;
 use frame_system::{self as system};
 
 use pallet_utils::{SpaceId, WhoAndWhen};
-use pallet_spaces::{Space, SpaceUpdate, AfterSpaceUpdated};
+use pallet_spaces::{Space, SpaceUpdate, AfterSpaceUpdated, AfterSpaceDeleted};
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SpaceHistoryRecord<T: Trait> {
@@ -49,3 +49,9 @@ impl<T: Trait> AfterSpaceUpdated<T> for Module<T> {
             ids.push(SpaceHistoryRecord::<T>::new(sender, old_data)));
     }
 }
+
+impl<T: Trait> AfterSpaceDeleted<T> for Module<T> {
+    fn after_space_deleted(_account: T::AccountId, space: &Space<T>) {
+        <EditHistory<T>>::remove(space.id);
+    }
+}
@@ -77,6 +77,8 @@ impl pallet_balances::Trait for Test {
 parameter_types! {
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  pub const MaxRawContentLen: u32 = 1024;
+  pub const RawContentDepositPerByte: u64 = 0;
 }
 
 impl pallet_utils::Trait for Test {
@@ -84,6 +86,8 @@ impl pallet_utils::Trait for Test {
   type Currency = Balances;
   type MinHandleLen = MinHandleLen;
   type MaxHandleLen = MaxHandleLen;
+  type MaxRawContentLen = MaxRawContentLen;
+  type RawContentDepositPerByte = RawContentDepositPerByte;
 }
 
 parameter_types! {
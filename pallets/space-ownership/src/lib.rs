@@ -10,8 +10,8 @@ use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::moderation::IsAccountBlocked;
-use pallet_spaces::{Module as Spaces, SpaceById, SpaceIdsByOwner};
-use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
+use pallet_spaces::{AfterSpaceDeleted, Module as Spaces, Space, SpaceById, SpaceIdsByOwner};
+use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, ContentHolder, remove_from_vec};
 
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
@@ -96,6 +96,9 @@ decl_module! {
 
       Spaces::maybe_transfer_handle_deposit_to_new_space_owner(&space, &new_owner)?;
 
+      // A new owner holds a deposit for the space content from now on.
+      Utils::<T>::update_content_deposit(&new_owner, ContentHolder::Space(space_id), &space.content)?;
+
       let old_owner = space.owner;
       space.owner = new_owner.clone();
       <SpaceById<T>>::insert(space_id, space);
@@ -127,3 +130,9 @@ decl_module! {
     }
   }
 }
+
+impl<T: Trait> AfterSpaceDeleted<T> for Module<T> {
+    fn after_space_deleted(_account: T::AccountId, space: &Space<T>) {
+        <PendingSpaceOwner<T>>::remove(space.id);
+    }
+}
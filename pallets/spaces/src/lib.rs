@@ -5,17 +5,22 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     dispatch::{DispatchError, DispatchResult},
     traits::{Get, Currency, ExistenceRequirement, ReservableCurrency},
+    weights::Weight,
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider,
+    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider, IsHandleReserved,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
-use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions, SpacePermissionsContext};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, WhoAndWhen, Content};
+use pallet_permissions::{
+    Module as Permissions, SpacePermission, SpacePermissions, SpacePermissionsContext, InheritedSpacePermissions,
+};
+use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, WhoAndWhen, Content, ContentHolder, remove_from_vec};
+
+pub mod migration;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Space<T: Trait> {
@@ -51,6 +56,21 @@ pub struct SpaceUpdate {
     pub permissions: Option<Option<SpacePermissions>>,
 }
 
+/// A version of this pallet's storage. Used to run storage migrations only once.
+#[allow(non_camel_case_types)]
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
 type BalanceOf<T> =
   <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
@@ -72,11 +92,22 @@ pub trait Trait: system::Trait
 
     type AfterSpaceUpdated: AfterSpaceUpdated<Self>;
 
+    type BeforeSpaceDeleted: BeforeSpaceDeleted<Self>;
+
+    type AfterSpaceDeleted: AfterSpaceDeleted<Self>;
+
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
     type IsContentBlocked: IsContentBlocked;
 
     type HandleDeposit: Get<BalanceOf<Self>>;
+
+    /// Handles that cannot be taken by spaces. Set to profiles to share one namespace
+    /// between space and profile handles, or to `()` to keep them separate.
+    type IsHandleReserved: IsHandleReserved;
+
+    /// Max number of spaces in a chain from a root space to its deepest subspace.
+    type MaxSpaceDepth: Get<u32>;
 }
 
 decl_error! {
@@ -95,6 +126,28 @@ decl_error! {
     NoPermissionToCreateSubspaces,
     /// Space is at root level, no parent_id specified
     SpaceIsAtRoot,
+    /// User has no permission to delete this subspace.
+    NoPermissionToDeleteAnySubspace,
+    /// A subspace owner is not allowed to delete their own subspaces in this parent space.
+    NoPermissionToDeleteOwnSubspaces,
+    /// Cannot delete a space that has posts. Delete or move them first.
+    SpaceHasPosts,
+    /// Cannot delete a space that has subspaces. Delete or move them first.
+    SpaceHasSubspaces,
+    /// Cannot move a space under itself or under one of its subspaces.
+    SpaceCannotBeItsOwnAncestor,
+    /// Max space depth reached.
+    MaxSpaceDepthReached,
+    /// Nothing to update in permissions inheritance of space.
+    NoUpdatesForPermissionsInheritance,
+    /// User has no permission to hide this subspace.
+    NoPermissionToHideAnySubspace,
+    /// A subspace owner is not allowed to hide their own subspaces in this parent space.
+    NoPermissionToHideOwnSubspaces,
+    /// Space is already hidden.
+    SpaceIsAlreadyHidden,
+    /// Space is not hidden.
+    SpaceIsNotHidden,
   }
 }
 
@@ -121,6 +174,21 @@ decl_storage! {
 
         pub SpaceIdsByOwner get(fn space_ids_by_owner):
             map hasher(twox_64_concat) T::AccountId => Vec<SpaceId>;
+
+        pub SubspaceIdsByParentId get(fn subspace_ids_by_parent_id):
+            map hasher(twox_64_concat) SpaceId => Vec<SpaceId>;
+
+        /// Whether a subspace inherits permission overrides and roles from its parent space.
+        pub InheritsParentPermissions get(fn inherits_parent_permissions):
+            map hasher(twox_64_concat) SpaceId => bool;
+
+        /// The number of direct subspaces of a space (key) by the depth of their subspace trees:
+        /// a value at index `i` counts subspaces whose trees have `i + 1` levels.
+        /// There are no trailing zeros, so the length of this list is the depth of the deepest subspace.
+        pub SubspaceCountsByTreeDepth get(fn subspace_counts_by_tree_depth):
+            map hasher(twox_64_concat) SpaceId => Vec<u32>;
+
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V3_0_0): Releases;
     }
     add_extra_genesis {
       config(endowed_account): T::AccountId;
@@ -143,13 +211,22 @@ decl_module! {
 
     const HandleDeposit: BalanceOf<T> = T::HandleDeposit::get();
 
+    const MaxSpaceDepth: u32 = T::MaxSpaceDepth::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(4, 4)]
+    fn on_runtime_upgrade() -> Weight {
+      migration::migrate::<T>()
+    }
+
+    #[weight = 500_000 + T::DbWeight::get().reads_writes(4, 4)
+      // Walk up the ancestors of a parent space to check and update their depths.
+      + T::DbWeight::get().reads_writes(3, 1) * T::MaxSpaceDepth::get() as u64
+    ]
     pub fn create_space(
       origin,
       parent_id_opt: Option<SpaceId>,
@@ -174,30 +251,53 @@ decl_module! {
           SpacePermission::CreateSubspaces,
           Error::<T>::NoPermissionToCreateSubspaces.into()
         )?;
+
+        let ancestors = Self::get_space_ancestors(parent_id);
+        ensure!(ancestors.len() < T::MaxSpaceDepth::get() as usize, Error::<T>::MaxSpaceDepthReached);
       }
 
       let permissions = permissions_opt.map(|perms| {
         Permissions::<T>::override_permissions(perms)
       });
 
+      let handle_in_lowercase_opt = handle_opt.clone()
+        .map(Self::lowercase_and_ensure_unique_handle)
+        .transpose()?;
+
       let space_id = Self::next_space_id();
-      let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt.clone(), permissions);
+      let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt, permissions);
 
-      if let Some(handle) = handle_opt {
-        Self::reserve_handle(&new_space, handle)?;
+      // Reserve both deposits before any other storage write.
+      let deposit_holder = ContentHolder::Space(space_id);
+      Utils::<T>::reserve_content_deposit(&owner, deposit_holder.clone(), &new_space.content)?;
+
+      if let Some(handle_in_lowercase) = handle_in_lowercase_opt {
+        if let Err(error) = Self::reserve_handle_deposit(&owner) {
+          Utils::<T>::unreserve_content_deposit(&deposit_holder);
+          return Err(error);
+        }
+        SpaceIdByHandle::insert(handle_in_lowercase, space_id);
       }
 
       T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
 
       <SpaceById<T>>::insert(space_id, new_space);
       <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
+
+      if let Some(parent_id) = parent_id_opt {
+        SubspaceIdsByParentId::mutate(parent_id, |ids| ids.push(space_id));
+        Self::update_subspace_tree_depths(parent_id, None, Some(1));
+      }
       NextSpaceId::mutate(|n| { *n += 1; });
 
       Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
       Ok(())
     }
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(2, 3)]
+    #[weight = 500_000 + T::DbWeight::get().reads_writes(2, 4)
+      // Walk up the ancestors of a new parent space and update depths of both old and new ancestors.
+      + T::DbWeight::get().reads_writes(5, 2) * T::MaxSpaceDepth::get() as u64
+    ]
     pub fn update_space(origin, space_id: SpaceId, update: SpaceUpdate) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
@@ -224,7 +324,6 @@ decl_module! {
       let mut is_update_applied = false;
       let mut old_data = SpaceUpdate::default();
 
-      // TODO: add tests for this case
       if let Some(parent_id_opt) = update.parent_id {
         if parent_id_opt != space.parent_id {
 
@@ -237,6 +336,8 @@ decl_module! {
               SpacePermission::CreateSubspaces,
               Error::<T>::NoPermissionToCreateSubspaces.into()
             )?;
+
+            Self::ensure_space_can_be_moved_under(space_id, parent_id)?;
           }
 
           old_data.parent_id = Some(space.parent_id);
@@ -254,6 +355,9 @@ decl_module! {
             ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
           }
 
+          // The editor pays for new content.
+          Utils::<T>::update_content_deposit(&owner, ContentHolder::Space(space_id), &content)?;
+
           old_data.content = Some(space.content);
           space.content = content;
           is_update_applied = true;
@@ -293,6 +397,22 @@ decl_module! {
       if is_update_applied {
         space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
 
+        if let Some(old_parent_id_opt) = old_data.parent_id {
+          let tree_depth = Self::get_subspace_tree_depth(space_id);
+
+          if let Some(old_parent_id) = old_parent_id_opt {
+            SubspaceIdsByParentId::mutate(old_parent_id, |ids| remove_from_vec(ids, space_id));
+            Self::update_subspace_tree_depths(old_parent_id, Some(tree_depth), None);
+          }
+          if let Some(parent_id) = space.parent_id {
+            SubspaceIdsByParentId::mutate(parent_id, |ids| ids.push(space_id));
+            Self::update_subspace_tree_depths(parent_id, None, Some(tree_depth));
+          }
+
+          // Permissions of a new parent should not be inherited without an explicit opt-in.
+          InheritsParentPermissions::remove(space_id);
+        }
+
         <SpaceById<T>>::insert(space_id, space.clone());
         T::AfterSpaceUpdated::after_space_updated(owner.clone(), &space, old_data);
 
@@ -300,6 +420,115 @@ decl_module! {
       }
       Ok(())
     }
+
+    /// Delete a space that has no posts and no subspaces.
+    /// A handle of this space becomes free and its deposit is returned to the space owner.
+    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(6, 6)
+      + T::DbWeight::get().reads_writes(2, 1) * T::MaxSpaceDepth::get() as u64
+      // Followers and roles of the space are only queued here and removed from `on_initialize`.
+      + T::DbWeight::get().reads_writes(2, 6)
+    ]
+    pub fn delete_space(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Self::require_space(space_id)?;
+
+      if let Some(parent_id) = space.parent_id {
+        let parent_space = Self::require_space(parent_id)?;
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
+
+        let (permission_to_check, permission_error) =
+          if space.is_owner(&who) {
+            (SpacePermission::DeleteOwnSubspaces, Error::<T>::NoPermissionToDeleteOwnSubspaces)
+          } else {
+            (SpacePermission::DeleteAnySubspace, Error::<T>::NoPermissionToDeleteAnySubspace)
+          };
+
+        Self::ensure_account_has_space_permission(
+          who.clone(),
+          &parent_space,
+          permission_to_check,
+          permission_error.into()
+        )?;
+      } else {
+        space.ensure_space_owner(who.clone())?;
+      }
+
+      ensure!(space.posts_count == 0, Error::<T>::SpaceHasPosts);
+      ensure!(Self::subspace_ids_by_parent_id(space_id).is_empty(), Error::<T>::SpaceHasSubspaces);
+
+      T::BeforeSpaceDeleted::before_space_deleted(who.clone(), &space)?;
+
+      if let Some(handle) = space.handle.clone() {
+        Self::unreserve_handle(&space, handle)?;
+      }
+      Utils::<T>::unreserve_content_deposit(&ContentHolder::Space(space_id));
+
+      if let Some(parent_id) = space.parent_id {
+        SubspaceIdsByParentId::mutate(parent_id, |ids| remove_from_vec(ids, space_id));
+        // A deleted space has no subspaces, so its tree has only one level.
+        Self::update_subspace_tree_depths(parent_id, Some(1), None);
+      }
+
+      <SpaceIdsByOwner<T>>::mutate(space.owner.clone(), |ids| remove_from_vec(ids, space_id));
+      <SpaceById<T>>::remove(space_id);
+      InheritsParentPermissions::remove(space_id);
+
+      T::AfterSpaceDeleted::after_space_deleted(who.clone(), &space);
+
+      Self::deposit_event(RawEvent::SpaceDeleted(who, space_id));
+      Ok(())
+    }
+
+    /// Hide a space. A subspace can be hidden by holders of `HideOwnSubspaces`/`HideAnySubspace`
+    /// in its parent space, a root space can be hidden by holders of `UpdateSpace` in this space.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn hide_space(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      Self::try_update_space_hidden(who, space_id, true)
+    }
+
+    /// Make a hidden space visible again. Requires the same permissions as `hide_space`.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn unhide_space(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      Self::try_update_space_hidden(who, space_id, false)
+    }
+
+    /// Enable or disable inheritance of permissions from a parent space.
+    /// When enabled, roles that are not overridden in this subspace are resolved
+    /// up the `parent_id` chain, and roles granted in parent spaces apply to this subspace.
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn set_inherit_parent_permissions(origin, space_id: SpaceId, inherit: bool) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let space = Self::require_space(space_id)?;
+      space.try_get_parent()?;
+
+      ensure!(T::IsAccountBlocked::is_allowed_account(who.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+
+      Self::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::UpdateSpace,
+        Error::<T>::NoPermissionToUpdateSpace.into()
+      )?;
+
+      ensure!(
+        Self::inherits_parent_permissions(space_id) != inherit,
+        Error::<T>::NoUpdatesForPermissionsInheritance
+      );
+
+      if inherit {
+        InheritsParentPermissions::insert(space_id, true);
+      } else {
+        InheritsParentPermissions::remove(space_id);
+      }
+
+      Self::deposit_event(RawEvent::SpaceUpdated(who, space_id));
+      Ok(())
+    }
   }
 }
 
@@ -406,21 +635,83 @@ impl<T: Trait> Module<T> {
         Ok(Self::space_by_id(space_id).ok_or(Error::<T>::SpaceNotFound)?)
     }
 
+    fn ensure_account_can_hide_space(account: &T::AccountId, space: &Space<T>) -> DispatchResult {
+        if let Some(parent_id) = space.parent_id {
+            let parent_space = Self::require_space(parent_id)?;
+
+            ensure!(T::IsAccountBlocked::is_allowed_account(account.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
+
+            let (permission_to_check, permission_error) =
+                if space.is_owner(account) {
+                    (SpacePermission::HideOwnSubspaces, Error::<T>::NoPermissionToHideOwnSubspaces)
+                } else {
+                    (SpacePermission::HideAnySubspace, Error::<T>::NoPermissionToHideAnySubspace)
+                };
+
+            Self::ensure_account_has_space_permission(
+                account.clone(),
+                &parent_space,
+                permission_to_check,
+                permission_error.into()
+            )
+        } else {
+            ensure!(T::IsAccountBlocked::is_allowed_account(account.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+            Self::ensure_account_has_space_permission(
+                account.clone(),
+                space,
+                SpacePermission::UpdateSpace,
+                Error::<T>::NoPermissionToUpdateSpace.into()
+            )
+        }
+    }
+
+    /// Hide or unhide a space and record this change in the space history.
+    pub fn try_update_space_hidden(who: T::AccountId, space_id: SpaceId, hidden: bool) -> DispatchResult {
+        let mut space = Self::require_space(space_id)?;
+
+        if hidden {
+            ensure!(!space.hidden, Error::<T>::SpaceIsAlreadyHidden);
+        } else {
+            ensure!(space.hidden, Error::<T>::SpaceIsNotHidden);
+        }
+
+        Self::ensure_account_can_hide_space(&who, &space)?;
+
+        let old_data = SpaceUpdate {
+            hidden: Some(space.hidden),
+            ..SpaceUpdate::default()
+        };
+
+        space.hidden = hidden;
+        space.updated = Some(WhoAndWhen::<T>::new(who.clone()));
+        <SpaceById<T>>::insert(space_id, space.clone());
+
+        T::AfterSpaceUpdated::after_space_updated(who.clone(), &space, old_data);
+
+        Self::deposit_event(RawEvent::SpaceUpdated(who, space_id));
+        Ok(())
+    }
+
+    /// Build a context to check permissions of this account in a given space.
+    pub fn get_permissions_context(account: &T::AccountId, space: &Space<T>) -> SpacePermissionsContext {
+        SpacePermissionsContext {
+            space_id: space.id,
+            is_space_owner: space.is_owner(account),
+            is_space_follower: space.is_follower(account),
+            space_perms: space.permissions.clone(),
+            inherited_perms: Self::get_inherited_permissions(space),
+            post_perms: None,
+        }
+    }
+
     pub fn ensure_account_has_space_permission(
         account: T::AccountId,
         space: &Space<T>,
         permission: SpacePermission,
         error: DispatchError,
     ) -> DispatchResult {
-        let is_owner = space.is_owner(&account);
-        let is_follower = space.is_follower(&account);
-
-        let ctx = SpacePermissionsContext {
-            space_id: space.id,
-            is_space_owner: is_owner,
-            is_space_follower: is_follower,
-            space_perms: space.permissions.clone(),
-        };
+        let ctx = Self::get_permissions_context(&account, space);
 
         T::Roles::ensure_account_has_space_permission(
             account,
@@ -430,8 +721,129 @@ impl<T: Trait> Module<T> {
         )
     }
 
+    /// Get permission overrides of parent spaces that this space inherits.
+    /// The chain stops at the first space that does not inherit from its parent.
+    pub fn get_inherited_permissions(space: &Space<T>) -> InheritedSpacePermissions {
+        let mut inherited_perms = InheritedSpacePermissions::new();
+        let mut current_space = space.clone();
+
+        while Self::inherits_parent_permissions(current_space.id) {
+            if inherited_perms.len() >= T::MaxSpaceDepth::get() as usize {
+                break;
+            }
+
+            match current_space.parent_id.and_then(Self::space_by_id) {
+                Some(parent) => {
+                    inherited_perms.push((parent.id, parent.permissions.clone()));
+                    current_space = parent;
+                }
+                None => break,
+            }
+        }
+
+        inherited_perms
+    }
+
+    /// Get all space ancestors (parent_id) including this space.
+    /// The list starts with this space and ends with a root space.
+    pub fn get_space_ancestors(space_id: SpaceId) -> Vec<Space<T>> {
+        let mut ancestors: Vec<Space<T>> = Vec::new();
+        let mut next_space_id = Some(space_id);
+
+        while let Some(id) = next_space_id {
+            // Stop if there is a cycle, so we never walk the same chain twice.
+            if ancestors.iter().any(|ancestor| ancestor.id == id) {
+                break;
+            }
+
+            if let Some(space) = Self::space_by_id(id) {
+                next_space_id = space.parent_id;
+                ancestors.push(space);
+            } else {
+                break;
+            }
+        }
+
+        ancestors
+    }
+
+    /// Get the number of levels in a tree of subspaces under this space, including this space.
+    pub fn get_subspace_tree_depth(space_id: SpaceId) -> u32 {
+        (Self::subspace_counts_by_tree_depth(space_id).len() as u32).saturating_add(1)
+    }
+
+    /// Update depths of subspace trees of a parent space and its ancestors, after a tree of
+    /// a direct subspace of this parent changed its depth from `old_subspace_depth`
+    /// (`None` if the subspace was added) to `new_subspace_depth` (`None` if it was removed).
+    /// Stops at the first ancestor whose depth did not change, so it walks up to `MaxSpaceDepth` levels.
+    pub(crate) fn update_subspace_tree_depths(
+        parent_id: SpaceId,
+        mut old_subspace_depth: Option<u32>,
+        mut new_subspace_depth: Option<u32>,
+    ) {
+        let mut next_space_id = Some(parent_id);
+
+        while let Some(space_id) = next_space_id {
+            let mut counts = Self::subspace_counts_by_tree_depth(space_id);
+            let old_depth = (counts.len() as u32).saturating_add(1);
+
+            if let Some(depth) = old_subspace_depth {
+                if let Some(count) = counts.get_mut(depth.saturating_sub(1) as usize) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+            if let Some(depth) = new_subspace_depth {
+                let index = depth.saturating_sub(1) as usize;
+                if counts.len() <= index {
+                    counts.resize(index + 1, 0);
+                }
+                counts[index] = counts[index].saturating_add(1);
+            }
+            while counts.last() == Some(&0) {
+                counts.pop();
+            }
+
+            let new_depth = (counts.len() as u32).saturating_add(1);
+            if counts.is_empty() {
+                SubspaceCountsByTreeDepth::remove(space_id);
+            } else {
+                SubspaceCountsByTreeDepth::insert(space_id, counts);
+            }
+
+            if new_depth == old_depth {
+                break;
+            }
+
+            old_subspace_depth = Some(old_depth);
+            new_subspace_depth = Some(new_depth);
+            next_space_id = Self::space_by_id(space_id).and_then(|space| space.parent_id);
+        }
+    }
+
+    /// Check that a space can be moved under a new parent:
+    /// a new parent should not be this space or any of its subspaces,
+    /// and the depth of the resulting tree should not exceed `MaxSpaceDepth`.
+    pub fn ensure_space_can_be_moved_under(space_id: SpaceId, new_parent_id: SpaceId) -> DispatchResult {
+        let new_ancestors = Self::get_space_ancestors(new_parent_id);
+
+        ensure!(
+            new_ancestors.iter().all(|ancestor| ancestor.id != space_id),
+            Error::<T>::SpaceCannotBeItsOwnAncestor
+        );
+
+        let new_depth = (new_ancestors.len() as u32).saturating_add(Self::get_subspace_tree_depth(space_id));
+        ensure!(new_depth <= T::MaxSpaceDepth::get(), Error::<T>::MaxSpaceDepthReached);
+
+        Ok(())
+    }
+
     pub fn try_move_space_to_root(space_id: SpaceId) -> DispatchResult {
         let mut space = Self::require_space(space_id)?;
+
+        if let Some(parent_id) = space.parent_id {
+            SubspaceIdsByParentId::mutate(parent_id, |ids| remove_from_vec(ids, space_id));
+            Self::update_subspace_tree_depths(parent_id, Some(Self::get_subspace_tree_depth(space_id)), None);
+        }
         space.parent_id = None;
 
         SpaceById::<T>::insert(space_id, space);
@@ -461,6 +873,9 @@ impl<T: Trait> Module<T> {
         // Check if a handle is unique across all spaces' handles:
         ensure!(Self::space_id_by_handle(handle_in_lowercase.clone()).is_none(), Error::<T>::SpaceHandleIsNotUnique);
 
+        // Check if a handle is not reserved in a shared namespace (e.g. by a profile):
+        ensure!(!T::IsHandleReserved::is_handle_reserved(&handle_in_lowercase), Error::<T>::SpaceHandleIsNotUnique);
+
         Ok(handle_in_lowercase)
     }
 
@@ -548,15 +963,24 @@ impl<T: Trait> Module<T> {
     }
 }
 
+impl<T: Trait> IsHandleReserved for Module<T> {
+    fn is_handle_reserved(handle: &[u8]) -> bool {
+        SpaceIdByHandle::contains_key(handle)
+    }
+}
+
 impl<T: Trait> SpaceForRolesProvider for Module<T> {
     type AccountId = T::AccountId;
 
     fn get_space(id: SpaceId) -> Result<SpaceForRoles<Self::AccountId>, DispatchError> {
         let space = Module::<T>::require_space(id)?;
 
+        let inherited_permissions = Module::<T>::get_inherited_permissions(&space);
+
         Ok(SpaceForRoles {
             owner: space.owner,
             permissions: space.permissions,
+            inherited_permissions,
         })
     }
 }
@@ -575,3 +999,24 @@ impl<T: Trait> BeforeSpaceCreated<T> for () {
 pub trait AfterSpaceUpdated<T: Trait> {
     fn after_space_updated(sender: T::AccountId, space: &Space<T>, old_data: SpaceUpdate);
 }
+
+/// Handler that will be called right before the space is deleted.
+/// An error returned from this handler cancels the deletion.
+pub trait BeforeSpaceDeleted<T: Trait> {
+    fn before_space_deleted(account: T::AccountId, space: &Space<T>) -> DispatchResult;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<T: Trait> BeforeSpaceDeleted<T> for Tuple {
+    fn before_space_deleted(account: T::AccountId, space: &Space<T>) -> DispatchResult {
+        for_tuples!( #( Tuple::before_space_deleted(account.clone(), space)?; )* );
+        Ok(())
+    }
+}
+
+/// Handler that will be called right after the space was removed from the storage.
+/// Use it to clean up the data that other pallets store for this space.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterSpaceDeleted<T: Trait> {
+    fn after_space_deleted(account: T::AccountId, space: &Space<T>);
+}
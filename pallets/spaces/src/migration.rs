@@ -0,0 +1,85 @@
+use frame_support::storage::{IterableStorageMap, StorageMap, StorageValue};
+use sp_std::collections::btree_map::BTreeMap;
+
+use super::*;
+
+/// Run all storage migrations that were not applied yet.
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+
+    if StorageVersion::get() == Releases::V2_0_0 {
+        weight = weight.saturating_add(migrate_to_v3::<T>());
+    }
+
+    weight
+}
+
+/// Build the `SubspaceIdsByParentId` index from the existing spaces.
+fn migrate_to_v2<T: Trait>() -> Weight {
+    let mut reads: Weight = 1;
+    let mut writes: Weight = 1;
+
+    for (space_id, space) in SpaceById::<T>::iter() {
+        reads += 1;
+
+        if let Some(parent_id) = space.parent_id {
+            SubspaceIdsByParentId::mutate(parent_id, |ids| ids.push(space_id));
+            writes += 1;
+        }
+    }
+
+    StorageVersion::put(Releases::V2_0_0);
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Count subspaces of every space by the depth of their subspace trees.
+fn migrate_to_v3<T: Trait>() -> Weight {
+    let mut reads: Weight = 1;
+    let mut writes: Weight = 1;
+    let mut depths = BTreeMap::new();
+
+    for (space_id, space) in SpaceById::<T>::iter() {
+        reads += 1;
+
+        if let Some(parent_id) = space.parent_id {
+            let index = subspace_tree_depth(space_id, &mut depths, &mut reads).saturating_sub(1) as usize;
+
+            SubspaceCountsByTreeDepth::mutate(parent_id, |counts| {
+                if counts.len() <= index {
+                    counts.resize(index + 1, 0);
+                }
+                counts[index] = counts[index].saturating_add(1);
+            });
+            writes += 1;
+        }
+    }
+
+    StorageVersion::put(Releases::V3_0_0);
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Get the depth of a subspace tree by walking all its subspaces, remembering depths that are already known.
+fn subspace_tree_depth(space_id: SpaceId, depths: &mut BTreeMap<SpaceId, u32>, reads: &mut Weight) -> u32 {
+    if let Some(depth) = depths.get(&space_id) {
+        return *depth;
+    }
+
+    // A placeholder stops the walk if an old space hierarchy has a cycle.
+    depths.insert(space_id, 1);
+
+    *reads += 1;
+    let max_subspace_depth = SubspaceIdsByParentId::get(space_id).into_iter()
+        .map(|subspace_id| subspace_tree_depth(subspace_id, depths, reads))
+        .max()
+        .unwrap_or(0);
+
+    let depth = max_subspace_depth.saturating_add(1);
+    depths.insert(space_id, depth);
+    depth
+}
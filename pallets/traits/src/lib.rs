@@ -5,7 +5,8 @@ use frame_support::dispatch::{DispatchError, DispatchResult};
 use pallet_permissions::{
   SpacePermission,
   SpacePermissions,
-  SpacePermissionsContext
+  SpacePermissionsContext,
+  InheritedSpacePermissions,
 };
 use pallet_utils::{SpaceId, User};
 
@@ -15,6 +16,7 @@ pub mod moderation;
 pub struct SpaceForRoles<AccountId> {
   pub owner: AccountId,
   pub permissions: Option<SpacePermissions>,
+  pub inherited_permissions: InheritedSpacePermissions,
 }
 
 pub trait SpaceForRolesProvider {
@@ -29,6 +31,63 @@ pub trait SpaceFollowsProvider {
   fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool;
 }
 
+/// Used to check if a handle is already reserved in another registry of handles,
+/// e.g. to share one namespace between space and profile handles.
+pub trait IsHandleReserved {
+  /// `handle` is expected to be in lowercase.
+  fn is_handle_reserved(handle: &[u8]) -> bool;
+}
+
+impl IsHandleReserved for () {
+  fn is_handle_reserved(_handle: &[u8]) -> bool {
+    false
+  }
+}
+
+/// Used to check if an account is in a personal blocklist of another account.
+pub trait IsBlockedByAccount<AccountId> {
+  fn is_blocked_by_account(account: &AccountId, blocker: &AccountId) -> bool;
+}
+
+impl<AccountId> IsBlockedByAccount<AccountId> for () {
+  fn is_blocked_by_account(_account: &AccountId, _blocker: &AccountId) -> bool {
+    false
+  }
+}
+
+/// Used to clean up storage of a reaped account in batches, as there can be too much of it
+/// to remove at once inside of a balance transfer that reaps the account.
+pub trait CleanUpReapedAccount<AccountId> {
+  /// Remove at most `limit` storage entries linked to a reaped account and return
+  /// a number of removed entries. The cleanup is complete if it is less than `limit`.
+  fn clean_up_reaped_account(who: &AccountId, limit: u32) -> u32;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<AccountId> CleanUpReapedAccount<AccountId> for Tuple {
+  fn clean_up_reaped_account(who: &AccountId, limit: u32) -> u32 {
+    let mut removed = 0;
+    // The next cleanup starts only when the previous one is complete.
+    for_tuples!( #(
+      if removed < limit {
+        removed += Tuple::clean_up_reaped_account(who, limit - removed);
+      }
+    )* );
+    removed
+  }
+}
+
+/// Used to get reputation of an account, e.g. to require a minimum reputation for a permission.
+pub trait ReputationProvider<AccountId> {
+  fn reputation_of(account: &AccountId) -> u32;
+}
+
+impl<AccountId> ReputationProvider<AccountId> for () {
+  fn reputation_of(_account: &AccountId) -> u32 {
+    0
+  }
+}
+
 pub trait PermissionChecker {
   type AccountId;
 
@@ -0,0 +1,291 @@
+//! Minimal `no_std` parser of IPFS CIDs.
+//!
+//! Supported formats:
+//! - CIDv0: base58btc encoded sha2-256 multihash (`Qm...`).
+//! - CIDv1: base32 (`b`/`B`) or base36 (`k`/`K`) multibase, `dag-pb`, `raw` or `dag-cbor` codec,
+//!   and `sha2-256`, `blake2b-256` or `blake2b-512` multihash.
+//!
+//! Every valid CID can be normalized to a canonical form: CIDv1 in a lowercase base32 multibase.
+//! The same content addressed by CIDv0 or by CIDv1 in any supported multibase has the same
+//! canonical form.
+
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+const BASE58_BTC_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+const MULTIBASE_BASE32_LOWER: u8 = b'b';
+const MULTIBASE_BASE32_UPPER: u8 = b'B';
+const MULTIBASE_BASE36_LOWER: u8 = b'k';
+const MULTIBASE_BASE36_UPPER: u8 = b'K';
+
+const CID_V0_LEN: usize = 46;
+const CID_V0_PREFIX: &[u8] = b"Qm";
+const CID_V1: u64 = 1;
+
+const CODEC_RAW: u64 = 0x55;
+const CODEC_DAG_PB: u64 = 0x70;
+const CODEC_DAG_CBOR: u64 = 0x71;
+
+const MULTIHASH_SHA2_256: u64 = 0x12;
+const MULTIHASH_BLAKE2B_256: u64 = 0xb220;
+const MULTIHASH_BLAKE2B_512: u64 = 0xb240;
+
+/// A max number of bytes in unsigned varint according to the multiformats spec.
+const MAX_VARINT_LEN: usize = 9;
+
+/// A max length of a supported CID string: CIDv1 with a blake2b-512 multihash in base32.
+/// Longer inputs are rejected before decoding, as decoding takes quadratic time in base58 and base36.
+pub const MAX_CID_LEN: usize = 113;
+
+/// Parsed binary representation of a CID.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Cid {
+    pub version: u64,
+    pub codec: u64,
+    pub multihash_code: u64,
+    pub digest: Vec<u8>,
+}
+
+impl Cid {
+    /// Parse a CID from its string (multibase) representation.
+    pub fn parse(cid: &[u8]) -> Option<Self> {
+        if cid.len() > MAX_CID_LEN {
+            return None;
+        }
+
+        if cid.len() == CID_V0_LEN && cid.starts_with(CID_V0_PREFIX) {
+            return Self::parse_v0(cid);
+        }
+
+        let (&multibase, encoded) = cid.split_first()?;
+        let bytes = match multibase {
+            MULTIBASE_BASE32_LOWER => decode_base32(encoded, false)?,
+            MULTIBASE_BASE32_UPPER => decode_base32(encoded, true)?,
+            MULTIBASE_BASE36_LOWER => decode_base36(encoded, false)?,
+            MULTIBASE_BASE36_UPPER => decode_base36(encoded, true)?,
+            _ => return None,
+        };
+
+        Self::parse_v1_bytes(&bytes)
+    }
+
+    fn parse_v0(cid: &[u8]) -> Option<Self> {
+        let bytes = decode_base_x(cid, |c| position_in(BASE58_BTC_ALPHABET, c), 58, b'1')?;
+
+        let (multihash_code, digest) = parse_multihash(&bytes)?;
+        if multihash_code != MULTIHASH_SHA2_256 {
+            return None;
+        }
+
+        Some(Cid { version: 0, codec: CODEC_DAG_PB, multihash_code, digest })
+    }
+
+    fn parse_v1_bytes(bytes: &[u8]) -> Option<Self> {
+        let (version, rest) = read_varint(bytes)?;
+        if version != CID_V1 {
+            return None;
+        }
+
+        let (codec, rest) = read_varint(rest)?;
+        if !matches!(codec, CODEC_RAW | CODEC_DAG_PB | CODEC_DAG_CBOR) {
+            return None;
+        }
+
+        let (multihash_code, digest) = parse_multihash(rest)?;
+
+        Some(Cid { version, codec, multihash_code, digest })
+    }
+
+    /// Binary representation of this CID as CIDv1.
+    pub fn to_v1_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.digest.len() + 8);
+        write_varint(CID_V1, &mut bytes);
+        write_varint(self.codec, &mut bytes);
+        write_varint(self.multihash_code, &mut bytes);
+        write_varint(self.digest.len() as u64, &mut bytes);
+        bytes.extend_from_slice(&self.digest);
+        bytes
+    }
+
+    /// Canonical string form of this CID: CIDv1 in a lowercase base32 multibase.
+    pub fn to_canonical(&self) -> Vec<u8> {
+        let mut canonical = Vec::new();
+        canonical.push(MULTIBASE_BASE32_LOWER);
+        canonical.extend(encode_base32(&self.to_v1_bytes()));
+        canonical
+    }
+}
+
+/// Check if a given string is a valid CID.
+pub fn is_valid_cid(cid: &[u8]) -> bool {
+    Cid::parse(cid).is_some()
+}
+
+/// Parse a CID and convert it to a canonical form. Returns `None` if the CID is invalid.
+pub fn normalize_cid(cid: &[u8]) -> Option<Vec<u8>> {
+    Cid::parse(cid).map(|cid| cid.to_canonical())
+}
+
+fn expected_digest_len(multihash_code: u64) -> Option<usize> {
+    match multihash_code {
+        MULTIHASH_SHA2_256 | MULTIHASH_BLAKE2B_256 => Some(32),
+        MULTIHASH_BLAKE2B_512 => Some(64),
+        _ => None,
+    }
+}
+
+/// Parse a multihash that should take all the bytes provided.
+fn parse_multihash(bytes: &[u8]) -> Option<(u64, Vec<u8>)> {
+    let (code, rest) = read_varint(bytes)?;
+    let (len, digest) = read_varint(rest)?;
+
+    let expected_len = expected_digest_len(code)?;
+    if len as usize != expected_len || digest.len() != expected_len {
+        return None;
+    }
+
+    Some((code, digest.to_vec()))
+}
+
+/// Read an unsigned varint. Only a minimal encoding is accepted,
+/// so the same number cannot be encoded in several ways.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value: u64 = 0;
+
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            // A trailing zero byte means that the varint is not minimally encoded.
+            if byte == 0 && i > 0 {
+                return None;
+            }
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+
+    None
+}
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn position_in(alphabet: &[u8], c: u8) -> Option<u8> {
+    alphabet.iter().position(|&x| x == c).map(|i| i as u8)
+}
+
+/// Decode an RFC 4648 base32 string without padding.
+fn decode_base32(encoded: &[u8], uppercase: bool) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for &c in encoded {
+        let c = if uppercase {
+            // Mixed case is not allowed inside of a single multibase.
+            if c.is_ascii_lowercase() { return None; }
+            c.to_ascii_lowercase()
+        } else {
+            c
+        };
+
+        buffer = (buffer << 5) | position_in(BASE32_ALPHABET, c)? as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // Leftover bits should be a zero padding, otherwise the encoding is not canonical.
+    if bits >= 5 || buffer != 0 {
+        return None;
+    }
+
+    Some(bytes)
+}
+
+fn encode_base32(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity((bytes.len() * 8 + 4) / 5);
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[(buffer >> bits) as usize & 0x1f]);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 0x1f]);
+    }
+
+    encoded
+}
+
+fn decode_base36(encoded: &[u8], uppercase: bool) -> Option<Vec<u8>> {
+    decode_base_x(
+        encoded,
+        |c| {
+            if uppercase {
+                if c.is_ascii_lowercase() { return None; }
+                position_in(BASE36_ALPHABET, c.to_ascii_lowercase())
+            } else {
+                position_in(BASE36_ALPHABET, c)
+            }
+        },
+        36,
+        b'0',
+    )
+}
+
+/// Decode a big-endian number encoded with an arbitrary alphabet, where leading
+/// `zero_char`s stand for leading zero bytes (as in base58btc and base36).
+fn decode_base_x<F>(encoded: &[u8], digit_of: F, base: u32, zero_char: u8) -> Option<Vec<u8>>
+    where F: Fn(u8) -> Option<u8>
+{
+    if encoded.is_empty() || encoded.len() > MAX_CID_LEN {
+        return None;
+    }
+
+    // Little-endian bytes of the decoded number.
+    let mut number: Vec<u8> = Vec::with_capacity(encoded.len());
+
+    for &c in encoded {
+        let mut carry = digit_of(c)? as u32;
+
+        for byte in number.iter_mut() {
+            carry += (*byte as u32) * base;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            number.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = encoded.iter().take_while(|&&c| c == zero_char).count();
+
+    let mut bytes = Vec::with_capacity(leading_zeros + number.len());
+    bytes.resize(leading_zeros, 0);
+    bytes.extend(number.iter().rev());
+
+    Some(bytes)
+}
@@ -0,0 +1,119 @@
+//! Minimal `no_std` parser of Hypercore protocol ids.
+//!
+//! Supported format: `[hyper://]<key>[+<version>][/<path>]`, where:
+//! - `key` is a 32-byte public key of a Hypercore feed encoded either as 64 hex chars
+//!   or as 52 z-base-32 chars;
+//! - `version` is a decimal version (length) of a feed;
+//! - `path` is a path of a file inside of a feed that consists of visible ASCII chars.
+
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+const HYPER_SCHEME: &[u8] = b"hyper://";
+const Z_BASE32_ALPHABET: &[u8] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+const KEY_LEN: usize = 32;
+const HEX_KEY_LEN: usize = KEY_LEN * 2;
+/// 256 bits of a key take 52 z-base-32 chars with 4 zero bits of padding.
+const Z_BASE32_KEY_LEN: usize = 52;
+
+const VERSION_SEPARATOR: u8 = b'+';
+const PATH_SEPARATOR: u8 = b'/';
+const MAX_PATH_LEN: usize = 512;
+
+/// Parsed Hypercore id.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct HyperId {
+    pub key: [u8; KEY_LEN],
+    pub version: Option<u64>,
+    pub path: Option<Vec<u8>>,
+}
+
+impl HyperId {
+    /// Parse a Hypercore id from its string representation.
+    pub fn parse(id: &[u8]) -> Option<Self> {
+        let id = id.strip_prefix(HYPER_SCHEME).unwrap_or(id);
+
+        let (key_and_version, path) = match id.iter().position(|&c| c == PATH_SEPARATOR) {
+            Some(i) => (&id[..i], Some(parse_path(&id[i..])?)),
+            None => (id, None),
+        };
+
+        let (encoded_key, version) = match key_and_version.iter().position(|&c| c == VERSION_SEPARATOR) {
+            Some(i) => (&key_and_version[..i], Some(parse_version(&key_and_version[i + 1..])?)),
+            None => (key_and_version, None),
+        };
+
+        let key = match encoded_key.len() {
+            HEX_KEY_LEN => decode_hex_key(encoded_key)?,
+            Z_BASE32_KEY_LEN => decode_z_base32_key(encoded_key)?,
+            _ => return None,
+        };
+
+        Some(HyperId { key, version, path })
+    }
+}
+
+/// Check if a given string is a valid Hypercore id.
+pub fn is_valid_hyper_id(id: &[u8]) -> bool {
+    HyperId::parse(id).is_some()
+}
+
+fn parse_version(version: &[u8]) -> Option<u64> {
+    if version.is_empty() {
+        return None;
+    }
+
+    version.iter().try_fold(0u64, |acc, &c| {
+        let digit = (c as char).to_digit(10)?;
+        acc.checked_mul(10)?.checked_add(digit as u64)
+    })
+}
+
+/// A path should start with `/` and consist of visible ASCII chars only.
+fn parse_path(path: &[u8]) -> Option<Vec<u8>> {
+    let is_valid = path.len() <= MAX_PATH_LEN
+        && path.first() == Some(&PATH_SEPARATOR)
+        && path.iter().all(|c| c.is_ascii_graphic());
+
+    if is_valid { Some(path.to_vec()) } else { None }
+}
+
+fn decode_hex_key(encoded: &[u8]) -> Option<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+
+    for (byte, pair) in key.iter_mut().zip(encoded.chunks(2)) {
+        let high = (pair[0] as char).to_digit(16)?;
+        let low = (pair[1] as char).to_digit(16)?;
+        *byte = (high << 4 | low) as u8;
+    }
+
+    Some(key)
+}
+
+fn decode_z_base32_key(encoded: &[u8]) -> Option<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    let mut len = 0;
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for &c in encoded {
+        let value = Z_BASE32_ALPHABET.iter().position(|&x| x == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            key[len] = (buffer >> bits) as u8;
+            len += 1;
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // Padding bits should be zero, so every key has only one z-base-32 representation.
+    if len != KEY_LEN || buffer != 0 {
+        return None;
+    }
+
+    Some(key)
+}
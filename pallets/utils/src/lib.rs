@@ -7,8 +7,10 @@ use frame_support::{
     traits::{
         Currency, Get,
         Imbalance, OnUnbalanced,
+        ReservableCurrency,
     },
 };
+use sp_runtime::traits::{Saturating, Zero};
 use sp_runtime::RuntimeDebug;
 use sp_std::{
     collections::btree_set::BTreeSet,
@@ -16,6 +18,9 @@ use sp_std::{
 };
 use frame_system::{self as system};
 
+pub mod cid;
+pub mod hyper;
+
 #[cfg(test)]
 mod mock;
 pub mod mock_functions;
@@ -54,12 +59,23 @@ pub enum Content {
     None,
     /// A raw vector of bytes.
     Raw(Vec<u8>),
-    /// IPFS CID v0 of content.
+    /// IPFS CID (v0 or v1) of content.
     IPFS(Vec<u8>),
-    /// Hypercore protocol (former DAT) id of content.
+    /// Hypercore protocol (former DAT) id of content: a feed key with optional version and path.
     Hyper(Vec<u8>),
 }
 
+/// An entity that can store `Raw` content on chain and hold a deposit for it.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum ContentHolder<AccountId> {
+    Space(SpaceId),
+    /// A post or a comment by its id.
+    Post(u64),
+    Profile(AccountId),
+    /// A space role by its id.
+    Role(u64),
+}
+
 impl Content {
     pub fn is_none(&self) -> bool {
         self == &Self::None
@@ -80,18 +96,28 @@ pub trait Trait: system::Trait + pallet_timestamp::Trait
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     /// The currency mechanism.
-    type Currency: Currency<Self::AccountId>;
+    type Currency: ReservableCurrency<Self::AccountId>;
 
     /// Minimal length of space/profile handle
     type MinHandleLen: Get<u32>;
 
     /// Max length of a space handle.
     type MaxHandleLen: Get<u32>;
+
+    /// Max length of `Raw` content in bytes.
+    type MaxRawContentLen: Get<u32>;
+
+    /// The amount reserved from a content owner per every byte of `Raw` content.
+    type RawContentDepositPerByte: Get<BalanceOf<Self>>;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as UtilsModule {
         pub TreasuryAccount get(fn treasury_account) build(|config| config.treasury_account.clone()): T::AccountId;
+
+        /// A deposit reserved for `Raw` content of an entity (key): an account that paid it and the amount.
+        pub ContentDepositByHolder get(fn content_deposit_by_holder):
+            map hasher(blake2_128_concat) ContentHolder<T::AccountId> => Option<(T::AccountId, BalanceOf<T>)>;
     }
     add_extra_genesis {
         config(treasury_account): T::AccountId;
@@ -112,6 +138,10 @@ decl_module! {
 
         const MaxHandleLen: u32 = T::MaxHandleLen::get();
 
+        const MaxRawContentLen: u32 = T::MaxRawContentLen::get();
+
+        const RawContentDepositPerByte: BalanceOf<T> = T::RawContentDepositPerByte::get();
+
         // Initializing errors
         type Error = Error<T>;
 
@@ -130,10 +160,10 @@ decl_error! {
         PostIsBlocked,
         /// IPFS CID is invalid.
         InvalidIpfsCid,
-        /// `Raw` content type is not yet supported.
-        RawContentTypeNotSupported,
-        /// `Hyper` content type is not yet supported.
-        HypercoreContentTypeNotSupported,
+        /// `Raw` content is longer than `MaxRawContentLen`.
+        RawContentIsTooLong,
+        /// Hypercore id is invalid.
+        InvalidHypercoreId,
         /// Space handle is too short.
         HandleIsTooShort,
         /// Space handle is too long.
@@ -142,6 +172,8 @@ decl_error! {
         HandleContainsInvalidChars,
         /// Content type is `None`.
         ContentIsEmpty,
+        /// Account is blocked by another account it tries to interact with.
+        AccountIsBlockedByAccount,
     }
 }
 
@@ -178,15 +210,30 @@ impl<T: Trait> Module<T> {
     pub fn is_valid_content(content: Content) -> DispatchResult {
         match content {
             Content::None => Ok(()),
-            Content::Raw(_) => Err(Error::<T>::RawContentTypeNotSupported.into()),
+            Content::Raw(raw) => {
+                ensure!(!raw.is_empty(), Error::<T>::ContentIsEmpty);
+                ensure!(raw.len() <= T::MaxRawContentLen::get() as usize, Error::<T>::RawContentIsTooLong);
+                Ok(())
+            },
             Content::IPFS(ipfs_cid) => {
-                let len = ipfs_cid.len();
-                // IPFS CID v0 is 46 bytes.
-                // IPFS CID v1 is 59 bytes.df-integration-tests/src/lib.rs:272:5
-                ensure!(len == 46 || len == 59, Error::<T>::InvalidIpfsCid);
+                ensure!(cid::is_valid_cid(&ipfs_cid), Error::<T>::InvalidIpfsCid);
+                Ok(())
+            },
+            Content::Hyper(hyper_id) => {
+                ensure!(hyper::is_valid_hyper_id(&hyper_id), Error::<T>::InvalidHypercoreId);
                 Ok(())
             },
-            Content::Hyper(_) => Err(Error::<T>::HypercoreContentTypeNotSupported.into())
+        }
+    }
+
+    /// Convert an IPFS CID of content to its canonical form (CIDv1 in base32),
+    /// so the same content cannot be represented by different ids. Other content is returned as is.
+    pub fn normalize_content(content: Content) -> Result<Content, DispatchError> {
+        match content {
+            Content::IPFS(ipfs_cid) => cid::normalize_cid(&ipfs_cid)
+                .map(Content::IPFS)
+                .ok_or_else(|| Error::<T>::InvalidIpfsCid.into()),
+            _ => Ok(content),
         }
     }
 
@@ -233,6 +280,57 @@ impl<T: Trait> Module<T> {
         Ok(handle_in_lowercase)
     }
 
+    /// Get the amount that should be reserved from a content owner to store this content on chain.
+    /// Only `Raw` content requires a deposit, other content types store just an id.
+    pub fn content_deposit(content: &Content) -> BalanceOf<T> {
+        match content {
+            Content::Raw(raw) => T::RawContentDepositPerByte::get().saturating_mul((raw.len() as u32).into()),
+            _ => Zero::zero(),
+        }
+    }
+
+    /// Reserve a deposit for content of a new entity and remember who paid how much,
+    /// so exactly this amount is returned later, even if the deposit per byte changes.
+    pub fn reserve_content_deposit(
+        depositor: &T::AccountId,
+        holder: ContentHolder<T::AccountId>,
+        content: &Content
+    ) -> DispatchResult {
+        let deposit = Self::content_deposit(content);
+        if !deposit.is_zero() {
+            T::Currency::reserve(depositor, deposit)?;
+            <ContentDepositByHolder<T>>::insert(holder, (depositor.clone(), deposit));
+        }
+        Ok(())
+    }
+
+    /// Return a deposit reserved for content of an entity to the account that paid it.
+    pub fn unreserve_content_deposit(holder: &ContentHolder<T::AccountId>) {
+        if let Some((depositor, deposit)) = <ContentDepositByHolder<T>>::take(holder) {
+            T::Currency::unreserve(&depositor, deposit);
+        }
+    }
+
+    /// Reserve a deposit for new content of an entity from a depositor,
+    /// then return a deposit for its previous content to the account that paid it.
+    pub fn update_content_deposit(
+        depositor: &T::AccountId,
+        holder: ContentHolder<T::AccountId>,
+        new_content: &Content
+    ) -> DispatchResult {
+        let deposit = Self::content_deposit(new_content);
+        if !deposit.is_zero() {
+            T::Currency::reserve(depositor, deposit)?;
+        }
+
+        Self::unreserve_content_deposit(&holder);
+
+        if !deposit.is_zero() {
+            <ContentDepositByHolder<T>>::insert(holder, (depositor.clone(), deposit));
+        }
+        Ok(())
+    }
+
     /// Ensure that a given content is not `None`.
     pub fn ensure_content_is_some(content: &Content) -> DispatchResult {
         ensure!(content.is_some(), Error::<T>::ContentIsEmpty);
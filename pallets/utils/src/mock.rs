@@ -80,6 +80,8 @@ impl pallet_balances::Trait for Test {
 parameter_types! {
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  pub const MaxRawContentLen: u32 = 1024;
+  pub const RawContentDepositPerByte: u64 = 1;
 }
 
 impl Trait for Test {
@@ -87,6 +89,8 @@ impl Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type MaxRawContentLen = MaxRawContentLen;
+    type RawContentDepositPerByte = RawContentDepositPerByte;
 }
 
 type System = system::Module<Test>;
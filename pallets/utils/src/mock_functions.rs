@@ -7,3 +7,11 @@ pub fn valid_content_ipfs() -> Content {
 pub fn invalid_content_ipfs() -> Content {
     Content::IPFS(b"QmRAQB6DaazhR8".to_vec())
 }
+
+pub fn valid_content_hyper() -> Content {
+    Content::Hyper(b"hyper://000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f+12/index.html".to_vec())
+}
+
+pub fn invalid_content_hyper() -> Content {
+    Content::Hyper(b"hyper://000102030405060708090a0b0c0d0e0f".to_vec())
+}
@@ -1,4 +1,4 @@
-use crate::{mock::*, remove_from_vec, log_2};
+use crate::{mock::*, mock_functions::valid_content_ipfs, remove_from_vec, log_2, cid, hyper, Content, Error, Module};
 
 use sp_std::iter::FromIterator;
 
@@ -91,3 +91,164 @@ fn convert_users_vec_to_btree_set_should_work() {
         );
     });
 }
+
+#[test]
+fn is_valid_content_should_accept_raw_content() {
+    ExtBuilder::build().execute_with(|| {
+        assert!(Module::<Test>::is_valid_content(Content::Raw(b"Hello, Subsocial!".to_vec())).is_ok());
+
+        let max_len_content = Content::Raw(vec![b'a'; MaxRawContentLen::get() as usize]);
+        assert!(Module::<Test>::is_valid_content(max_len_content).is_ok());
+    });
+}
+
+#[test]
+fn is_valid_content_should_fail_when_raw_content_is_too_long() {
+    ExtBuilder::build().execute_with(|| {
+        let too_long_content = Content::Raw(vec![b'a'; MaxRawContentLen::get() as usize + 1]);
+        assert_eq!(
+            Module::<Test>::is_valid_content(too_long_content),
+            Err(Error::<Test>::RawContentIsTooLong.into())
+        );
+    });
+}
+
+#[test]
+fn is_valid_content_should_fail_when_raw_content_is_empty() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(
+            Module::<Test>::is_valid_content(Content::Raw(vec![])),
+            Err(Error::<Test>::ContentIsEmpty.into())
+        );
+    });
+}
+
+#[test]
+fn content_deposit_should_be_charged_only_for_raw_content() {
+    ExtBuilder::build().execute_with(|| {
+        let raw = b"Hello".to_vec();
+        let expected_deposit = RawContentDepositPerByte::get() * raw.len() as u64;
+
+        assert_eq!(Module::<Test>::content_deposit(&Content::Raw(raw)), expected_deposit);
+        assert_eq!(Module::<Test>::content_deposit(&Content::None), 0);
+        assert_eq!(Module::<Test>::content_deposit(&valid_content_ipfs()), 0);
+    });
+}
+
+const CID_V0: &[u8] = b"QmRAQB6YaCaidP37UdDnjFY5aQuiBrbqdyoW1CaDgwxkD4";
+const CID_V1_BASE32: &[u8] = b"bafybeibj6lixxzqqufnyigbgolgvwlbrict5nwms6qzlgts5ctj4liyxt4";
+const CID_V1_BASE32_UPPER: &[u8] = b"BAFYBEIBJ6LIXXZQQUFNYIGBGOLGVWLBRICT5NWMS6QZLGTS5CTJ4LIYXT4";
+const CID_V1_BASE36_UPPER: &[u8] = b"K2JMTXSEQZ46SOLM5WN95OZ85WNBQOJ14L9QCJH3KKUBQ21GE0RXET27";
+const CID_V1_BLAKE2B_512: &[u8] =
+    b"bafy4bzacibgf3kyaqrm2gqfnfnaq62yl6ocrbons6qpk477fe4j3roi77msortlbhvw3jdvtxe7donrpbejciqwj3y567py6f23wr6iqosuh6h23";
+
+#[test]
+fn is_valid_cid_should_accept_supported_cids() {
+    assert!(cid::is_valid_cid(CID_V0));
+    assert!(cid::is_valid_cid(CID_V1_BASE32));
+    assert!(cid::is_valid_cid(CID_V1_BASE32_UPPER));
+    assert!(cid::is_valid_cid(CID_V1_BASE36_UPPER));
+
+    // CIDv1 with a raw codec.
+    assert!(cid::is_valid_cid(b"bafkreibj6lixxzqqufnyigbgolgvwlbrict5nwms6qzlgts5ctj4liyxt4"));
+    // CIDv1 with a dag-cbor codec and blake2b-256 multihash.
+    assert!(cid::is_valid_cid(b"bafy2bzaceaaacaqdaqcqmbyibefawdanbyhraeiscmkbkfqxdamrugy4dupb6"));
+    // The longest supported CID: CIDv1 with a dag-cbor codec and blake2b-512 multihash.
+    assert_eq!(CID_V1_BLAKE2B_512.len(), cid::MAX_CID_LEN);
+    assert!(cid::is_valid_cid(CID_V1_BLAKE2B_512));
+}
+
+#[test]
+fn is_valid_cid_should_reject_invalid_cids() {
+    // Not a base58 char at the end.
+    assert!(!cid::is_valid_cid(b"QmRAQB6YaCaidP37UdDnjFY5aQuiBrbqdyoW1CaDgwxkD0"));
+    // Right length and prefix, but not a sha2-256 multihash.
+    assert!(!cid::is_valid_cid(b"Qmzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz"));
+    // Unsupported codec.
+    assert!(!cid::is_valid_cid(b"bafqreibj6lixxzqqufnyigbgolgvwlbrict5nwms6qzlgts5ctj4liyxt4"));
+    // Mixed case in base32.
+    assert!(!cid::is_valid_cid(b"bafybeibj6lixxzqqufnyigbgolgvwlbrict5nwms6qzlgts5ctj4LIYXT4"));
+    // Digest is shorter than declared in the multihash.
+    assert!(!cid::is_valid_cid(b"bafybeibj6lixxzqqufnyigbgolgvwlbrict5nwms6qzlgts5ctj4liyx"));
+    // Unsupported multibase.
+    assert!(!cid::is_valid_cid(b"zdj7WhuEjrB52m1BisYCtmjH1hSKa7yZ3jEZ9JcXaFRD51wVz"));
+    assert!(!cid::is_valid_cid(b""));
+    assert!(!cid::is_valid_cid(b"b"));
+    // Too long input is rejected before decoding.
+    assert!(!cid::is_valid_cid(&[b'k'; cid::MAX_CID_LEN + 1]));
+    assert!(!cid::is_valid_cid(&[b'1'; 10_000]));
+}
+
+#[test]
+fn normalize_cid_should_return_the_same_cid_for_all_encodings() {
+    assert_eq!(cid::normalize_cid(CID_V0), Some(CID_V1_BASE32.to_vec()));
+    assert_eq!(cid::normalize_cid(CID_V1_BASE32), Some(CID_V1_BASE32.to_vec()));
+    assert_eq!(cid::normalize_cid(CID_V1_BASE32_UPPER), Some(CID_V1_BASE32.to_vec()));
+    assert_eq!(cid::normalize_cid(CID_V1_BASE36_UPPER), Some(CID_V1_BASE32.to_vec()));
+}
+
+#[test]
+fn normalize_content_should_fail_with_invalid_ipfs_cid() {
+    ExtBuilder::build().execute_with(|| {
+        assert_eq!(
+            Module::<Test>::normalize_content(Content::IPFS(b"QmRAQB6DaazhR8".to_vec())),
+            Err(Error::<Test>::InvalidIpfsCid.into())
+        );
+        assert_eq!(
+            Module::<Test>::normalize_content(Content::IPFS(CID_V0.to_vec())),
+            Ok(Content::IPFS(CID_V1_BASE32.to_vec()))
+        );
+    });
+}
+
+const HYPER_HEX_KEY: &[u8] = b"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const HYPER_Z_BASE32_KEY: &[u8] = b"yyyoryarywdyqnyjbefoadeqbhebnrounoktcfaadrpbs8y7daxo";
+
+#[test]
+fn parse_hyper_id_should_work() {
+    let expected_key: Vec<u8> = (0..32).collect();
+
+    let hex_id = hyper::HyperId::parse(HYPER_HEX_KEY).unwrap();
+    assert_eq!(hex_id.key.to_vec(), expected_key);
+    assert_eq!(hex_id.version, None);
+    assert_eq!(hex_id.path, None);
+
+    let z_base32_id = hyper::HyperId::parse(HYPER_Z_BASE32_KEY).unwrap();
+    assert_eq!(z_base32_id, hex_id);
+
+    let full_id = [b"hyper://", HYPER_HEX_KEY, b"+12/blog/index.html"].concat();
+    let full_id = hyper::HyperId::parse(&full_id).unwrap();
+    assert_eq!(full_id.key.to_vec(), expected_key);
+    assert_eq!(full_id.version, Some(12));
+    assert_eq!(full_id.path, Some(b"/blog/index.html".to_vec()));
+}
+
+#[test]
+fn is_valid_hyper_id_should_reject_invalid_ids() {
+    // A key is too short.
+    assert!(!hyper::is_valid_hyper_id(&HYPER_HEX_KEY[..62]));
+    // Not a hex char in a key.
+    assert!(!hyper::is_valid_hyper_id(&[&HYPER_HEX_KEY[..63], b"g"].concat()));
+    // Non-zero padding bits in z-base-32 key.
+    assert!(!hyper::is_valid_hyper_id(&[&HYPER_Z_BASE32_KEY[..51], b"a"].concat()));
+    // Empty version.
+    assert!(!hyper::is_valid_hyper_id(&[HYPER_HEX_KEY, b"+"].concat()));
+    // Version is not a number.
+    assert!(!hyper::is_valid_hyper_id(&[HYPER_HEX_KEY, b"+v1"].concat()));
+    // Whitespace in a path.
+    assert!(!hyper::is_valid_hyper_id(&[HYPER_HEX_KEY, b"/my post.md"].concat()));
+    // Unsupported scheme.
+    assert!(!hyper::is_valid_hyper_id(&[b"dat://", HYPER_HEX_KEY].concat()));
+    assert!(!hyper::is_valid_hyper_id(b""));
+}
+
+#[test]
+fn is_valid_content_should_validate_hyper_content() {
+    ExtBuilder::build().execute_with(|| {
+        assert!(Module::<Test>::is_valid_content(Content::Hyper(HYPER_HEX_KEY.to_vec())).is_ok());
+        assert_eq!(
+            Module::<Test>::is_valid_content(Content::Hyper(b"hyper://abc".to_vec())),
+            Err(Error::<Test>::InvalidHypercoreId.into())
+        );
+    });
+}
@@ -44,6 +44,8 @@ use pallet_contracts;
 use pallet_transaction_payment;
 use pallet_contracts_rpc_runtime_api;
 use pallet_contracts_primitives;
+use pallet_posts::PostId;
+use pallet_scores::ScoreContribution;
 
 
 pub mod constants;
@@ -119,10 +121,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("subsocial"),
 	impl_name: create_runtime_str!("dappforce-subsocial"),
 	authoring_version: 0,
-	spec_version: 10,
+	spec_version: 12,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -177,7 +179,7 @@ impl frame_system::Trait for Runtime {
     /// The identifier used to distinguish between accounts.
     type AccountId = AccountId;
     /// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-    type Lookup = multiaddress::AccountIdLookup<AccountId, ()>;
+    type Lookup = multiaddress::AccountIdLookup<AccountId, (), HandleResolver>;
     /// The header type.
     type Header = generic::Header<BlockNumber, BlakeTwo256>;
     /// The ubiquitous event type.
@@ -213,7 +215,8 @@ impl frame_system::Trait for Runtime {
     /// What to do if a new account is created.
     type OnNewAccount = ();
     /// What to do if an account is fully reaped from the system.
-    type OnKilledAccount = ();
+    /// Profiles queue the account, so other pallets clean it up in batches (see `ReapedAccountCleanup`).
+    type OnKilledAccount = Profiles;
     /// Weight information for the extrinsics of this pallet.
     type SystemWeightInfo = ();
 }
@@ -368,6 +371,8 @@ impl pallet_utility::Trait for Runtime {
 parameter_types! {
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  pub const MaxRawContentLen: u32 = 1024;
+  pub const RawContentDepositPerByte: Balance = deposit(0, 1);
 }
 
 impl pallet_utils::Trait for Runtime {
@@ -375,6 +380,12 @@ impl pallet_utils::Trait for Runtime {
 	type Currency = Balances;
 	type MinHandleLen = MinHandleLen;
 	type MaxHandleLen = MaxHandleLen;
+	type MaxRawContentLen = MaxRawContentLen;
+	type RawContentDepositPerByte = RawContentDepositPerByte;
+}
+
+impl pallet_blocklists::Trait for Runtime {
+	type Event = Event;
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -385,14 +396,21 @@ impl pallet_permissions::Trait for Runtime {
 
 parameter_types! {
   pub const MaxCommentDepth: u32 = 10;
+  pub const MaxRepliesToProcessPerDeletePost: u16 = 100;
 }
 
 impl pallet_posts::Trait for Runtime {
 	type Event = Event;
 	type MaxCommentDepth = MaxCommentDepth;
 	type PostScores = Scores;
+	type MaxRepliesToProcessPerDeletePost = MaxRepliesToProcessPerDeletePost;
 	type AfterPostUpdated = PostHistory;
+	type AfterPostDeleted = (Scores, Reactions, PostHistory);
 	type IsPostBlocked = ()/*Moderation*/;
+	type IsBlockedByAccount = Blocklists;
+	type ScheduledCall = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
 }
 
 parameter_types! {}
@@ -405,37 +423,58 @@ impl pallet_profile_follows::Trait for Runtime {
 	type Event = Event;
 	type BeforeAccountFollowed = Scores;
 	type BeforeAccountUnfollowed = Scores;
+	type IsBlockedByAccount = Blocklists;
 }
 
-parameter_types! {}
+parameter_types! {
+	pub ProfileHandleDeposit: Balance = 50 * CENTS;
+	pub const MaxReapedAccountEntriesPerBlock: u32 = 100;
+}
 
 impl pallet_profiles::Trait for Runtime {
 	type Event = Event;
 	type AfterProfileUpdated = ProfileHistory;
+	type HandleDeposit = ProfileHandleDeposit;
+	type IsHandleReserved = Spaces;
+	type ReapedAccountCleanup = (ProfileFollows, SpaceFollows, Roles, Blocklists, Scores, Reactions);
+	type MaxReapedAccountEntriesPerBlock = MaxReapedAccountEntriesPerBlock;
 }
 
 parameter_types! {}
 
 impl pallet_profile_history::Trait for Runtime {}
 
-parameter_types! {}
+parameter_types! {
+  // Unicode code points of: thumbs up, red heart, laughing, surprised, crying, fire and party popper.
+  pub SupportedEmojis: Vec<pallet_reactions::EmojiCode> =
+    vec![0x1F44D, 0x2764, 0x1F602, 0x1F62E, 0x1F622, 0x1F525, 0x1F389];
+  pub const MaxReactionsToCleanUpPerBlock: u32 = 500;
+}
 
 impl pallet_reactions::Trait for Runtime {
 	type Event = Event;
 	type PostReactionScores = Scores;
+	type SupportedEmojis = SupportedEmojis;
+	type MaxReactionsToCleanUpPerBlock = MaxReactionsToCleanUpPerBlock;
+	type WeightInfo = pallet_reactions::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+  pub const MaxExpiredRoleEntriesPerBlock: u32 = 500;
+  pub const MaxRoleEntriesToCleanUpPerBlock: u32 = 500;
 }
 
 impl pallet_roles::Trait for Runtime {
 	type Event = Event;
 	type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+	type MaxExpiredRoleEntriesPerBlock = MaxExpiredRoleEntriesPerBlock;
+	type MaxRoleEntriesToCleanUpPerBlock = MaxRoleEntriesToCleanUpPerBlock;
 	type Spaces = Spaces;
 	type SpaceFollows = SpaceFollows;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
+	type Reputation = Scores;
 }
 
 parameter_types! {
@@ -450,6 +489,12 @@ parameter_types! {
   pub const ShareCommentActionWeight: i16 = 5;
   pub const UpvoteCommentActionWeight: i16 = 4;
   pub const DownvoteCommentActionWeight: i16 = -2;
+
+  pub const ReputationDecayPeriod: BlockNumber = DAYS * 30;
+  pub const ReputationDecayPercent: u8 = 5;
+  pub const ReputationDecayFloor: u32 = 1;
+  pub const MaxDecayedAccountsPerBlock: u32 = 50;
+  pub const MaxPostScoresToCleanUpPerBlock: u32 = 500;
 }
 
 impl pallet_scores::Trait for Runtime {
@@ -466,14 +511,25 @@ impl pallet_scores::Trait for Runtime {
 	type ShareCommentActionWeight = ShareCommentActionWeight;
 	type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
 	type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+	type ReputationDecayPeriod = ReputationDecayPeriod;
+	type ReputationDecayPercent = ReputationDecayPercent;
+	type ReputationDecayFloor = ReputationDecayFloor;
+	type MaxDecayedAccountsPerBlock = MaxDecayedAccountsPerBlock;
+	type MaxPostScoresToCleanUpPerBlock = MaxPostScoresToCleanUpPerBlock;
+
+	type UpdateWeightsOrigin = EnsureRoot<AccountId>;
 }
 
-parameter_types! {}
+parameter_types! {
+  pub const MaxSpaceFollowersToCleanUpPerBlock: u32 = 500;
+}
 
 impl pallet_space_follows::Trait for Runtime {
 	type Event = Event;
 	type BeforeSpaceFollowed = Scores;
 	type BeforeSpaceUnfollowed = Scores;
+	type MaxSpaceFollowersToCleanUpPerBlock = MaxSpaceFollowersToCleanUpPerBlock;
 }
 
 parameter_types! {}
@@ -484,6 +540,7 @@ impl pallet_space_ownership::Trait for Runtime {
 
 parameter_types! {
 	pub HandleDeposit: Balance = 50 * CENTS;
+	pub const MaxSpaceDepth: u32 = 10;
 }
 
 impl pallet_spaces::Trait for Runtime {
@@ -493,9 +550,13 @@ impl pallet_spaces::Trait for Runtime {
 	type SpaceFollows = SpaceFollows;
 	type BeforeSpaceCreated = SpaceFollows;
 	type AfterSpaceUpdated = SpaceHistory;
+	type BeforeSpaceDeleted = SpaceFollows;
+	type AfterSpaceDeleted = (Roles, SpaceHistory, SpaceOwnership, Reactions/*, Moderation*/);
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
 	type HandleDeposit = HandleDeposit;
+	type IsHandleReserved = Profiles;
+	type MaxSpaceDepth = MaxSpaceDepth;
 }
 
 parameter_types! {}
@@ -600,15 +661,15 @@ construct_runtime!(
 		// Subsocial custom pallets:
 
 		Permissions: pallet_permissions::{Module, Call},
-		Posts: pallet_posts::{Module, Call, Storage, Event<T>},
+		Posts: pallet_posts::{Module, Call, Storage, Event<T>, Config},
 		PostHistory: pallet_post_history::{Module, Storage},
-		ProfileFollows: pallet_profile_follows::{Module, Call, Storage, Event<T>},
-		Profiles: pallet_profiles::{Module, Call, Storage, Event<T>},
+		ProfileFollows: pallet_profile_follows::{Module, Call, Storage, Event<T>, Config},
+		Profiles: pallet_profiles::{Module, Call, Storage, Event<T>, Config},
 		ProfileHistory: pallet_profile_history::{Module, Storage},
-		Reactions: pallet_reactions::{Module, Call, Storage, Event<T>},
-		Roles: pallet_roles::{Module, Call, Storage, Event<T>},
-		Scores: pallet_scores::{Module, Call, Storage, Event<T>},
-		SpaceFollows: pallet_space_follows::{Module, Call, Storage, Event<T>},
+		Reactions: pallet_reactions::{Module, Call, Storage, Event<T>, Config},
+		Roles: pallet_roles::{Module, Call, Storage, Event<T>, Config},
+		Scores: pallet_scores::{Module, Call, Storage, Event<T>, Config},
+		SpaceFollows: pallet_space_follows::{Module, Call, Storage, Event<T>, Config},
 		SpaceHistory: pallet_space_history::{Module, Storage},
 		SpaceOwnership: pallet_space_ownership::{Module, Call, Storage, Event<T>},
 		Spaces: pallet_spaces::{Module, Call, Storage, Event<T>, Config<T>},
@@ -622,12 +683,32 @@ construct_runtime!(
 		// Donations: pallet_donations::{Module, Call, Storage, Event<T>},
 		// Subscriptions: pallet_subscriptions::{Module, Call, Storage, Event<T>},
         Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
+
+		// Pallets added after the launch go last, so indexes of existing pallets stay the same.
+
+		Blocklists: pallet_blocklists::{Module, Call, Storage, Event<T>},
 	}
 );
 
 /// The address format for describing accounts.
 mod multiaddress;
 pub type Address = multiaddress::MultiAddress<AccountId, ()>;
+
+/// Resolves a profile handle to a profile account, or a space handle to a space owner.
+/// If profiles and spaces do not share a namespace of handles, a profile handle takes precedence.
+pub struct HandleResolver;
+impl multiaddress::ResolveHandle<AccountId> for HandleResolver {
+	fn resolve_handle(handle: &[u8]) -> Option<AccountId> {
+		let handle = handle.strip_prefix(b"@").unwrap_or(handle);
+		let handle_in_lowercase = Utils::lowercase_handle(handle.to_vec());
+
+		Profiles::profile_id_by_handle(&handle_in_lowercase).or_else(|| {
+			Spaces::space_id_by_handle(&handle_in_lowercase)
+				.and_then(|space_id| Spaces::space_by_id(space_id))
+				.map(|space| space.owner)
+		})
+	}
+}
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -819,6 +900,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_reactions, Reactions);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -859,4 +941,14 @@ impl_runtime_apis! {
             Contracts::rent_projection(address)
         }
     }
+
+	impl pallet_scores_rpc_runtime_api::ScoresApi<Block, AccountId> for Runtime {
+		fn get_post_score_contributions(post_id: PostId) -> Vec<ScoreContribution<AccountId>> {
+			Scores::post_score_contributions(post_id)
+		}
+
+		fn get_account_reputation_contributions(account: AccountId) -> Vec<ScoreContribution<AccountId>> {
+			Scores::account_reputation_contributions(account)
+		}
+	}
 }
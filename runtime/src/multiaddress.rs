@@ -16,8 +16,8 @@
 // limitations under the License.
 
 //! MultiAddress type is a wrapper for multiple downstream account formats.
-//! Copied from `sp_runtime::multiaddress`, and can be removed and replaced when
-//! updating to a newer version of Substrate.
+//! Copied from `sp_runtime::multiaddress` and extended with a `Handle` variant
+//! that is resolved to an account of a profile or a space owner.
 
 use codec::{Encode, Decode, Codec};
 use sp_std::{vec::Vec, marker::PhantomData, fmt::Debug};
@@ -37,6 +37,8 @@ pub enum MultiAddress<AccountId, AccountIndex> {
     Address32([u8; 32]),
     /// Its a 20 byte representation.
     Address20([u8; 20]),
+    /// It's a handle of a profile or a space, optionally prefixed with `@`.
+    Handle(Vec<u8>),
 }
 
 #[cfg(feature = "std")]
@@ -51,6 +53,7 @@ impl<AccountId, AccountIndex> std::fmt::Display for MultiAddress<AccountId, Acco
             MultiAddress::Raw(inner) => write!(f, "MultiAddress::Raw({})", HexDisplay::from(inner)),
             MultiAddress::Address32(inner) => write!(f, "MultiAddress::Address32({})", HexDisplay::from(inner)),
             MultiAddress::Address20(inner) => write!(f, "MultiAddress::Address20({})", HexDisplay::from(inner)),
+            MultiAddress::Handle(inner) => write!(f, "MultiAddress::Handle({})", String::from_utf8_lossy(inner)),
             _ => write!(f, "{:?}", self),
         }
     }
@@ -68,19 +71,33 @@ impl<AccountId: Default, AccountIndex> Default for MultiAddress<AccountId, Accou
     }
 }
 
+/// Resolves a handle from `MultiAddress::Handle` to an account id.
+pub trait ResolveHandle<AccountId> {
+    fn resolve_handle(handle: &[u8]) -> Option<AccountId>;
+}
+
+impl<AccountId> ResolveHandle<AccountId> for () {
+    fn resolve_handle(_handle: &[u8]) -> Option<AccountId> {
+        None
+    }
+}
+
 /// A lookup implementation returning the `AccountId` from a `MultiAddress`.
-pub struct AccountIdLookup<AccountId, AccountIndex>(PhantomData<(AccountId, AccountIndex)>);
-impl<AccountId, AccountIndex> StaticLookup for AccountIdLookup<AccountId, AccountIndex>
+/// Handles are resolved with `Handles`, all other formats except `Id` are not supported.
+pub struct AccountIdLookup<AccountId, AccountIndex, Handles = ()>(PhantomData<(AccountId, AccountIndex, Handles)>);
+impl<AccountId, AccountIndex, Handles> StaticLookup for AccountIdLookup<AccountId, AccountIndex, Handles>
     where
         AccountId: Codec + Clone + PartialEq + Debug,
         AccountIndex: Codec + Clone + PartialEq + Debug,
         MultiAddress<AccountId, AccountIndex>: Codec,
+        Handles: ResolveHandle<AccountId>,
 {
     type Source = MultiAddress<AccountId, AccountIndex>;
     type Target = AccountId;
     fn lookup(x: Self::Source) -> Result<Self::Target, LookupError> {
         match x {
             MultiAddress::Id(i) => Ok(i),
+            MultiAddress::Handle(handle) => Handles::resolve_handle(&handle).ok_or(LookupError),
             _ => Err(LookupError),
         }
     }
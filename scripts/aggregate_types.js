@@ -5,6 +5,7 @@ const fs = require('fs');
 const path = require('path');
 
 const pallets = [
+  "blocklists",
   "donations",
   "faucets",
   "moderation",
@@ -29,8 +30,18 @@ const pallets = [
 // Types that are native to the runtime itself (i.e. come from lib.rs)
 // These specifics are from https://polkadot.js.org/api/start/types.extend.html#impact-on-extrinsics
 const runtimeTypeOverrides = {
-  "Address": "AccountId",
-  "LookupSource": "AccountId"
+  "Address": "MultiAddress",
+  "LookupSource": "MultiAddress",
+  "MultiAddress": {
+    "_enum": {
+      "Id": "AccountId",
+      "Index": "Null",
+      "Raw": "Bytes",
+      "Address32": "H256",
+      "Address20": "H160",
+      "Handle": "Text"
+    }
+  }
 }
 
 let allTypes = {